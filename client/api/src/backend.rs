@@ -55,6 +55,16 @@ pub trait Backend<Block: BlockT>: Send + Sync {
 
 	/// Get the hash of the latest substrate block fully indexed by the backend.
 	async fn latest_block_hash(&self) -> Result<Block::Hash, String>;
+
+	/// Returns reference to the chain-wide log bloom index, if the backend maintains one.
+	fn bloom_index(&self) -> Option<&dyn BloomIndexBackend<Block>> {
+		None
+	}
+
+	/// Returns reference to the contract creation index, if the backend maintains one.
+	fn contract_creation_index(&self) -> Option<&dyn ContractCreationIndexBackend<Block>> {
+		None
+	}
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -82,3 +92,58 @@ pub trait LogIndexerBackend<Block: BlockT>: Send + Sync {
 		topics: Vec<Vec<Option<H256>>>,
 	) -> Result<Vec<FilteredLog<Block>>, String>;
 }
+
+/// The chain-wide log bloom index interface.
+///
+/// The canonical chain is split in sections of [`BloomIndexBackend::section_size`] blocks. For
+/// each of the 2048 bits of a logs bloom, the index stores a bit vector per section flagging the
+/// blocks whose bloom has that bit set, so a filter only needs to look at a few vectors per
+/// section to find its candidate blocks.
+pub trait BloomIndexBackend<Block: BlockT>: Send + Sync {
+	/// Number of blocks in a section.
+	fn section_size(&self) -> u64;
+
+	/// Get the number and substrate hash of the last indexed block. All the canonical blocks
+	/// before it are indexed as well.
+	fn indexed_tip(&self) -> Result<Option<(u64, Block::Hash)>, String>;
+
+	/// Get the bit vector of a bloom bit over a section, the block at offset `i` of the section
+	/// being bit `i % 8` of byte `i / 8`. `None` if no block of the section has this bit set.
+	fn section_bits(&self, bit: usize, section: u64) -> Result<Option<Vec<u8>>, String>;
+}
+
+/// Returns the positions of the bits set in a logs bloom, as indexed by [`BloomIndexBackend`].
+pub fn bloom_bit_positions(bloom: &[u8]) -> impl Iterator<Item = usize> + '_ {
+	bloom.iter().enumerate().flat_map(|(index, byte)| {
+		(0..8)
+			.filter(move |bit| byte & (1 << bit) != 0)
+			.map(move |bit| index * 8 + bit)
+	})
+}
+
+/// Metadata of the creation of a contract.
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode)]
+pub struct ContractCreation {
+	/// Hash of the Ethereum transaction that created the contract.
+	pub transaction_hash: H256,
+	/// Number of the block including the transaction.
+	pub block_number: u64,
+	/// Address of the account that sent the transaction.
+	pub creator: H160,
+	/// Whether the contract was created by a CREATE frame of the transaction rather than by the
+	/// transaction itself.
+	pub is_internal: bool,
+}
+
+/// The contract creation index interface.
+pub trait ContractCreationIndexBackend<Block: BlockT>: Send + Sync {
+	/// Get the number and substrate hash of the last indexed block. All the canonical blocks
+	/// before it are indexed as well.
+	fn indexed_tip(&self) -> Result<Option<(u64, Block::Hash)>, String>;
+
+	/// Get the creation of a contract, along with the substrate hash of the block including it.
+	fn contract_creation(
+		&self,
+		address: H160,
+	) -> Result<Option<(Block::Hash, ContractCreation)>, String>;
+}
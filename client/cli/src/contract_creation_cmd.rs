@@ -0,0 +1,73 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use std::{marker::PhantomData, sync::Arc};
+
+// Substrate
+use sc_cli::{PruningParams, SharedParams};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+// Frontier
+use fc_storage::StorageOverride;
+
+/// Cli tool to build the contract creation index of the Frontier backend db for an
+/// existing chain.
+///
+/// The node keeps the index up to date while running, this avoids the catch up at startup.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ContractCreationBackfillCmd {
+	/// Shared parameters
+	#[command(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[command(flatten)]
+	pub pruning_params: PruningParams,
+}
+
+impl ContractCreationBackfillCmd {
+	pub fn run<B, C>(
+		&self,
+		client: Arc<C>,
+		storage_override: Arc<dyn StorageOverride<B>>,
+		backend: Arc<fc_db::kv::Backend<B, C>>,
+	) -> sc_cli::Result<()>
+	where
+		B: BlockT,
+		C: HeaderBackend<B>,
+	{
+		while fc_mapping_sync::kv::index_contract_creations(
+			client.as_ref(),
+			storage_override.as_ref(),
+			backend.as_ref(),
+		)? {}
+
+		let tip = backend.contract_creation().indexed_tip_number()?;
+		println!("Contract creation index built up to block {:?}", tip);
+		Ok(())
+	}
+}
+
+impl sc_cli::CliConfiguration for ContractCreationBackfillCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+}
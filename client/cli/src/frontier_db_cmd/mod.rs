@@ -19,7 +19,7 @@
 mod mapping_db;
 mod meta_db;
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
 pub(crate) mod utils;
 
 use std::{path::PathBuf, str::FromStr, sync::Arc};
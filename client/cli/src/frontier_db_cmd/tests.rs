@@ -43,7 +43,7 @@ use frontier_template_runtime::RuntimeApi;
 
 use crate::frontier_db_cmd::{Column, FrontierDbCmd, Operation};
 
-type OpaqueBlock =
+pub type OpaqueBlock =
 	Block<Header<u64, BlakeTwo256>, substrate_test_runtime_client::runtime::Extrinsic>;
 
 pub fn open_frontier_backend<Block: BlockT, C: HeaderBackend<Block>>(
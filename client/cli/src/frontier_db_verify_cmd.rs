@@ -0,0 +1,565 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+// Substrate
+use sc_cli::{PruningParams, SharedParams};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, One, Zero},
+	Digest,
+};
+// Frontier
+use fc_storage::StorageOverride;
+use fp_consensus::FindLogError;
+
+/// Cli tool to check the consistency of the mappings of the Frontier backend db against
+/// the canonical chain, e.g. after an unclean shutdown.
+///
+/// Prints a JSON report of the inconsistencies found and of the blocks which could not be
+/// verified, and fails if there is any inconsistency.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct FrontierDbVerifyCmd {
+	/// Shared parameters
+	#[command(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[command(flatten)]
+	pub pruning_params: PruningParams,
+}
+
+/// An inconsistency of the Frontier backend db.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Inconsistency {
+	/// Column of the entry, `block` or `transaction`.
+	pub column: &'static str,
+	/// Key of the entry, an Ethereum block or transaction hash.
+	pub key: String,
+	/// What is wrong with it.
+	pub description: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Report {
+	inconsistencies: Vec<Inconsistency>,
+	/// Synced blocks whose Ethereum block is not available in their state, e.g. pruned.
+	unverifiable_blocks: Vec<String>,
+}
+
+impl FrontierDbVerifyCmd {
+	pub fn run<B, C>(
+		&self,
+		client: Arc<C>,
+		storage_override: Arc<dyn StorageOverride<B>>,
+		backend: Arc<fc_db::kv::Backend<B, C>>,
+	) -> sc_cli::Result<()>
+	where
+		B: BlockT,
+		C: HeaderBackend<B>,
+	{
+		let best_number = client.info().best_number;
+		let mut report = Report {
+			inconsistencies: Vec::new(),
+			unverifiable_blocks: Vec::new(),
+		};
+		let mut number = Zero::zero();
+		while number <= best_number {
+			let Some(substrate_hash) = client.hash(number)? else {
+				break;
+			};
+			let header = client
+				.header(substrate_hash)?
+				.ok_or_else(|| format!("Missing header of block {substrate_hash:?}"))?;
+			match verify_block(
+				substrate_hash,
+				header.digest(),
+				storage_override.as_ref(),
+				backend.mapping(),
+			)? {
+				Some(inconsistencies) => report.inconsistencies.extend(inconsistencies),
+				None => report
+					.unverifiable_blocks
+					.push(format!("{substrate_hash:?}")),
+			}
+			number += One::one();
+		}
+
+		let json = serde_json::to_string_pretty(&report)
+			.map_err(|e| format!("Failed to serialize the report: {e}"))?;
+		println!("{json}");
+		if report.inconsistencies.is_empty() {
+			Ok(())
+		} else {
+			Err(format!(
+				"Found {} inconsistencies in the Frontier db",
+				report.inconsistencies.len()
+			)
+			.into())
+		}
+	}
+}
+
+/// Check the mappings of the canonical block `substrate_hash`, unless it is not synced yet:
+///
+/// - the block column maps the Keccak-256 hash of its Ethereum header to it,
+/// - the transaction column maps each of its transactions to it, at the Ethereum block hash
+///   of its header and at the index of the transaction in the block, so that the indices of
+///   a block are contiguous,
+/// - the Ethereum block hash of each transaction mapping is in the block column.
+///
+/// Returns `None` when the block has an Ethereum block, as its `digest` says, which is not
+/// available in its state.
+fn verify_block<B: BlockT>(
+	substrate_hash: B::Hash,
+	digest: &Digest,
+	storage_override: &dyn StorageOverride<B>,
+	mapping: &fc_db::kv::MappingDb<B>,
+) -> Result<Option<Vec<Inconsistency>>, String> {
+	let mut inconsistencies = Vec::new();
+	if !mapping.is_synced(&substrate_hash)? {
+		return Ok(Some(inconsistencies));
+	}
+	let Some(block) = storage_override.current_block(substrate_hash) else {
+		if has_ethereum_block(digest) {
+			return Ok(None);
+		}
+		// Synced blocks without an Ethereum block are only marked as such.
+		return Ok(Some(inconsistencies));
+	};
+
+	let ethereum_hash = block.header.hash();
+	if !mapping
+		.block_hash(&ethereum_hash)?
+		.is_some_and(|hashes| hashes.contains(&substrate_hash))
+	{
+		inconsistencies.push(Inconsistency {
+			column: "block",
+			key: format!("{ethereum_hash:?}"),
+			description: format!("Missing mapping to the synced block {substrate_hash:?}"),
+		});
+	}
+
+	for (index, transaction) in block.transactions.iter().enumerate() {
+		let transaction_hash = transaction.hash();
+		let metadata = mapping.transaction_metadata(&transaction_hash)?;
+		for meta in &metadata {
+			if mapping.block_hash(&meta.ethereum_block_hash)?.is_none() {
+				inconsistencies.push(Inconsistency {
+					column: "transaction",
+					key: format!("{transaction_hash:?}"),
+					description: format!(
+						"Mapped to the Ethereum block {:?}, which is not in the block column",
+						meta.ethereum_block_hash
+					),
+				});
+			}
+		}
+
+		let Some(meta) = metadata
+			.iter()
+			.find(|meta| meta.substrate_block_hash == substrate_hash)
+		else {
+			inconsistencies.push(Inconsistency {
+				column: "transaction",
+				key: format!("{transaction_hash:?}"),
+				description: format!("Missing mapping to the synced block {substrate_hash:?}"),
+			});
+			continue;
+		};
+		if meta.ethereum_block_hash != ethereum_hash {
+			inconsistencies.push(Inconsistency {
+				column: "transaction",
+				key: format!("{transaction_hash:?}"),
+				description: format!(
+					"Mapped to the Ethereum block {:?}, the Keccak-256 hash of the header of \
+					block {substrate_hash:?} is {ethereum_hash:?}",
+					meta.ethereum_block_hash
+				),
+			});
+		}
+		if meta.ethereum_index as usize != index {
+			inconsistencies.push(Inconsistency {
+				column: "transaction",
+				key: format!("{transaction_hash:?}"),
+				description: format!(
+					"Mapped at index {}, it is at index {index} of block {substrate_hash:?}",
+					meta.ethereum_index
+				),
+			});
+		}
+	}
+
+	Ok(Some(inconsistencies))
+}
+
+/// Whether the block of `digest` has an Ethereum block, also when the digest is invalid with
+/// several Frontier logs.
+fn has_ethereum_block(digest: &Digest) -> bool {
+	!matches!(fp_consensus::find_log(digest), Err(FindLogError::NotFound))
+}
+
+impl sc_cli::CliConfiguration for FrontierDbVerifyCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{collections::HashMap, path::Path};
+
+	use ethereum::{BlockV2, TransactionV2};
+	use ethereum_types::{H160, H256, H64, U256};
+	use scale_codec::Encode;
+	use tempfile::tempdir;
+	// Substrate
+	use sc_cli::DatabasePruningMode;
+	use sp_runtime::{DigestItem, Permill};
+	use substrate_test_runtime_client::{DefaultTestClientBuilderExt, TestClientBuilder};
+	// Frontier
+	use fc_db::kv::MappingCommitment;
+	use fp_consensus::{PostLog, FRONTIER_ENGINE_ID};
+	use fp_rpc::TransactionStatus;
+	use frontier_template_runtime::RuntimeApi;
+
+	use super::*;
+	use crate::frontier_db_cmd::tests::{open_frontier_backend, OpaqueBlock};
+
+	/// Serves the Ethereum blocks of the given Substrate blocks.
+	struct BlockOverride(HashMap<H256, BlockV2>);
+
+	impl StorageOverride<OpaqueBlock> for BlockOverride {
+		fn account_code_at(&self, _at: H256, _address: H160) -> Option<Vec<u8>> {
+			None
+		}
+		fn account_storage_at(&self, _at: H256, _address: H160, _index: U256) -> Option<H256> {
+			None
+		}
+		fn current_block(&self, at: H256) -> Option<BlockV2> {
+			self.0.get(&at).cloned()
+		}
+		fn current_receipts(&self, _at: H256) -> Option<Vec<ethereum::ReceiptV3>> {
+			None
+		}
+		fn current_transaction_statuses(&self, _at: H256) -> Option<Vec<TransactionStatus>> {
+			None
+		}
+		fn elasticity(&self, _at: H256) -> Option<Permill> {
+			None
+		}
+		fn is_eip1559(&self, _at: H256) -> bool {
+			false
+		}
+		fn current_withdrawals(&self, _at: H256) -> Option<Vec<fp_rpc::Withdrawal>> {
+			None
+		}
+	}
+
+	fn transaction(nonce: u64) -> TransactionV2 {
+		TransactionV2::Legacy(ethereum::LegacyTransaction {
+			nonce: U256::from(nonce),
+			gas_price: U256::one(),
+			gas_limit: U256::from(21_000),
+			action: ethereum::TransactionAction::Create,
+			value: U256::zero(),
+			input: vec![],
+			signature: ethereum::TransactionSignature::new(
+				38,
+				H256::repeat_byte(0x01),
+				H256::repeat_byte(0x02),
+			)
+			.unwrap(),
+		})
+	}
+
+	fn ethereum_block(transactions: Vec<TransactionV2>) -> BlockV2 {
+		let partial_header = ethereum::PartialHeader {
+			parent_hash: H256::zero(),
+			beneficiary: H160::zero(),
+			state_root: H256::zero(),
+			receipts_root: H256::zero(),
+			logs_bloom: Default::default(),
+			difficulty: U256::zero(),
+			number: U256::zero(),
+			gas_limit: U256::zero(),
+			gas_used: U256::zero(),
+			timestamp: 0,
+			extra_data: vec![],
+			mix_hash: H256::zero(),
+			nonce: H64::zero(),
+		};
+		BlockV2::new(partial_header, transactions, vec![])
+	}
+
+	fn cmd() -> FrontierDbVerifyCmd {
+		FrontierDbVerifyCmd {
+			shared_params: SharedParams {
+				chain: None,
+				dev: true,
+				base_path: None,
+				log: vec![],
+				disable_log_color: true,
+				enable_log_reloading: true,
+				tracing_targets: None,
+				tracing_receiver: sc_cli::arg_enums::TracingReceiver::Log,
+				detailed_log_output: false,
+			},
+			pruning_params: PruningParams {
+				state_pruning: Some(DatabasePruningMode::Archive),
+				blocks_pruning: DatabasePruningMode::Archive,
+			},
+		}
+	}
+
+	/// Verify the mappings written by `write` for the genesis block, whose Ethereum block has
+	/// the given transactions.
+	fn verify(
+		transactions: Vec<TransactionV2>,
+		write: impl FnOnce(&Path, H256, &BlockV2),
+	) -> (Vec<Inconsistency>, sc_cli::Result<()>) {
+		let tmp = tempdir().expect("create a temporary directory");
+		let (client, _) =
+			TestClientBuilder::new().build_with_native_executor::<RuntimeApi, _>(None);
+		let client = Arc::new(client);
+		let genesis_hash = client.info().genesis_hash;
+		let block = ethereum_block(transactions);
+		write(tmp.path(), genesis_hash, &block);
+
+		let backend = open_frontier_backend::<OpaqueBlock, _>(client.clone(), tmp.path().into())
+			.expect("a temporary db was created");
+		let storage_override = Arc::new(BlockOverride(HashMap::from([(genesis_hash, block)])));
+		let inconsistencies = verify_block(
+			genesis_hash,
+			&Digest::default(),
+			storage_override.as_ref(),
+			backend.mapping(),
+		)
+		.unwrap()
+		.expect("the Ethereum block is available");
+		let result = cmd().run(client, storage_override, backend);
+		(inconsistencies, result)
+	}
+
+	/// Write `commitments` to the Frontier db at `path`.
+	fn write_hashes(path: &Path, commitments: Vec<MappingCommitment<OpaqueBlock>>) {
+		let (client, _) =
+			TestClientBuilder::new().build_with_native_executor::<RuntimeApi, _>(None);
+		let backend = open_frontier_backend::<OpaqueBlock, _>(Arc::new(client), path.into())
+			.expect("a temporary db was created");
+		for commitment in commitments {
+			backend.mapping().write_hashes(commitment).unwrap();
+		}
+	}
+
+	#[test]
+	fn consistent_mappings_pass() {
+		let (inconsistencies, result) = verify(
+			vec![transaction(0), transaction(1)],
+			|path, genesis_hash, block| {
+				write_hashes(
+					path,
+					vec![MappingCommitment {
+						block_hash: genesis_hash,
+						ethereum_block_hash: block.header.hash(),
+						ethereum_transaction_hashes: block
+							.transactions
+							.iter()
+							.map(|transaction| transaction.hash())
+							.collect(),
+					}],
+				)
+			},
+		);
+		assert_eq!(inconsistencies, vec![]);
+		assert!(result.is_ok());
+	}
+
+	#[test]
+	fn unsynced_blocks_are_skipped() {
+		let (inconsistencies, result) = verify(vec![transaction(0)], |_, _, _| ());
+		assert_eq!(inconsistencies, vec![]);
+		assert!(result.is_ok());
+	}
+
+	#[test]
+	fn missing_block_mapping_is_reported() {
+		let mut ethereum_hash = H256::zero();
+		let (inconsistencies, result) = verify(vec![], |path, genesis_hash, block| {
+			ethereum_hash = block.header.hash();
+			// Synced under another Ethereum block hash.
+			write_hashes(
+				path,
+				vec![MappingCommitment {
+					block_hash: genesis_hash,
+					ethereum_block_hash: H256::repeat_byte(0xee),
+					ethereum_transaction_hashes: vec![],
+				}],
+			)
+		});
+		assert_eq!(inconsistencies.len(), 1);
+		assert_eq!(inconsistencies[0].column, "block");
+		assert_eq!(inconsistencies[0].key, format!("{ethereum_hash:?}"));
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn wrong_transaction_index_is_reported() {
+		let mut substrate_hash = H256::zero();
+		let (inconsistencies, result) = verify(
+			vec![transaction(0), transaction(1)],
+			|path, genesis_hash, block| {
+				substrate_hash = genesis_hash;
+				let mut transaction_hashes: Vec<_> = block
+					.transactions
+					.iter()
+					.map(|transaction| transaction.hash())
+					.collect();
+				transaction_hashes.reverse();
+				write_hashes(
+					path,
+					vec![MappingCommitment {
+						block_hash: genesis_hash,
+						ethereum_block_hash: block.header.hash(),
+						ethereum_transaction_hashes: transaction_hashes,
+					}],
+				)
+			},
+		);
+		assert_eq!(
+			inconsistencies,
+			vec![
+				Inconsistency {
+					column: "transaction",
+					key: format!("{:?}", transaction(0).hash()),
+					description: format!(
+						"Mapped at index 1, it is at index 0 of block {substrate_hash:?}"
+					),
+				},
+				Inconsistency {
+					column: "transaction",
+					key: format!("{:?}", transaction(1).hash()),
+					description: format!(
+						"Mapped at index 0, it is at index 1 of block {substrate_hash:?}"
+					),
+				},
+			]
+		);
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn dangling_transaction_mapping_is_reported() {
+		let other_ethereum_hash = H256::repeat_byte(0xee);
+		let (inconsistencies, result) =
+			verify(vec![transaction(0)], |path, genesis_hash, block| {
+				// The transaction is also mapped to a block of another fork.
+				write_hashes(
+					path,
+					vec![
+						MappingCommitment {
+							block_hash: genesis_hash,
+							ethereum_block_hash: block.header.hash(),
+							ethereum_transaction_hashes: vec![transaction(0).hash()],
+						},
+						MappingCommitment {
+							block_hash: H256::repeat_byte(0x99),
+							ethereum_block_hash: other_ethereum_hash,
+							ethereum_transaction_hashes: vec![transaction(0).hash()],
+						},
+					],
+				);
+				// Lose the block mapping of the other fork, as an unclean shutdown could.
+				let db = kvdb_rocksdb::Database::open(
+					&kvdb_rocksdb::DatabaseConfig::with_columns(4),
+					path,
+				)
+				.expect("the temporary db is closed");
+				let db: Arc<dyn fc_db::kv::Database<fc_db::kv::DbHash>> =
+					sp_database::as_database(db);
+				let mut db_transaction = sp_database::Transaction::new();
+				// The block mapping column of the kv backend.
+				db_transaction.remove(1, other_ethereum_hash.as_bytes());
+				db.commit(db_transaction).unwrap();
+			});
+		assert_eq!(
+			inconsistencies,
+			vec![Inconsistency {
+				column: "transaction",
+				key: format!("{:?}", transaction(0).hash()),
+				description: format!(
+					"Mapped to the Ethereum block {other_ethereum_hash:?}, which is not in the \
+					block column"
+				),
+			}]
+		);
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn blocks_missing_their_ethereum_block_are_unverifiable() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let (client, _) =
+			TestClientBuilder::new().build_with_native_executor::<RuntimeApi, _>(None);
+		let client = Arc::new(client);
+		let genesis_hash = client.info().genesis_hash;
+		write_hashes(
+			tmp.path(),
+			vec![MappingCommitment {
+				block_hash: genesis_hash,
+				ethereum_block_hash: H256::repeat_byte(0xee),
+				ethereum_transaction_hashes: vec![],
+			}],
+		);
+		let backend = open_frontier_backend::<OpaqueBlock, _>(client, tmp.path().into())
+			.expect("a temporary db was created");
+		// The state of the block, here its Ethereum block, is not available.
+		let storage_override = BlockOverride(HashMap::new());
+
+		let ethereum_digest = Digest {
+			logs: vec![DigestItem::Consensus(
+				FRONTIER_ENGINE_ID,
+				PostLog::BlockHash(H256::repeat_byte(0xee)).encode(),
+			)],
+		};
+		assert_eq!(
+			verify_block(
+				genesis_hash,
+				&ethereum_digest,
+				&storage_override,
+				backend.mapping()
+			),
+			Ok(None)
+		);
+		assert_eq!(
+			verify_block(
+				genesis_hash,
+				&Digest::default(),
+				&storage_override,
+				backend.mapping()
+			),
+			Ok(Some(vec![]))
+		);
+	}
+}
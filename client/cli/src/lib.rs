@@ -18,6 +18,12 @@
 
 #![warn(unused_crate_dependencies)]
 
+mod contract_creation_cmd;
 mod frontier_db_cmd;
+mod frontier_db_verify_cmd;
 
-pub use self::frontier_db_cmd::FrontierDbCmd;
+pub use self::{
+	contract_creation_cmd::ContractCreationBackfillCmd,
+	frontier_db_cmd::FrontierDbCmd,
+	frontier_db_verify_cmd::{FrontierDbVerifyCmd, Inconsistency},
+};
@@ -0,0 +1,300 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+	collections::{btree_map::Entry, BTreeMap},
+	sync::Arc,
+};
+
+use scale_codec::Encode;
+// Substrate
+use sp_runtime::traits::Block as BlockT;
+// Frontier
+use fc_api::bloom_bit_positions;
+
+use super::{columns, indexed_column::IndexedColumn, static_keys, Database, DbHash};
+
+/// Number of blocks in a bloom bits section.
+pub const SECTION_SIZE: u64 = 4096;
+const SECTION_BYTES: usize = (SECTION_SIZE / 8) as usize;
+/// Number of bits of a logs bloom.
+const BLOOM_BIT_COUNT: usize = 2048;
+
+/// A canonical block to add to the bloom index.
+#[derive(Debug)]
+pub struct BloomBitsCommitment<Block: BlockT> {
+	pub block_number: u64,
+	pub block_hash: Block::Hash,
+	/// Logs bloom of the Ethereum block, empty if the block has none.
+	pub logs_bloom: Vec<u8>,
+}
+
+/// Chain-wide log bloom index, stored in the meta column.
+pub struct BloomBitsDb<Block> {
+	index: IndexedColumn<Block, ()>,
+}
+
+impl<Block: BlockT> BloomBitsDb<Block> {
+	pub(super) fn new(db: Arc<dyn Database<DbHash>>) -> Self {
+		Self {
+			index: IndexedColumn::new(
+				db,
+				"Bloom index",
+				static_keys::BLOOM_BITS_TIP,
+				static_keys::BLOOM_BITS_BLOCK,
+			),
+		}
+	}
+
+	fn bits_key(bit: usize, section: u64) -> Vec<u8> {
+		(static_keys::BLOOM_BITS, bit as u16, section).encode()
+	}
+
+	/// Get the substrate hash of the block indexed at the given number.
+	pub fn indexed_block_hash(&self, number: u64) -> Result<Option<Block::Hash>, String> {
+		self.index.indexed_block_hash(number)
+	}
+
+	/// Get the number of the last indexed block.
+	pub fn indexed_tip_number(&self) -> Result<Option<u64>, String> {
+		self.index.indexed_tip_number()
+	}
+
+	fn section_bits(&self, bit: usize, section: u64) -> Result<Option<Vec<u8>>, String> {
+		Ok(self
+			.index
+			.db
+			.get(columns::META, &Self::bits_key(bit, section)))
+	}
+
+	/// Add the given blocks on top of the indexed chain. The blocks must be contiguous and
+	/// start right after the current tip.
+	pub fn write_blocks(&self, commitments: Vec<BloomBitsCommitment<Block>>) -> Result<(), String> {
+		let mut batch = self.index.write_batch()?;
+		let mut updated: BTreeMap<(usize, u64), Vec<u8>> = BTreeMap::new();
+
+		for commitment in commitments {
+			batch.push(commitment.block_number, commitment.block_hash, &())?;
+			let section = commitment.block_number / SECTION_SIZE;
+			let offset = (commitment.block_number % SECTION_SIZE) as usize;
+			for bit in bloom_bit_positions(&commitment.logs_bloom) {
+				let bits = match updated.entry((bit, section)) {
+					Entry::Occupied(entry) => entry.into_mut(),
+					Entry::Vacant(entry) => entry.insert(
+						self.section_bits(bit, section)?
+							.unwrap_or_else(|| vec![0; SECTION_BYTES]),
+					),
+				};
+				bits[offset / 8] |= 1 << (offset % 8);
+			}
+		}
+
+		for ((bit, section), bits) in updated {
+			batch
+				.transaction
+				.set(columns::META, &Self::bits_key(bit, section), &bits);
+		}
+
+		batch.commit()
+	}
+
+	/// Remove the indexed tip, after it was retracted from the canonical chain.
+	///
+	/// If the logs bloom of the retracted block is not known anymore its bits stay set, which
+	/// only yields false positive candidates.
+	pub fn revert_tip(&self, logs_bloom: &[u8]) -> Result<(), String> {
+		self.index.revert_tip(|transaction, number, _| {
+			let section = number / SECTION_SIZE;
+			let offset = (number % SECTION_SIZE) as usize;
+			for bit in bloom_bit_positions(logs_bloom) {
+				if let Some(mut bits) = self.section_bits(bit, section)? {
+					bits[offset / 8] &= !(1 << (offset % 8));
+					transaction.set(columns::META, &Self::bits_key(bit, section), &bits);
+				}
+			}
+			Ok(())
+		})
+	}
+
+	/// Forget the indexed tip and remove the bit vectors of the indexed sections, so that the
+	/// index is rebuilt from genesis.
+	///
+	/// Used when the index is disabled, as it would go stale over reorgs.
+	pub fn reset(&self) -> Result<(), String> {
+		self.index.reset(|transaction, tip| {
+			let Some(tip) = tip else {
+				return Ok(());
+			};
+			for section in 0..=tip / SECTION_SIZE {
+				for bit in 0..BLOOM_BIT_COUNT {
+					transaction.remove(columns::META, &Self::bits_key(bit, section));
+				}
+			}
+			Ok(())
+		})
+	}
+}
+
+impl<Block: BlockT> fc_api::BloomIndexBackend<Block> for BloomBitsDb<Block> {
+	fn section_size(&self) -> u64 {
+		SECTION_SIZE
+	}
+
+	fn indexed_tip(&self) -> Result<Option<(u64, Block::Hash)>, String> {
+		self.index.indexed_tip()
+	}
+
+	fn section_bits(&self, bit: usize, section: u64) -> Result<Option<Vec<u8>>, String> {
+		BloomBitsDb::section_bits(self, bit, section)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use fc_api::BloomIndexBackend;
+	use sp_core::H256;
+	use sp_runtime::{generic::Header, traits::BlakeTwo256};
+
+	type OpaqueBlock = sp_runtime::generic::Block<
+		Header<u64, BlakeTwo256>,
+		substrate_test_runtime_client::runtime::Extrinsic,
+	>;
+
+	fn bloom_bits_db() -> BloomBitsDb<OpaqueBlock> {
+		BloomBitsDb::new(Arc::new(sp_database::MemDb::default()))
+	}
+
+	fn commitment(block_number: u64, bits: &[usize]) -> BloomBitsCommitment<OpaqueBlock> {
+		let mut logs_bloom = vec![0u8; 256];
+		for bit in bits {
+			logs_bloom[bit / 8] |= 1 << (bit % 8);
+		}
+		BloomBitsCommitment {
+			block_number,
+			block_hash: H256::repeat_byte((block_number as u8).wrapping_add(1)),
+			logs_bloom,
+		}
+	}
+
+	#[test]
+	fn write_blocks_sets_section_bits() {
+		let bloom_bits = bloom_bits_db();
+		bloom_bits
+			.write_blocks(vec![
+				commitment(0, &[]),
+				commitment(1, &[3, 2047]),
+				commitment(2, &[3]),
+			])
+			.expect("blocks are indexed");
+
+		assert_eq!(
+			bloom_bits.indexed_tip().unwrap(),
+			Some((2, H256::repeat_byte(3)))
+		);
+		let bits = BloomIndexBackend::section_bits(&bloom_bits, 3, 0)
+			.unwrap()
+			.expect("bit 3 is set");
+		assert_eq!(bits.len(), SECTION_BYTES);
+		assert_eq!(bits[0], 0b110);
+		let bits = BloomIndexBackend::section_bits(&bloom_bits, 2047, 0)
+			.unwrap()
+			.expect("bit 2047 is set");
+		assert_eq!(bits[0], 0b010);
+		assert_eq!(
+			BloomIndexBackend::section_bits(&bloom_bits, 4, 0).unwrap(),
+			None
+		);
+	}
+
+	#[test]
+	fn write_blocks_must_be_contiguous() {
+		let bloom_bits = bloom_bits_db();
+		bloom_bits
+			.write_blocks(vec![commitment(0, &[1])])
+			.expect("block is indexed");
+
+		assert!(bloom_bits.write_blocks(vec![commitment(2, &[1])]).is_err());
+		assert_eq!(bloom_bits.indexed_tip_number().unwrap(), Some(0));
+	}
+
+	#[test]
+	fn revert_tip_clears_section_bits() {
+		let bloom_bits = bloom_bits_db();
+		bloom_bits
+			.write_blocks(vec![commitment(0, &[5]), commitment(1, &[5, 6])])
+			.expect("blocks are indexed");
+
+		bloom_bits
+			.revert_tip(&commitment(1, &[5, 6]).logs_bloom)
+			.expect("tip is reverted");
+
+		assert_eq!(
+			bloom_bits.indexed_tip().unwrap(),
+			Some((0, H256::repeat_byte(1)))
+		);
+		assert_eq!(bloom_bits.indexed_block_hash(1).unwrap(), None);
+		let bits = BloomIndexBackend::section_bits(&bloom_bits, 5, 0)
+			.unwrap()
+			.expect("bit 5 is still set by block 0");
+		assert_eq!(bits[0], 0b01);
+		let bits = BloomIndexBackend::section_bits(&bloom_bits, 6, 0)
+			.unwrap()
+			.expect("vector is kept");
+		assert_eq!(bits[0], 0);
+
+		// The next block is indexed on top of the new tip.
+		bloom_bits
+			.write_blocks(vec![commitment(1, &[6])])
+			.expect("block is indexed");
+		assert_eq!(bloom_bits.indexed_tip_number().unwrap(), Some(1));
+	}
+
+	#[test]
+	fn reset_removes_section_bits() {
+		let bloom_bits = bloom_bits_db();
+		let mut commitments = (0..=SECTION_SIZE)
+			.map(|number| commitment(number, &[]))
+			.collect::<Vec<_>>();
+		commitments[1] = commitment(1, &[7]);
+		commitments[SECTION_SIZE as usize] = commitment(SECTION_SIZE, &[7, 2047]);
+		bloom_bits
+			.write_blocks(commitments)
+			.expect("blocks are indexed");
+
+		bloom_bits.reset().expect("index is reset");
+		assert_eq!(bloom_bits.indexed_tip().unwrap(), None);
+		for (bit, section) in [(7, 0), (7, 1), (2047, 1)] {
+			assert_eq!(
+				BloomIndexBackend::section_bits(&bloom_bits, bit, section).unwrap(),
+				None
+			);
+		}
+
+		// Rebuilt from genesis without the bits of the previous chain.
+		bloom_bits
+			.write_blocks(vec![commitment(0, &[]), commitment(1, &[8])])
+			.expect("blocks are indexed again");
+		assert_eq!(
+			BloomIndexBackend::section_bits(&bloom_bits, 7, 0).unwrap(),
+			None
+		);
+		bloom_bits.reset().expect("index is reset again");
+		bloom_bits.reset().expect("nothing to reset");
+	}
+}
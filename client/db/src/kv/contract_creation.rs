@@ -0,0 +1,240 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use scale_codec::{Decode, Encode};
+// Substrate
+use sp_core::H160;
+use sp_runtime::traits::Block as BlockT;
+// Frontier
+use fc_api::ContractCreation;
+
+use super::{columns, indexed_column::IndexedColumn, static_keys, Database, DbHash};
+
+/// A canonical block to add to the contract creation index.
+#[derive(Debug)]
+pub struct ContractCreationCommitment<Block: BlockT> {
+	pub block_number: u64,
+	pub block_hash: Block::Hash,
+	/// Contracts created in the block, along with their creation metadata.
+	pub creations: Vec<(H160, ContractCreation)>,
+}
+
+/// Index of the creation of each contract, stored in the meta column.
+pub struct ContractCreationDb<Block> {
+	/// Indexed blocks, along with the contracts they created.
+	index: IndexedColumn<Block, Vec<H160>>,
+}
+
+impl<Block: BlockT> ContractCreationDb<Block> {
+	pub(super) fn new(db: Arc<dyn Database<DbHash>>) -> Self {
+		Self {
+			index: IndexedColumn::new(
+				db,
+				"Contract creation index",
+				static_keys::CONTRACT_CREATION_TIP,
+				static_keys::CONTRACT_CREATION_BLOCK,
+			),
+		}
+	}
+
+	fn creation_key(address: &H160) -> Vec<u8> {
+		(static_keys::CONTRACT_CREATION, address).encode()
+	}
+
+	/// Get the substrate hash of the block indexed at the given number.
+	pub fn indexed_block_hash(&self, number: u64) -> Result<Option<Block::Hash>, String> {
+		self.index.indexed_block_hash(number)
+	}
+
+	/// Get the number of the last indexed block.
+	pub fn indexed_tip_number(&self) -> Result<Option<u64>, String> {
+		self.index.indexed_tip_number()
+	}
+
+	fn creation(&self, address: &H160) -> Result<Option<(Block::Hash, ContractCreation)>, String> {
+		match self
+			.index
+			.db
+			.get(columns::META, &Self::creation_key(address))
+		{
+			Some(raw) => Ok(Some(
+				Decode::decode(&mut &raw[..]).map_err(|e| e.to_string())?,
+			)),
+			None => Ok(None),
+		}
+	}
+
+	/// Add the given blocks on top of the indexed chain. The blocks must be contiguous and
+	/// start right after the current tip.
+	///
+	/// A contract created again at the same address (e.g. after a self-destruct) points to
+	/// its latest creation.
+	pub fn write_blocks(
+		&self,
+		commitments: Vec<ContractCreationCommitment<Block>>,
+	) -> Result<(), String> {
+		let mut batch = self.index.write_batch()?;
+
+		for commitment in commitments {
+			let addresses = commitment
+				.creations
+				.iter()
+				.map(|(address, _)| *address)
+				.collect::<Vec<_>>();
+			batch.push(commitment.block_number, commitment.block_hash, &addresses)?;
+			for (address, creation) in commitment.creations {
+				batch.transaction.set(
+					columns::META,
+					&Self::creation_key(&address),
+					&(commitment.block_hash, creation).encode(),
+				);
+			}
+		}
+
+		batch.commit()
+	}
+
+	/// Remove the indexed tip, after it was retracted from the canonical chain.
+	///
+	/// A contract re-created by the retracted block loses its previous creation as well.
+	pub fn revert_tip(&self) -> Result<(), String> {
+		self.index.revert_tip(|transaction, _, indexed| {
+			let Some((block_hash, addresses)) = indexed else {
+				return Ok(());
+			};
+			for address in addresses {
+				// Only forget the creations that still point to the retracted block.
+				if let Some((hash, _)) = self.creation(&address)? {
+					if hash == block_hash {
+						transaction.remove(columns::META, &Self::creation_key(&address));
+					}
+				}
+			}
+			Ok(())
+		})
+	}
+}
+
+impl<Block: BlockT> fc_api::ContractCreationIndexBackend<Block> for ContractCreationDb<Block> {
+	fn indexed_tip(&self) -> Result<Option<(u64, Block::Hash)>, String> {
+		self.index.indexed_tip()
+	}
+
+	fn contract_creation(
+		&self,
+		address: H160,
+	) -> Result<Option<(Block::Hash, ContractCreation)>, String> {
+		self.creation(&address)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use fc_api::ContractCreationIndexBackend;
+	use sp_core::H256;
+	use sp_runtime::{generic::Header, traits::BlakeTwo256};
+
+	type OpaqueBlock = sp_runtime::generic::Block<
+		Header<u64, BlakeTwo256>,
+		substrate_test_runtime_client::runtime::Extrinsic,
+	>;
+
+	fn contract_creation_db() -> ContractCreationDb<OpaqueBlock> {
+		ContractCreationDb::new(Arc::new(sp_database::MemDb::default()))
+	}
+
+	fn commitment(
+		block_number: u64,
+		fork: u8,
+		contracts: &[u8],
+	) -> ContractCreationCommitment<OpaqueBlock> {
+		ContractCreationCommitment {
+			block_number,
+			block_hash: H256::repeat_byte(block_number as u8 + fork),
+			creations: contracts
+				.iter()
+				.map(|contract| {
+					(
+						H160::repeat_byte(*contract),
+						ContractCreation {
+							transaction_hash: H256::repeat_byte(*contract),
+							block_number,
+							creator: H160::repeat_byte(0xff),
+							is_internal: false,
+						},
+					)
+				})
+				.collect(),
+		}
+	}
+
+	#[test]
+	fn write_blocks_indexes_creations() {
+		let index = contract_creation_db();
+		index
+			.write_blocks(vec![commitment(0, 1, &[]), commitment(1, 1, &[1, 2])])
+			.expect("blocks are indexed");
+
+		assert_eq!(
+			index.indexed_tip().unwrap(),
+			Some((1, H256::repeat_byte(2)))
+		);
+		let (hash, creation) = index
+			.contract_creation(H160::repeat_byte(2))
+			.unwrap()
+			.expect("contract is indexed");
+		assert_eq!(hash, H256::repeat_byte(2));
+		assert_eq!(creation.transaction_hash, H256::repeat_byte(2));
+		assert_eq!(creation.block_number, 1);
+		assert_eq!(index.contract_creation(H160::repeat_byte(3)).unwrap(), None);
+
+		assert!(index.write_blocks(vec![commitment(3, 1, &[3])]).is_err());
+		assert_eq!(index.indexed_tip_number().unwrap(), Some(1));
+	}
+
+	#[test]
+	fn revert_tip_forgets_retracted_creations() {
+		let index = contract_creation_db();
+		index
+			.write_blocks(vec![commitment(0, 1, &[1]), commitment(1, 1, &[1, 2])])
+			.expect("blocks are indexed");
+
+		index.revert_tip().expect("tip is reverted");
+
+		assert_eq!(
+			index.indexed_tip().unwrap(),
+			Some((0, H256::repeat_byte(1)))
+		);
+		assert_eq!(index.contract_creation(H160::repeat_byte(2)).unwrap(), None);
+		// The contract re-created by the retracted block is removed as well.
+		assert_eq!(index.contract_creation(H160::repeat_byte(1)).unwrap(), None);
+
+		// The fork block is indexed on top of the new tip.
+		index
+			.write_blocks(vec![commitment(1, 2, &[2])])
+			.expect("block is indexed");
+		let (hash, _) = index
+			.contract_creation(H160::repeat_byte(2))
+			.unwrap()
+			.expect("contract is indexed");
+		assert_eq!(hash, H256::repeat_byte(3));
+	}
+}
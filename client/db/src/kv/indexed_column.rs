@@ -0,0 +1,296 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use parking_lot::{Mutex, MutexGuard};
+use scale_codec::{Decode, Encode};
+// Substrate
+use sp_database::Transaction;
+use sp_runtime::traits::Block as BlockT;
+
+use super::{columns, Database, DbHash};
+
+/// Index built block by block along the canonical chain, stored in the meta column.
+///
+/// Each indexed block is stored under `(block_prefix, number)` along with an `Entry` holding
+/// what is needed to revert it, and the number of the last indexed block under `tip_key`.
+pub struct IndexedColumn<Block, Entry> {
+	pub(super) db: Arc<dyn Database<DbHash>>,
+	write_lock: Arc<Mutex<()>>,
+	/// Name of the index, used in errors.
+	name: &'static str,
+	tip_key: &'static [u8],
+	block_prefix: &'static [u8],
+	_marker: PhantomData<(Block, Entry)>,
+}
+
+impl<Block: BlockT, Entry: Encode + Decode> IndexedColumn<Block, Entry> {
+	pub(super) fn new(
+		db: Arc<dyn Database<DbHash>>,
+		name: &'static str,
+		tip_key: &'static [u8],
+		block_prefix: &'static [u8],
+	) -> Self {
+		Self {
+			db,
+			write_lock: Arc::new(Mutex::new(())),
+			name,
+			tip_key,
+			block_prefix,
+			_marker: PhantomData,
+		}
+	}
+
+	fn block_key(&self, number: u64) -> Vec<u8> {
+		(self.block_prefix, number).encode()
+	}
+
+	/// Get the substrate hash and the entry of the block indexed at the given number.
+	pub(super) fn indexed_block(
+		&self,
+		number: u64,
+	) -> Result<Option<(Block::Hash, Entry)>, String> {
+		match self.db.get(columns::META, &self.block_key(number)) {
+			Some(raw) => Ok(Some(
+				Decode::decode(&mut &raw[..]).map_err(|e| e.to_string())?,
+			)),
+			None => Ok(None),
+		}
+	}
+
+	/// Get the substrate hash of the block indexed at the given number.
+	pub fn indexed_block_hash(&self, number: u64) -> Result<Option<Block::Hash>, String> {
+		Ok(self.indexed_block(number)?.map(|(hash, _)| hash))
+	}
+
+	/// Get the number of the last indexed block.
+	pub fn indexed_tip_number(&self) -> Result<Option<u64>, String> {
+		match self.db.get(columns::META, self.tip_key) {
+			Some(raw) => Ok(Some(u64::decode(&mut &raw[..]).map_err(|e| e.to_string())?)),
+			None => Ok(None),
+		}
+	}
+
+	/// Get the number and substrate hash of the last indexed block.
+	pub fn indexed_tip(&self) -> Result<Option<(u64, Block::Hash)>, String> {
+		let Some(number) = self.indexed_tip_number()? else {
+			return Ok(None);
+		};
+		Ok(self.indexed_block_hash(number)?.map(|hash| (number, hash)))
+	}
+
+	/// Start adding blocks on top of the indexed chain, holding the write lock until the batch is
+	/// committed or dropped.
+	pub(super) fn write_batch(&self) -> Result<IndexBatch<'_, Block, Entry>, String> {
+		let lock = self.write_lock.lock();
+		let next = self.indexed_tip_number()?.map_or(0, |tip| tip + 1);
+		Ok(IndexBatch {
+			index: self,
+			_lock: lock,
+			first: next,
+			next,
+			transaction: Transaction::new(),
+		})
+	}
+
+	/// Remove the indexed tip, after it was retracted from the canonical chain.
+	///
+	/// `revert` is given the number and the indexed hash and entry of the tip, to add the removal
+	/// of whatever else was indexed for it to the transaction.
+	pub(super) fn revert_tip(
+		&self,
+		revert: impl FnOnce(
+			&mut Transaction<DbHash>,
+			u64,
+			Option<(Block::Hash, Entry)>,
+		) -> Result<(), String>,
+	) -> Result<(), String> {
+		let _lock = self.write_lock.lock();
+
+		let Some(number) = self.indexed_tip_number()? else {
+			return Ok(());
+		};
+
+		let mut transaction = Transaction::new();
+		revert(&mut transaction, number, self.indexed_block(number)?)?;
+		transaction.remove(columns::META, &self.block_key(number));
+		match number.checked_sub(1) {
+			Some(tip) => transaction.set(columns::META, self.tip_key, &tip.encode()),
+			None => transaction.remove(columns::META, self.tip_key),
+		}
+
+		self.db.commit(transaction).map_err(|e| e.to_string())?;
+
+		Ok(())
+	}
+
+	/// Forget the indexed tip so that the index is rebuilt from genesis, `clear` being given the
+	/// number of the tip to add the removal of the indexed data to the transaction.
+	pub(super) fn reset(
+		&self,
+		clear: impl FnOnce(&mut Transaction<DbHash>, Option<u64>) -> Result<(), String>,
+	) -> Result<(), String> {
+		let _lock = self.write_lock.lock();
+
+		let mut transaction = Transaction::new();
+		clear(&mut transaction, self.indexed_tip_number()?)?;
+		transaction.remove(columns::META, self.tip_key);
+		self.db.commit(transaction).map_err(|e| e.to_string())?;
+
+		Ok(())
+	}
+}
+
+/// Blocks being added on top of an [`IndexedColumn`].
+pub(super) struct IndexBatch<'a, Block, Entry> {
+	index: &'a IndexedColumn<Block, Entry>,
+	_lock: MutexGuard<'a, ()>,
+	first: u64,
+	next: u64,
+	/// Transaction the index data of the pushed blocks is added to.
+	pub transaction: Transaction<DbHash>,
+}
+
+impl<'a, Block: BlockT, Entry: Encode + Decode> IndexBatch<'a, Block, Entry> {
+	/// Add the next block of the indexed chain along with its entry. The blocks must be
+	/// contiguous and start right after the current tip.
+	pub fn push(&mut self, number: u64, hash: Block::Hash, entry: &Entry) -> Result<(), String> {
+		if number != self.next {
+			return Err(format!(
+				"{} is not contiguous, expected block {} got {}",
+				self.index.name, self.next, number
+			));
+		}
+		self.transaction.set(
+			columns::META,
+			&self.index.block_key(number),
+			&(hash, entry).encode(),
+		);
+		self.next += 1;
+		Ok(())
+	}
+
+	/// Commit the batch, moving the tip to the last pushed block.
+	pub fn commit(mut self) -> Result<(), String> {
+		if self.next == self.first {
+			return Ok(());
+		}
+		self.transaction
+			.set(columns::META, self.index.tip_key, &(self.next - 1).encode());
+		self.index
+			.db
+			.commit(self.transaction)
+			.map_err(|e| e.to_string())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::H256;
+	use sp_runtime::{generic::Header, traits::BlakeTwo256};
+
+	type OpaqueBlock = sp_runtime::generic::Block<
+		Header<u64, BlakeTwo256>,
+		substrate_test_runtime_client::runtime::Extrinsic,
+	>;
+
+	fn indexed_column() -> IndexedColumn<OpaqueBlock, u32> {
+		IndexedColumn::new(
+			Arc::new(sp_database::MemDb::default()),
+			"Test index",
+			b"TEST_TIP",
+			b"TEST_BLOCK",
+		)
+	}
+
+	fn write(index: &IndexedColumn<OpaqueBlock, u32>, numbers: &[u64]) -> Result<(), String> {
+		let mut batch = index.write_batch()?;
+		for number in numbers {
+			batch.push(
+				*number,
+				H256::repeat_byte(*number as u8 + 1),
+				&(*number as u32),
+			)?;
+		}
+		batch.commit()
+	}
+
+	#[test]
+	fn batches_move_the_tip() {
+		let index = indexed_column();
+		assert_eq!(index.indexed_tip().unwrap(), None);
+
+		write(&index, &[]).expect("empty batch is committed");
+		assert_eq!(index.indexed_tip().unwrap(), None);
+
+		write(&index, &[0, 1]).expect("blocks are indexed");
+		write(&index, &[2]).expect("block is indexed");
+		assert_eq!(
+			index.indexed_tip().unwrap(),
+			Some((2, H256::repeat_byte(3)))
+		);
+		assert_eq!(
+			index.indexed_block(1).unwrap(),
+			Some((H256::repeat_byte(2), 1))
+		);
+
+		let error = write(&index, &[4]).unwrap_err();
+		assert_eq!(
+			error,
+			"Test index is not contiguous, expected block 3 got 4"
+		);
+		assert_eq!(index.indexed_tip_number().unwrap(), Some(2));
+		assert_eq!(index.indexed_block(4).unwrap(), None);
+	}
+
+	#[test]
+	fn revert_tip_hands_out_the_tip_entry() {
+		let index = indexed_column();
+		write(&index, &[0, 1]).expect("blocks are indexed");
+
+		let mut reverted = None;
+		index
+			.revert_tip(|_, number, indexed| {
+				reverted = Some((number, indexed));
+				Ok(())
+			})
+			.expect("tip is reverted");
+		assert_eq!(reverted, Some((1, Some((H256::repeat_byte(2), 1)))));
+		assert_eq!(index.indexed_tip_number().unwrap(), Some(0));
+		assert_eq!(index.indexed_block(1).unwrap(), None);
+
+		index.revert_tip(|_, _, _| Ok(())).expect("tip is reverted");
+		assert_eq!(index.indexed_tip_number().unwrap(), None);
+		index
+			.revert_tip(|_, _, _| panic!("nothing is indexed"))
+			.expect("nothing to revert");
+
+		write(&index, &[0]).expect("block is indexed again");
+		let mut cleared = None;
+		index
+			.reset(|_, tip| {
+				cleared = Some(tip);
+				Ok(())
+			})
+			.expect("index is reset");
+		assert_eq!(cleared, Some(Some(0)));
+		assert_eq!(index.indexed_tip_number().unwrap(), None);
+	}
+}
@@ -16,6 +16,9 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+mod bloom_bits;
+mod contract_creation;
+mod indexed_column;
 mod parity_db_adapter;
 mod upgrade;
 mod utils;
@@ -57,8 +60,19 @@ pub(crate) mod columns {
 	pub const SYNCED_MAPPING: u32 = 3;
 }
 
+pub use self::{
+	bloom_bits::{BloomBitsCommitment, BloomBitsDb, SECTION_SIZE as BLOOM_BITS_SECTION_SIZE},
+	contract_creation::{ContractCreationCommitment, ContractCreationDb},
+};
+
 pub mod static_keys {
 	pub const CURRENT_SYNCING_TIPS: &[u8] = b"CURRENT_SYNCING_TIPS";
+	pub const BLOOM_BITS_TIP: &[u8] = b"BLOOM_BITS_TIP";
+	pub const BLOOM_BITS_BLOCK: &[u8] = b"BLOOM_BITS_BLOCK";
+	pub const BLOOM_BITS: &[u8] = b"BLOOM_BITS";
+	pub const CONTRACT_CREATION_TIP: &[u8] = b"CONTRACT_CREATION_TIP";
+	pub const CONTRACT_CREATION_BLOCK: &[u8] = b"CONTRACT_CREATION_BLOCK";
+	pub const CONTRACT_CREATION: &[u8] = b"CONTRACT_CREATION";
 }
 
 #[derive(Clone)]
@@ -66,6 +80,8 @@ pub struct Backend<Block, C> {
 	client: Arc<C>,
 	meta: Arc<MetaDb<Block>>,
 	mapping: Arc<MappingDb<Block>>,
+	bloom_bits: Arc<BloomBitsDb<Block>>,
+	contract_creation: Arc<ContractCreationDb<Block>>,
 	log_indexer: LogIndexerBackend<Block>,
 }
 
@@ -93,6 +109,14 @@ impl<Block: BlockT, C: HeaderBackend<Block>> fc_api::Backend<Block> for Backend<
 	async fn latest_block_hash(&self) -> Result<Block::Hash, String> {
 		Ok(self.client.info().best_hash)
 	}
+
+	fn bloom_index(&self) -> Option<&dyn fc_api::BloomIndexBackend<Block>> {
+		Some(&*self.bloom_bits)
+	}
+
+	fn contract_creation_index(&self) -> Option<&dyn fc_api::ContractCreationIndexBackend<Block>> {
+		Some(&*self.contract_creation)
+	}
 }
 
 #[derive(Clone, Default)]
@@ -167,6 +191,8 @@ impl<Block: BlockT, C: HeaderBackend<Block>> Backend<Block, C> {
 				db: db.clone(),
 				_marker: PhantomData,
 			}),
+			bloom_bits: Arc::new(BloomBitsDb::new(db.clone())),
+			contract_creation: Arc::new(ContractCreationDb::new(db.clone())),
 			log_indexer: LogIndexerBackend(PhantomData),
 		})
 	}
@@ -178,6 +204,14 @@ impl<Block: BlockT, C: HeaderBackend<Block>> Backend<Block, C> {
 	pub fn meta(&self) -> &Arc<MetaDb<Block>> {
 		&self.meta
 	}
+
+	pub fn bloom_bits(&self) -> &Arc<BloomBitsDb<Block>> {
+		&self.bloom_bits
+	}
+
+	pub fn contract_creation(&self) -> &Arc<ContractCreationDb<Block>> {
+		&self.contract_creation
+	}
 }
 
 pub struct MetaDb<Block> {
@@ -0,0 +1,126 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, sync::Arc};
+
+// Substrate
+use sc_client_api::client::ImportNotifications;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+// Frontier
+use fc_storage::StorageOverride;
+
+use super::chain_index::{self, ChainIndex};
+
+/// Worker maintaining the chain-wide log bloom index of the key-value backend.
+///
+/// On an existing chain the index is first backfilled from genesis, then it follows the best
+/// chain, reverting the blocks retracted by reorgs.
+pub struct BloomBitsWorker<Block, C>(PhantomData<(Block, C)>);
+
+impl<Block: BlockT, C> BloomBitsWorker<Block, C>
+where
+	C: HeaderBackend<Block>,
+{
+	pub async fn run(
+		client: Arc<C>,
+		storage_override: Arc<dyn StorageOverride<Block>>,
+		frontier_backend: Arc<fc_db::kv::Backend<Block, C>>,
+		import_notifications: ImportNotifications<Block>,
+	) {
+		chain_index::follow_best_chain(
+			import_notifications,
+			BloomIndex::<Block>::LOG_TARGET,
+			BloomIndex::<Block>::NAME,
+			|| {
+				index_blocks(
+					client.as_ref(),
+					storage_override.as_ref(),
+					frontier_backend.as_ref(),
+				)
+			},
+		)
+		.await
+	}
+}
+
+/// The bloom index, reading the logs bloom of the indexed blocks from the runtime storage.
+struct BloomIndex<'a, Block: BlockT> {
+	storage_override: &'a dyn StorageOverride<Block>,
+	bloom_bits: &'a fc_db::kv::BloomBitsDb<Block>,
+}
+
+impl<'a, Block: BlockT> BloomIndex<'a, Block> {
+	fn logs_bloom(&self, hash: Block::Hash) -> Vec<u8> {
+		self.storage_override
+			.current_block(hash)
+			.map(|block| block.header.logs_bloom.as_bytes().to_vec())
+			.unwrap_or_default()
+	}
+}
+
+impl<'a, Block: BlockT> ChainIndex<Block> for BloomIndex<'a, Block> {
+	type Commitment = fc_db::kv::BloomBitsCommitment<Block>;
+
+	const LOG_TARGET: &'static str = "bloom-bits";
+	const NAME: &'static str = "bloom index";
+	const EMOJI: &'static str = "🌸";
+
+	fn indexed_tip_number(&self) -> Result<Option<u64>, String> {
+		self.bloom_bits.indexed_tip_number()
+	}
+
+	fn indexed_block_hash(&self, number: u64) -> Result<Option<Block::Hash>, String> {
+		self.bloom_bits.indexed_block_hash(number)
+	}
+
+	fn revert_tip(&self, indexed_hash: Option<Block::Hash>) -> Result<(), String> {
+		let logs_bloom = indexed_hash
+			.map(|hash| self.logs_bloom(hash))
+			.unwrap_or_default();
+		self.bloom_bits.revert_tip(&logs_bloom)
+	}
+
+	fn commitment(&self, block_number: u64, block_hash: Block::Hash) -> Self::Commitment {
+		fc_db::kv::BloomBitsCommitment {
+			block_number,
+			block_hash,
+			logs_bloom: self.logs_bloom(block_hash),
+		}
+	}
+
+	fn write_blocks(&self, commitments: Vec<Self::Commitment>) -> Result<(), String> {
+		self.bloom_bits.write_blocks(commitments)
+	}
+}
+
+/// Brings the bloom index in line with the best chain, indexing a batch of blocks.
+/// Returns whether there are blocks left to index.
+pub fn index_blocks<Block: BlockT, C: HeaderBackend<Block>>(
+	client: &C,
+	storage_override: &dyn StorageOverride<Block>,
+	frontier_backend: &fc_db::kv::Backend<Block, C>,
+) -> Result<bool, String> {
+	chain_index::index_blocks(
+		client,
+		&BloomIndex {
+			storage_override,
+			bloom_bits: frontier_backend.bloom_bits(),
+		},
+	)
+}
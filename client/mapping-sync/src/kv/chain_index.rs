@@ -0,0 +1,133 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use futures::prelude::*;
+// Substrate
+use sc_client_api::client::ImportNotifications;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
+
+/// Maximum number of blocks indexed in a single database transaction.
+const BATCH_SIZE: u64 = 1024;
+
+/// Index of the key-value backend built block by block along the best chain.
+pub(super) trait ChainIndex<Block: BlockT> {
+	/// What gets indexed for a block.
+	type Commitment;
+
+	/// Log target of the index.
+	const LOG_TARGET: &'static str;
+	/// Name of the index in logs.
+	const NAME: &'static str;
+	/// Prefix of the backfill progress logs.
+	const EMOJI: &'static str;
+
+	/// Get the number of the last indexed block.
+	fn indexed_tip_number(&self) -> Result<Option<u64>, String>;
+
+	/// Get the substrate hash of the block indexed at the given number.
+	fn indexed_block_hash(&self, number: u64) -> Result<Option<Block::Hash>, String>;
+
+	/// Remove the indexed tip, with the hash it was indexed with if known.
+	fn revert_tip(&self, indexed_hash: Option<Block::Hash>) -> Result<(), String>;
+
+	/// Build the index entry of a canonical block.
+	fn commitment(&self, block_number: u64, block_hash: Block::Hash) -> Self::Commitment;
+
+	/// Add the given blocks on top of the indexed chain.
+	fn write_blocks(&self, commitments: Vec<Self::Commitment>) -> Result<(), String>;
+}
+
+/// Brings the index in line with the best chain, indexing at most [`BATCH_SIZE`] blocks.
+/// Returns whether there are blocks left to index.
+pub(super) fn index_blocks<Block, C, I>(client: &C, index: &I) -> Result<bool, String>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block>,
+	I: ChainIndex<Block>,
+{
+	let best_number: u64 = client.info().best_number.unique_saturated_into();
+	let canonical_hash = |number: u64| -> Result<Option<Block::Hash>, String> {
+		client
+			.hash(number.unique_saturated_into())
+			.map_err(|e| format!("{:?}", e))
+	};
+
+	// Revert the indexed blocks retracted from the canonical chain. Only the unfinalized tail
+	// can be retracted, so this stops at the first block still canonical.
+	while let Some(number) = index.indexed_tip_number()? {
+		let indexed_hash = index.indexed_block_hash(number)?;
+		let canonical = number <= best_number
+			&& indexed_hash.is_some()
+			&& canonical_hash(number)? == indexed_hash;
+		if canonical {
+			break;
+		}
+		log::debug!(target: I::LOG_TARGET, "Reverting retracted block #{} from the {}", number, I::NAME);
+		index.revert_tip(indexed_hash)?;
+	}
+
+	let from = index.indexed_tip_number()?.map_or(0, |tip| tip + 1);
+	if from > best_number {
+		return Ok(false);
+	}
+	let to = best_number.min(from + BATCH_SIZE - 1);
+
+	let mut commitments = Vec::with_capacity((to - from + 1) as usize);
+	for block_number in from..=to {
+		let block_hash = canonical_hash(block_number)?
+			.ok_or_else(|| format!("Canonical block #{} not found", block_number))?;
+		commitments.push(index.commitment(block_number, block_hash));
+	}
+	index.write_blocks(commitments)?;
+
+	if to < best_number {
+		log::info!(
+			target: I::LOG_TARGET,
+			"{} Backfilling the {}: {} of {} blocks indexed",
+			I::EMOJI,
+			I::NAME,
+			to + 1,
+			best_number + 1,
+		);
+	}
+	Ok(to < best_number)
+}
+
+/// Keeps calling `index_blocks` on every block import, as long as it reports blocks left to
+/// index.
+pub(super) async fn follow_best_chain<Block: BlockT>(
+	mut import_notifications: ImportNotifications<Block>,
+	log_target: &'static str,
+	name: &'static str,
+	mut index_blocks: impl FnMut() -> Result<bool, String>,
+) {
+	loop {
+		match index_blocks() {
+			// More blocks to index, keep going.
+			Ok(true) => continue,
+			Ok(false) => (),
+			Err(e) => {
+				log::warn!(target: log_target, "Update of the {} failed with error {:?}, retrying.", name, e);
+			}
+		}
+		if import_notifications.next().await.is_none() {
+			return;
+		}
+	}
+}
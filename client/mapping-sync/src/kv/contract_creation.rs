@@ -0,0 +1,150 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, sync::Arc};
+
+// Substrate
+use sc_client_api::client::ImportNotifications;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+// Frontier
+use fc_api::ContractCreation;
+use fc_storage::StorageOverride;
+
+use super::chain_index::{self, ChainIndex};
+
+/// Worker maintaining the contract creation index of the key-value backend.
+///
+/// On an existing chain the index is first backfilled from genesis, then it follows the best
+/// chain, reverting the blocks retracted by reorgs.
+pub struct ContractCreationWorker<Block, C>(PhantomData<(Block, C)>);
+
+impl<Block: BlockT, C> ContractCreationWorker<Block, C>
+where
+	C: HeaderBackend<Block>,
+{
+	pub async fn run(
+		client: Arc<C>,
+		storage_override: Arc<dyn StorageOverride<Block>>,
+		frontier_backend: Arc<fc_db::kv::Backend<Block, C>>,
+		import_notifications: ImportNotifications<Block>,
+	) {
+		chain_index::follow_best_chain(
+			import_notifications,
+			ContractCreationIndex::<Block>::LOG_TARGET,
+			ContractCreationIndex::<Block>::NAME,
+			|| {
+				index_blocks(
+					client.as_ref(),
+					storage_override.as_ref(),
+					frontier_backend.as_ref(),
+				)
+			},
+		)
+		.await
+	}
+}
+
+/// The contract creation index, reading the creations of the indexed blocks from the runtime
+/// storage.
+struct ContractCreationIndex<'a, Block: BlockT> {
+	storage_override: &'a dyn StorageOverride<Block>,
+	contract_creation: &'a fc_db::kv::ContractCreationDb<Block>,
+}
+
+impl<'a, Block: BlockT> ChainIndex<Block> for ContractCreationIndex<'a, Block> {
+	type Commitment = fc_db::kv::ContractCreationCommitment<Block>;
+
+	const LOG_TARGET: &'static str = "contract-creation";
+	const NAME: &'static str = "contract creation index";
+	const EMOJI: &'static str = "🏗️";
+
+	fn indexed_tip_number(&self) -> Result<Option<u64>, String> {
+		self.contract_creation.indexed_tip_number()
+	}
+
+	fn indexed_block_hash(&self, number: u64) -> Result<Option<Block::Hash>, String> {
+		self.contract_creation.indexed_block_hash(number)
+	}
+
+	fn revert_tip(&self, _indexed_hash: Option<Block::Hash>) -> Result<(), String> {
+		self.contract_creation.revert_tip()
+	}
+
+	/// Builds the index entry of a block from the contracts its transactions successfully
+	/// created.
+	///
+	/// Only the creations made by the transactions themselves are known from the stored statuses
+	/// and receipts, contracts created by CREATE frames would need the block to be traced.
+	fn commitment(&self, block_number: u64, block_hash: Block::Hash) -> Self::Commitment {
+		let statuses = self
+			.storage_override
+			.current_transaction_statuses(block_hash)
+			.unwrap_or_default();
+		let receipts = self
+			.storage_override
+			.current_receipts(block_hash)
+			.unwrap_or_default();
+		let creations = statuses
+			.into_iter()
+			.zip(receipts)
+			.filter_map(|(status, receipt)| {
+				let status_code = match receipt {
+					ethereum::ReceiptV3::Legacy(d)
+					| ethereum::ReceiptV3::EIP2930(d)
+					| ethereum::ReceiptV3::EIP1559(d) => d.status_code,
+				};
+				let address = status.contract_address.filter(|_| status_code == 1)?;
+				Some((
+					address,
+					ContractCreation {
+						transaction_hash: status.transaction_hash,
+						block_number,
+						creator: status.from,
+						is_internal: false,
+					},
+				))
+			})
+			.collect();
+		fc_db::kv::ContractCreationCommitment {
+			block_number,
+			block_hash,
+			creations,
+		}
+	}
+
+	fn write_blocks(&self, commitments: Vec<Self::Commitment>) -> Result<(), String> {
+		self.contract_creation.write_blocks(commitments)
+	}
+}
+
+/// Brings the contract creation index in line with the best chain, indexing a batch of blocks.
+/// Returns whether there are blocks left to index.
+pub fn index_blocks<Block: BlockT, C: HeaderBackend<Block>>(
+	client: &C,
+	storage_override: &dyn StorageOverride<Block>,
+	frontier_backend: &fc_db::kv::Backend<Block, C>,
+) -> Result<bool, String> {
+	chain_index::index_blocks(
+		client,
+		&ContractCreationIndex {
+			storage_override,
+			contract_creation: frontier_backend.contract_creation(),
+		},
+	)
+}
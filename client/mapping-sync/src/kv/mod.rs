@@ -18,9 +18,16 @@
 
 #![allow(clippy::too_many_arguments)]
 
+mod bloom_bits;
+mod chain_index;
+mod contract_creation;
 mod worker;
 
-pub use worker::MappingSyncWorker;
+pub use self::{
+	bloom_bits::{index_blocks as index_bloom_bits, BloomBitsWorker},
+	contract_creation::{index_blocks as index_contract_creations, ContractCreationWorker},
+	worker::MappingSyncWorker,
+};
 
 use std::sync::Arc;
 
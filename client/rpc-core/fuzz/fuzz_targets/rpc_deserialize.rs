@@ -0,0 +1,66 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Deserializes arbitrary input as each of the RPC parameter types with handwritten
+//! deserialization.
+
+#![no_main]
+
+use fc_rpc_core::types::{
+	BlockNumberOrHash, Bytes, CallStateOverride, Filter, FilterAddress, Index, Topic,
+	TransactionRequest,
+};
+use libfuzzer_sys::fuzz_target;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Deserializing never panics. What deserializes serializes, and serializes the same once
+/// deserialized again.
+fn round_trip<T: DeserializeOwned + Serialize>(data: &[u8]) {
+	let Ok(value) = serde_json::from_slice::<T>(data) else {
+		return;
+	};
+	let serialized = serde_json::to_vec(&value).expect("Deserialized values serialize");
+	let value = serde_json::from_slice::<T>(&serialized)
+		.unwrap_or_else(|err| panic!("{} does not deserialize: {err}", show(&serialized)));
+	let reserialized = serde_json::to_vec(&value).expect("Deserialized values serialize");
+	assert_eq!(
+		show(&serialized),
+		show(&reserialized),
+		"Serialization is not stable"
+	);
+}
+
+/// Deserializing never panics, for the types which are only deserialized.
+fn deserialize<T: DeserializeOwned>(data: &[u8]) {
+	let _ = serde_json::from_slice::<T>(data);
+}
+
+fn show(json: &[u8]) -> String {
+	String::from_utf8_lossy(json).into_owned()
+}
+
+fuzz_target!(|data: &[u8]| {
+	round_trip::<BlockNumberOrHash>(data);
+	round_trip::<Bytes>(data);
+	round_trip::<Filter>(data);
+	round_trip::<FilterAddress>(data);
+	round_trip::<Index>(data);
+	round_trip::<Topic>(data);
+	deserialize::<CallStateOverride>(data);
+	deserialize::<TransactionRequest>(data);
+});
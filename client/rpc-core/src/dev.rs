@@ -0,0 +1,33 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Dev rpc interface.
+
+use ethereum_types::H160;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+use crate::types::Bytes;
+
+/// Signing methods of the dev accounts, only served in dev mode.
+#[rpc(server)]
+pub trait DevApi {
+	/// Signs EIP-712 typed data, given as a JSON object or a JSON string, with the dev account of
+	/// `address`. Returns the 65 bytes `r || s || v` signature, `v` being 27 or 28.
+	#[method(name = "eth_signTypedData_v4")]
+	fn sign_typed_data_v4(&self, address: H160, typed_data: serde_json::Value) -> RpcResult<Bytes>;
+}
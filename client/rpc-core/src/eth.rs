@@ -40,7 +40,8 @@ pub trait EthApi {
 	#[method(name = "eth_syncing")]
 	async fn syncing(&self) -> RpcResult<SyncStatus>;
 
-	/// Returns block author.
+	/// Returns the Ethereum address of the node's authority key, zero if the node is not an
+	/// authority.
 	#[method(name = "eth_coinbase")]
 	fn author(&self) -> RpcResult<H160>;
 
@@ -53,10 +54,9 @@ pub trait EthApi {
 	fn block_number(&self) -> RpcResult<U256>;
 
 	/// Returns the chain ID used for transaction signing at the
-	/// current best block. None is returned if not
-	/// available.
+	/// current best block.
 	#[method(name = "eth_chainId")]
-	fn chain_id(&self) -> RpcResult<Option<U64>>;
+	fn chain_id(&self) -> RpcResult<U64>;
 
 	// ########################################################################
 	// Block
@@ -144,6 +144,11 @@ pub trait EthApi {
 	#[method(name = "eth_getTransactionReceipt")]
 	async fn transaction_receipt(&self, hash: H256) -> RpcResult<Option<Receipt>>;
 
+	/// Returns the transactions ready in the pool, by decreasing priority fee between senders and
+	/// in nonce order for each sender.
+	#[method(name = "eth_pendingTransactions")]
+	fn pending_transactions(&self) -> RpcResult<Vec<Transaction>>;
+
 	// ########################################################################
 	// State
 	// ########################################################################
@@ -228,15 +233,17 @@ pub trait EthApi {
 	// Mining
 	// ########################################################################
 
-	/// Returns true if client is actively mining new blocks.
+	/// Returns true if client is actively mining new blocks, always false as Substrate chains
+	/// do not use proof of work.
 	#[method(name = "eth_mining")]
 	fn is_mining(&self) -> RpcResult<bool>;
 
-	/// Returns the number of hashes per second that the node is mining with.
+	/// Returns the number of hashes per second that the node is mining with, always zero.
 	#[method(name = "eth_hashrate")]
 	fn hashrate(&self) -> RpcResult<U256>;
 
-	/// Returns the hash of the current block, the seedHash, and the boundary condition to be met.
+	/// Returns the hash of the current block, the seedHash, and the boundary condition to be met,
+	/// always three zero hashes.
 	#[method(name = "eth_getWork")]
 	fn work(&self) -> RpcResult<Work>;
 
@@ -293,3 +300,14 @@ pub trait EthFilterApi {
 	#[method(name = "eth_getLogs")]
 	async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>>;
 }
+
+/// Paginated `eth_getLogs`, a non-standard extension.
+#[cfg(feature = "logs-pagination")]
+#[rpc(server)]
+pub trait EthLogsPageApi {
+	/// Returns the logs matching given filter object, at most as many as `eth_getLogs` returns.
+	/// Starts at `cursor` instead of the `fromBlock` of the filter when given. A truncated page
+	/// has the cursor of the next log, continuing deterministically as new blocks arrive.
+	#[method(name = "eth_getLogsPage")]
+	async fn logs_page(&self, filter: Filter, cursor: Option<LogCursor>) -> RpcResult<LogsPage>;
+}
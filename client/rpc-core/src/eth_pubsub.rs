@@ -26,10 +26,28 @@ use crate::types::pubsub;
 #[rpc(server)]
 pub trait EthPubSubApi {
 	/// Subscribe to Eth subscription.
+	///
+	/// With `resumable` set the subscription is resumable: its notifications carry a cursor and
+	/// are buffered by the server for a while, see `eth_resubscribe`. The returned subscription
+	/// id is its resume token.
 	#[subscription(
 		name = "eth_subscribe" => "eth_subscription",
 		unsubscribe = "eth_unsubscribe",
 		item = pubsub::Result
 	)]
-	fn subscribe(&self, kind: pubsub::Kind, params: Option<pubsub::Params>);
+	fn subscribe(
+		&self,
+		kind: pubsub::Kind,
+		params: Option<pubsub::Params>,
+		resumable: Option<bool>,
+	);
+
+	/// Resume the subscription whose `eth_subscribe` call returned `resume_token`, replaying the
+	/// buffered notifications after `last_seen_cursor` before continuing live.
+	#[subscription(
+		name = "eth_resubscribe" => "eth_subscription",
+		unsubscribe = "eth_unresubscribe",
+		item = pubsub::ResumableResult
+	)]
+	fn resubscribe(&self, resume_token: String, last_seen_cursor: u64);
 }
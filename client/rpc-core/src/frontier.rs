@@ -0,0 +1,62 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Frontier rpc interface.
+
+use ethereum_types::{H160, H256, U256};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+use crate::types::{
+	ContractCreation, EthereumBlockMapping, HashOrNumber, SubstrateBlockMapping,
+	SubstrateExtrinsicMapping,
+};
+
+/// Frontier rpc interface.
+#[rpc(server)]
+pub trait FrontierApi {
+	/// Returns the transaction that created the contract at the given address, or `None` if
+	/// the creation is not indexed.
+	#[method(name = "frontier_getContractCreation")]
+	fn contract_creation(&self, address: H160) -> RpcResult<Option<ContractCreation>>;
+
+	/// Returns the number of the earliest block whose state is available, older blocks can't
+	/// be traced or inspected on this node.
+	#[method(name = "frontier_earliestTraceableBlock")]
+	fn earliest_traceable_block(&self) -> RpcResult<U256>;
+
+	/// Returns the ethereum block of the given substrate block.
+	#[method(name = "frontier_substrateBlockToEthBlock")]
+	async fn substrate_block_to_eth_block(
+		&self,
+		substrate_hash: H256,
+	) -> RpcResult<EthereumBlockMapping>;
+
+	/// Returns the canonical substrate block of the given ethereum block.
+	#[method(name = "frontier_ethBlockToSubstrateBlock")]
+	async fn eth_block_to_substrate_block(
+		&self,
+		block: HashOrNumber,
+	) -> RpcResult<SubstrateBlockMapping>;
+
+	/// Returns the substrate block and the index of the extrinsic including the given
+	/// ethereum transaction.
+	#[method(name = "frontier_ethTxToSubstrateExtrinsic")]
+	async fn eth_tx_to_substrate_extrinsic(
+		&self,
+		transaction_hash: H256,
+	) -> RpcResult<SubstrateExtrinsicMapping>;
+}
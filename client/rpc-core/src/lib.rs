@@ -22,19 +22,29 @@
 pub mod types;
 
 mod debug;
+mod dev;
 mod eth;
 mod eth_pubsub;
+mod frontier;
+mod modules;
 mod net;
+mod personal;
 #[cfg(feature = "txpool")]
 mod txpool;
 mod web3;
 
+#[cfg(feature = "logs-pagination")]
+pub use self::eth::EthLogsPageApiServer;
 #[cfg(feature = "txpool")]
 pub use self::txpool::TxPoolApiServer;
 pub use self::{
 	debug::DebugApiServer,
+	dev::DevApiServer,
 	eth::{EthApiServer, EthFilterApiServer},
 	eth_pubsub::EthPubSubApiServer,
+	frontier::FrontierApiServer,
+	modules::ModulesApiServer,
 	net::NetApiServer,
+	personal::PersonalApiServer,
 	web3::Web3ApiServer,
 };
@@ -0,0 +1,38 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Personal rpc interface.
+
+use ethereum_types::H160;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+use crate::types::Bytes;
+
+/// Personal rpc interface, signing and recovering `eth_sign` style messages.
+#[rpc(server)]
+pub trait PersonalApi {
+	/// Signs `keccak256("\x19Ethereum Signed Message:\n" + len(message) + message)` with the dev
+	/// account of `address`. Returns the 65 bytes `r || s || v` signature, `v` being 27 or 28.
+	#[method(name = "personal_sign")]
+	fn sign(&self, message: Bytes, address: H160) -> RpcResult<Bytes>;
+
+	/// Returns the address which signed `message` with `signature`, as returned by
+	/// `personal_sign`.
+	#[method(name = "personal_ecRecover")]
+	fn ec_recover(&self, message: Bytes, signature: Bytes) -> RpcResult<H160>;
+}
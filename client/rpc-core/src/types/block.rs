@@ -18,7 +18,7 @@
 
 use std::{collections::BTreeMap, ops::Deref};
 
-use ethereum_types::{Bloom as H2048, H160, H256, H64, U256};
+use ethereum_types::{Bloom as H2048, H160, H256, H64, U256, U64};
 use serde::{ser::Error, Serialize, Serializer};
 
 use crate::types::{Bytes, Transaction};
@@ -62,6 +62,27 @@ pub struct Block {
 	/// Base Fee for post-EIP1559 blocks.
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub base_fee_per_gas: Option<U256>,
+	/// Withdrawals of the block, `None` for the chains not processing any.
+	pub withdrawals: Option<Vec<Withdrawal>>,
+	/// Root of the withdrawals trie, along with the withdrawals.
+	///
+	/// Computed from the withdrawals: the Ethereum header of Frontier does not commit to it, so
+	/// the block hash does not cover it.
+	pub withdrawals_root: Option<H256>,
+}
+
+/// Withdrawal representation, EIP-4895.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Withdrawal {
+	/// Index of the withdrawal
+	pub index: U64,
+	/// Index of the validator withdrawing
+	pub validator_index: U64,
+	/// Address credited
+	pub address: H160,
+	/// Amount credited, in Gwei
+	pub amount: U64,
 }
 
 /// Block header representation.
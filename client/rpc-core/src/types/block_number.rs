@@ -21,6 +21,7 @@ use std::fmt;
 use ethereum_types::H256;
 use serde::{
 	de::{Error, MapAccess, Visitor},
+	ser::SerializeMap,
 	Deserialize, Deserializer, Serialize, Serializer,
 };
 
@@ -80,10 +81,13 @@ impl Serialize for BlockNumberOrHash {
 			BlockNumberOrHash::Hash {
 				hash,
 				require_canonical,
-			} => serializer.serialize_str(&format!(
-				"{{ 'hash': '{}', 'requireCanonical': '{}'  }}",
-				hash, require_canonical
-			)),
+			} => {
+				// EIP-1898 block hash parameter, as deserialized.
+				let mut map = serializer.serialize_map(Some(2))?;
+				map.serialize_entry("blockHash", &hash)?;
+				map.serialize_entry("requireCanonical", &require_canonical)?;
+				map.end()
+			}
 			BlockNumberOrHash::Num(ref x) => serializer.serialize_str(&format!("0x{:x}", x)),
 			BlockNumberOrHash::Latest => serializer.serialize_str("latest"),
 			BlockNumberOrHash::Earliest => serializer.serialize_str("earliest"),
@@ -234,4 +238,34 @@ mod tests {
 		assert_eq!(match_block_number(bn_tag_finalized).unwrap(), 999);
 		assert_eq!(match_block_number(bn_tag_pending).unwrap(), 1001);
 	}
+
+	#[test]
+	fn block_number_serialize_round_trips() {
+		for block_number in [
+			BlockNumberOrHash::Hash {
+				hash: H256::repeat_byte(0x01),
+				require_canonical: true,
+			},
+			BlockNumberOrHash::Num(42),
+			BlockNumberOrHash::Latest,
+			BlockNumberOrHash::Earliest,
+			BlockNumberOrHash::Pending,
+			BlockNumberOrHash::Safe,
+			BlockNumberOrHash::Finalized,
+		] {
+			let json = serde_json::to_string(&block_number).unwrap();
+			assert_eq!(
+				serde_json::from_str::<BlockNumberOrHash>(&json).unwrap(),
+				block_number
+			);
+		}
+		assert_eq!(
+			serde_json::to_value(BlockNumberOrHash::Hash {
+				hash: H256::zero(),
+				require_canonical: false,
+			})
+			.unwrap(),
+			serde_json::json!({ "blockHash": H256::zero(), "requireCanonical": false })
+		);
+	}
 }
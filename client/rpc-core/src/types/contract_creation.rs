@@ -0,0 +1,33 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use ethereum_types::{H160, H256, U256};
+use serde::Serialize;
+
+/// Contract creation metadata.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractCreation {
+	/// Hash of the transaction that created the contract.
+	pub transaction_hash: H256,
+	/// Number of the block including the transaction.
+	pub block_number: U256,
+	/// Sender of the transaction.
+	pub creator: H160,
+	/// Whether the contract was created by a CREATE frame of the transaction.
+	pub is_internal: bool,
+}
@@ -65,6 +65,22 @@ where
 	}
 }
 
+impl<T> Serialize for VariadicValue<T>
+where
+	T: DeserializeOwned + Serialize,
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		match self {
+			VariadicValue::Single(value) => value.serialize(serializer),
+			VariadicValue::Multiple(values) => values.serialize(serializer),
+			VariadicValue::Null => serializer.serialize_none(),
+		}
+	}
+}
+
 /// Filter Address
 pub type FilterAddress = VariadicValue<H160>;
 /// Topic, supports `A` | `null` | `[A,B,C]` | `[A,[B,C]]` | `[null,[B,C]]` | `[null,[null,C]]`
@@ -131,19 +147,24 @@ impl From<&VariadicValue<Option<H256>>> for Vec<Option<Bloom>> {
 }
 
 /// Filter
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "camelCase")]
 pub struct Filter {
 	/// From Block
+	#[serde(skip_serializing_if = "Option::is_none")]
 	pub from_block: Option<BlockNumberOrHash>,
 	/// To Block
+	#[serde(skip_serializing_if = "Option::is_none")]
 	pub to_block: Option<BlockNumberOrHash>,
 	/// Block hash
+	#[serde(skip_serializing_if = "Option::is_none")]
 	pub block_hash: Option<H256>,
 	/// Address
+	#[serde(skip_serializing_if = "Option::is_none")]
 	pub address: Option<FilterAddress>,
 	/// Topics
+	#[serde(skip_serializing_if = "Option::is_none")]
 	pub topics: Option<Topic>,
 }
 
@@ -755,4 +776,22 @@ mod tests {
 			&topics_bloom
 		));
 	}
+
+	#[test]
+	fn filter_serialize_round_trips() {
+		let json = serde_json::json!({
+			"fromBlock": "0x1",
+			"toBlock": "latest",
+			"address": ["0x1000000000000000000000000000000000000000"],
+			"topics": [
+				null,
+				[
+					"0x4000000000000000000000000000000000000000000000000000000000000000",
+					"0x5000000000000000000000000000000000000000000000000000000000000000"
+				]
+			],
+		});
+		let filter: Filter = serde_json::from_value(json.clone()).unwrap();
+		assert_eq!(serde_json::to_value(&filter).unwrap(), json);
+	}
 }
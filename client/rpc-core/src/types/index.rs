@@ -20,7 +20,7 @@ use std::fmt;
 
 use serde::{
 	de::{Error, Visitor},
-	Deserialize, Deserializer,
+	Deserialize, Deserializer, Serialize, Serializer,
 };
 
 /// Represents usize.
@@ -34,6 +34,15 @@ impl Index {
 	}
 }
 
+impl Serialize for Index {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(&format!("0x{:x}", self.0))
+	}
+}
+
 impl<'a> Deserialize<'a> for Index {
 	fn deserialize<D>(deserializer: D) -> Result<Index, D::Error>
 	where
@@ -93,4 +102,9 @@ mod tests {
 		let deserialized: Vec<Index> = serde_json::from_str(s).unwrap();
 		assert_eq!(deserialized, vec![Index(10), Index(10), Index(42)]);
 	}
+
+	#[test]
+	fn index_serialization() {
+		assert_eq!(serde_json::to_string(&Index(10)).unwrap(), r#""0xa""#);
+	}
 }
@@ -0,0 +1,119 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::types::Log;
+
+/// Position of a log, its block number and its index in the block, serialized as
+/// `0xBLOCK:INDEX` with both numbers in hex.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct LogCursor {
+	pub block_number: u64,
+	pub log_index: u32,
+}
+
+impl Serialize for LogCursor {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(&format!("0x{:x}:{:x}", self.block_number, self.log_index))
+	}
+}
+
+impl<'a> Deserialize<'a> for LogCursor {
+	fn deserialize<D>(deserializer: D) -> Result<LogCursor, D::Error>
+	where
+		D: Deserializer<'a>,
+	{
+		let value = String::deserialize(deserializer)?;
+		let invalid = || D::Error::custom(format!("Invalid log cursor: {value}"));
+		let (block_number, log_index) = value
+			.strip_prefix("0x")
+			.and_then(|value| value.split_once(':'))
+			.ok_or_else(invalid)?;
+		Ok(LogCursor {
+			block_number: u64::from_str_radix(block_number, 16).map_err(|_| invalid())?,
+			log_index: u32::from_str_radix(log_index, 16).map_err(|_| invalid())?,
+		})
+	}
+}
+
+/// Logs of a page of `eth_getLogsPage`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogsPage {
+	/// Logs matching the filter, in order.
+	pub logs: Vec<Log>,
+	/// Position of the next matching log, when the page is truncated.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub next_cursor: Option<LogCursor>,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn log_cursor_serialization() {
+		let cursor = LogCursor {
+			block_number: 0x1b4,
+			log_index: 10,
+		};
+		assert_eq!(serde_json::to_string(&cursor).unwrap(), r#""0x1b4:a""#);
+		assert_eq!(
+			serde_json::from_str::<LogCursor>(r#""0x1b4:a""#).unwrap(),
+			cursor
+		);
+
+		for invalid in [
+			r#""1b4:a""#,
+			r#""0x1b4""#,
+			r#""0x1b4:""#,
+			r#""0xz:1""#,
+			"436",
+		] {
+			assert!(serde_json::from_str::<LogCursor>(invalid).is_err());
+		}
+	}
+
+	#[test]
+	fn logs_page_serialization() {
+		let page = LogsPage {
+			logs: Vec::new(),
+			next_cursor: None,
+		};
+		assert_eq!(
+			serde_json::to_value(&page).unwrap(),
+			serde_json::json!({ "logs": [] })
+		);
+
+		let page = LogsPage {
+			next_cursor: Some(LogCursor {
+				block_number: 2,
+				log_index: 0,
+			}),
+			..page
+		};
+		assert_eq!(
+			serde_json::to_value(&page).unwrap(),
+			serde_json::json!({ "logs": [], "nextCursor": "0x2:0" })
+		);
+	}
+}
@@ -0,0 +1,105 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use ethereum_types::{H256, U256};
+use serde::{Deserialize, Serialize};
+
+/// Ethereum block hash or number.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum HashOrNumber {
+	Hash(H256),
+	Number(U256),
+}
+
+/// Why a block or transaction could not be mapped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UnmappedReason {
+	/// The block is not known to the node.
+	UnknownBlock,
+	/// The block has no ethereum digest.
+	NonEthereumBlock,
+	/// The block or transaction is not indexed by the frontier backend, yet.
+	NotIndexed,
+}
+
+/// Ethereum block of a substrate block.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthereumBlockMapping {
+	/// Hash of the ethereum block, `None` if the block is not mapped.
+	pub ethereum_block_hash: Option<H256>,
+	/// Why the block is not mapped.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub reason: Option<UnmappedReason>,
+}
+
+/// Substrate block of an ethereum block.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubstrateBlockMapping {
+	/// Hash of the substrate block, `None` if the block is not mapped.
+	pub substrate_block_hash: Option<H256>,
+	/// Why the block is not mapped.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub reason: Option<UnmappedReason>,
+}
+
+/// Substrate extrinsic of an ethereum transaction.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubstrateExtrinsicMapping {
+	/// Hash of the substrate block including the extrinsic, `None` if the transaction is not
+	/// mapped.
+	pub substrate_block_hash: Option<H256>,
+	/// Index of the extrinsic in the substrate block, `None` if the transaction is not mapped.
+	pub extrinsic_index: Option<U256>,
+	/// Why the transaction is not mapped.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub reason: Option<UnmappedReason>,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn hash_or_number_deserialization() {
+		let hash = format!("\"0x{}\"", "01".repeat(32));
+		assert_eq!(
+			serde_json::from_str::<HashOrNumber>(&hash).unwrap(),
+			HashOrNumber::Hash(H256::repeat_byte(0x01))
+		);
+		assert_eq!(
+			serde_json::from_str::<HashOrNumber>("\"0x10\"").unwrap(),
+			HashOrNumber::Number(U256::from(16))
+		);
+	}
+
+	#[test]
+	fn unmapped_serialization() {
+		let mapping = EthereumBlockMapping {
+			ethereum_block_hash: None,
+			reason: Some(UnmappedReason::NonEthereumBlock),
+		};
+		assert_eq!(
+			serde_json::to_string(&mapping).unwrap(),
+			r#"{"ethereumBlockHash":null,"reason":"nonEthereumBlock"}"#
+		);
+	}
+}
@@ -23,10 +23,15 @@ mod block;
 mod block_number;
 mod bytes;
 mod call_request;
+mod contract_creation;
 mod fee;
 mod filter;
 mod index;
 mod log;
+#[cfg(feature = "logs-pagination")]
+mod logs_page;
+mod mapping;
+mod modules;
 mod receipt;
 mod sync;
 mod transaction;
@@ -40,21 +45,29 @@ pub mod pubsub;
 use ethereum::TransactionV2 as EthereumTransaction;
 use ethereum_types::H160;
 
+#[cfg(feature = "logs-pagination")]
+pub use self::logs_page::{LogCursor, LogsPage};
 #[cfg(feature = "txpool")]
 pub use self::txpool::{Summary, TransactionMap, TxPoolResult};
 pub use self::{
 	account_info::{AccountInfo, EthAccount, ExtAccountInfo, RecoveredAccount, StorageProof},
-	block::{Block, BlockTransactions, Header, Rich, RichBlock, RichHeader},
+	block::{Block, BlockTransactions, Header, Rich, RichBlock, RichHeader, Withdrawal},
 	block_number::BlockNumberOrHash,
 	bytes::Bytes,
 	call_request::CallStateOverride,
+	contract_creation::ContractCreation,
 	fee::{FeeHistory, FeeHistoryCache, FeeHistoryCacheItem, FeeHistoryCacheLimit},
 	filter::{
-		Filter, FilterAddress, FilterChanges, FilterPool, FilterPoolItem, FilterType,
+		BloomFilter, Filter, FilterAddress, FilterChanges, FilterPool, FilterPoolItem, FilterType,
 		FilteredParams, Topic, VariadicValue,
 	},
 	index::Index,
 	log::Log,
+	mapping::{
+		EthereumBlockMapping, HashOrNumber, SubstrateBlockMapping, SubstrateExtrinsicMapping,
+		UnmappedReason,
+	},
+	modules::RpcModuleInfo,
 	receipt::Receipt,
 	sync::{
 		ChainStatus, EthProtocolInfo, PeerCount, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo,
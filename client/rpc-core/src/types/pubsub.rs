@@ -77,7 +77,7 @@ impl<'a> Deserialize<'a> for Params {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum PubSubResult {
 	/// New block header.
-	Header(Box<RichHeader>),
+	Header(Box<PubSubHeader>),
 	/// Log
 	Log(Box<Log>),
 	/// Transaction hash
@@ -87,8 +87,8 @@ pub enum PubSubResult {
 }
 
 impl PubSubResult {
-	pub fn header(block: EthereumBlock) -> Self {
-		Self::Header(Box::new(Rich {
+	pub fn header(block: EthereumBlock, reorg_depth: u64) -> Self {
+		let header = Rich {
 			inner: Header {
 				hash: Some(H256::from(keccak_256(&rlp::encode(&block.header)))),
 				parent_hash: block.header.parent_hash,
@@ -109,6 +109,10 @@ impl PubSubResult {
 				size: Some(U256::from(rlp::encode(&block.header).len() as u32)),
 			},
 			extra_info: BTreeMap::new(),
+		};
+		Self::Header(Box::new(PubSubHeader {
+			header,
+			reorg_depth,
 		}))
 	}
 
@@ -177,6 +181,25 @@ impl Serialize for PubSubResult {
 	}
 }
 
+/// Notification of a resumable subscription.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ResumableResult {
+	/// Position of the notification in the subscription, to resume the subscription after it.
+	pub cursor: u64,
+	pub result: PubSubResult,
+}
+
+/// New heads notification.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PubSubHeader {
+	#[serde(flatten)]
+	pub header: RichHeader,
+	/// Number of previously notified heads that are no longer canonical.
+	/// Zero when the header is a direct child of the previously notified head.
+	pub reorg_depth: u64,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum PubSubSyncing {
@@ -258,3 +258,153 @@ pub struct RichRawTransaction {
 	#[serde(rename = "tx")]
 	pub transaction: Transaction,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	fn to_json(transaction: &EthereumTransaction) -> serde_json::Value {
+		let mut json = serde_json::to_value(Transaction::build_from(
+			H160::repeat_byte(0x33),
+			transaction,
+		))
+		.unwrap();
+		assert_eq!(
+			json.as_object_mut().unwrap().remove("hash"),
+			Some(serde_json::to_value(transaction.hash()).unwrap())
+		);
+		json
+	}
+
+	#[test]
+	fn test_serialize_legacy_transaction() {
+		let transaction = EthereumTransaction::Legacy(ethereum::LegacyTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(2),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Call(H160::repeat_byte(0x11)),
+			value: U256::from(3),
+			input: vec![0x12, 0x34],
+			signature: ethereum::TransactionSignature::new(
+				37,
+				H256::from_low_u64_be(4),
+				H256::from_low_u64_be(5),
+			)
+			.expect("signature is valid"),
+		});
+
+		assert_eq!(
+			to_json(&transaction),
+			json!({
+				"type": "0x0",
+				"nonce": "0x0",
+				"blockHash": null,
+				"blockNumber": null,
+				"transactionIndex": null,
+				"from": "0x3333333333333333333333333333333333333333",
+				"to": "0x1111111111111111111111111111111111111111",
+				"value": "0x3",
+				"gas": "0x5208",
+				"gasPrice": "0x2",
+				"input": "0x1234",
+				"creates": null,
+				"chainId": "0x1",
+				"v": "0x25",
+				"r": "0x4",
+				"s": "0x5"
+			})
+		);
+	}
+
+	#[test]
+	fn test_serialize_eip2930_transaction() {
+		let transaction = EthereumTransaction::EIP2930(ethereum::EIP2930Transaction {
+			chain_id: 42,
+			nonce: U256::one(),
+			gas_price: U256::from(2),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Call(H160::repeat_byte(0x11)),
+			value: U256::from(3),
+			input: vec![0x12, 0x34],
+			access_list: vec![AccessListItem {
+				address: H160::repeat_byte(0x22),
+				storage_keys: vec![H256::from_low_u64_be(1)],
+			}],
+			odd_y_parity: true,
+			r: H256::from_low_u64_be(4),
+			s: H256::from_low_u64_be(5),
+		});
+
+		assert_eq!(
+			to_json(&transaction),
+			json!({
+				"type": "0x1",
+				"nonce": "0x1",
+				"blockHash": null,
+				"blockNumber": null,
+				"transactionIndex": null,
+				"from": "0x3333333333333333333333333333333333333333",
+				"to": "0x1111111111111111111111111111111111111111",
+				"value": "0x3",
+				"gas": "0x5208",
+				"gasPrice": "0x2",
+				"input": "0x1234",
+				"creates": null,
+				"chainId": "0x2a",
+				"accessList": [{
+					"address": "0x2222222222222222222222222222222222222222",
+					"storageKeys": ["0x0000000000000000000000000000000000000000000000000000000000000001"]
+				}],
+				"yParity": "0x1",
+				"v": "0x1",
+				"r": "0x4",
+				"s": "0x5"
+			})
+		);
+	}
+
+	#[test]
+	fn test_serialize_eip1559_transaction() {
+		let transaction = EthereumTransaction::EIP1559(ethereum::EIP1559Transaction {
+			chain_id: 42,
+			nonce: U256::from(2),
+			max_priority_fee_per_gas: U256::one(),
+			max_fee_per_gas: U256::from(2),
+			gas_limit: U256::from(53_000),
+			action: TransactionAction::Create,
+			value: U256::zero(),
+			input: vec![0x60, 0x00],
+			access_list: vec![],
+			odd_y_parity: false,
+			r: H256::from_low_u64_be(4),
+			s: H256::from_low_u64_be(5),
+		});
+
+		assert_eq!(
+			to_json(&transaction),
+			json!({
+				"type": "0x2",
+				"nonce": "0x2",
+				"blockHash": null,
+				"blockNumber": null,
+				"transactionIndex": null,
+				"from": "0x3333333333333333333333333333333333333333",
+				"to": null,
+				"value": "0x0",
+				"gas": "0xcf08",
+				"gasPrice": "0x2",
+				"maxFeePerGas": "0x2",
+				"maxPriorityFeePerGas": "0x1",
+				"input": "0x6000",
+				"creates": null,
+				"chainId": "0x2a",
+				"accessList": [],
+				"yParity": "0x0",
+				"v": "0x0",
+				"r": "0x4",
+				"s": "0x5"
+			})
+		);
+	}
+}
@@ -50,3 +50,26 @@ impl Serialize for Work {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn work_serialization() {
+		let zero = "0x0000000000000000000000000000000000000000000000000000000000000000";
+		assert_eq!(
+			serde_json::to_value(Work::default()).unwrap(),
+			serde_json::json!([zero, zero, zero])
+		);
+
+		let work = Work {
+			number: Some(16),
+			..Default::default()
+		};
+		assert_eq!(
+			serde_json::to_value(work).unwrap(),
+			serde_json::json!([zero, zero, zero, "0x10"])
+		);
+	}
+}
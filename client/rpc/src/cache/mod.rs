@@ -19,13 +19,13 @@
 mod lru_cache;
 
 use std::{
-	collections::{BTreeMap, HashMap},
+	collections::{BTreeMap, BTreeSet, HashMap},
 	marker::PhantomData,
 	sync::{Arc, Mutex},
 };
 
 use ethereum::BlockV2 as EthereumBlock;
-use ethereum_types::U256;
+use ethereum_types::{H160, U256};
 use futures::StreamExt;
 use tokio::sync::{mpsc, oneshot};
 // Substrate
@@ -247,6 +247,27 @@ impl<B: BlockT> EthBlockDataCacheTask<B> {
 	}
 }
 
+/// Transactions left out of the fee history rewards, and so of the suggested priority fee,
+/// as they don't reflect the fee market.
+///
+/// Transactions sent by the block author are always left out.
+#[derive(Clone, Debug, Default)]
+pub struct FeeOracleConfig {
+	/// Transactions with a lower effective tip are left out.
+	pub ignore_price: u64,
+	/// Senders whose transactions are left out, e.g. system or sponsored transaction senders.
+	pub ignored_senders: BTreeSet<H160>,
+}
+
+impl FeeOracleConfig {
+	fn is_ignored(&self, sender: Option<H160>, coinbase: H160, effective_reward: u64) -> bool {
+		effective_reward < self.ignore_price
+			|| sender.map_or(false, |sender| {
+				sender == coinbase || self.ignored_senders.contains(&sender)
+			})
+	}
+}
+
 pub struct EthTask<B, C, BE>(PhantomData<(B, C, BE)>);
 
 impl<B, C, BE> EthTask<B, C, BE>
@@ -281,6 +302,7 @@ where
 		storage_override: Arc<dyn StorageOverride<B>>,
 		fee_history_cache: FeeHistoryCache,
 		block_limit: u64,
+		fee_oracle_config: FeeOracleConfig,
 	) {
 		struct TransactionHelper {
 			gas_used: u64,
@@ -311,6 +333,7 @@ where
 			let mut block_number: Option<u64> = None;
 			let base_fee = client.runtime_api().gas_price(hash).unwrap_or_default();
 			let receipts = storage_override.current_receipts(hash);
+			let statuses = storage_override.current_transaction_statuses(hash).unwrap_or_default();
 			let mut result = FeeHistoryCacheItem {
 				base_fee: UniqueSaturatedInto::<u64>::unique_saturated_into(base_fee),
 				gas_used_ratio: 0f64,
@@ -321,6 +344,7 @@ where
 				let gas_used = UniqueSaturatedInto::<u64>::unique_saturated_into(block.header.gas_used) as f64;
 				let gas_limit = UniqueSaturatedInto::<u64>::unique_saturated_into(block.header.gas_limit) as f64;
 				result.gas_used_ratio = gas_used / gas_limit;
+				let coinbase = block.header.beneficiary;
 
 				let mut previous_cumulative_gas = U256::zero();
 				let used_gas = |current: U256, previous: &mut U256| -> u64 {
@@ -351,15 +375,24 @@ where
 							None => 0,
 						},
 					})
+					.enumerate()
+					// Leave out the transactions not reflecting the fee market.
+					.filter(|(i, tx)| {
+						let sender = statuses.get(*i).map(|status| status.from);
+						!fee_oracle_config.is_ignored(sender, coinbase, tx.effective_reward)
+					})
+					.map(|(_, tx)| tx)
 					.collect();
 				// Sort ASC by effective reward.
 				transactions.sort_by(|a, b| a.effective_reward.cmp(&b.effective_reward));
+				// Percentiles are weighted by the gas used by the sampled transactions only.
+				let sampled_gas_used = transactions.iter().map(|tx| tx.gas_used).sum::<u64>() as f64;
 
 				// Calculate percentile rewards.
 				result.rewards = reward_percentiles
 					.into_iter()
 					.filter_map(|p| {
-						let target_gas = (p * gas_used / 100f64) as u64;
+						let target_gas = (p * sampled_gas_used / 100f64) as u64;
 						let mut sum_gas = 0;
 						for tx in &transactions {
 							sum_gas += tx.gas_used;
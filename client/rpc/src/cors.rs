@@ -0,0 +1,352 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Allowed origins of the RPC methods.
+
+use std::{
+	str::FromStr,
+	sync::Arc,
+	task::{Context, Poll},
+};
+
+use futures::future::BoxFuture;
+use hyper::{body::HttpBody, header, Body, Method, Request, Response, StatusCode};
+
+/// Origins allowed to call the methods matching `method`, either a method name or a prefix
+/// followed by `*`, like `debug_*`.
+///
+/// Parsed from `<method>=<origin>[,<origin>...]`, like `debug_*=localhost`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MethodCorsConfig {
+	pub method: String,
+	pub allowed_origins: Vec<String>,
+}
+
+impl MethodCorsConfig {
+	/// Length of the matched method name, the longest match being the most specific rule.
+	fn matches(&self, method: &str) -> Option<usize> {
+		match self.method.strip_suffix('*') {
+			Some(prefix) => method.starts_with(prefix).then_some(prefix.len()),
+			// Exact names are more specific than any prefix.
+			None => (self.method == method).then_some(usize::MAX),
+		}
+	}
+}
+
+impl FromStr for MethodCorsConfig {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (method, origins) = s
+			.split_once('=')
+			.ok_or_else(|| format!("Expected <method>=<origins>, got {s:?}"))?;
+		let allowed_origins: Vec<_> = origins
+			.split(',')
+			.map(str::trim)
+			.filter(|origin| !origin.is_empty())
+			.map(str::to_string)
+			.collect();
+		if method.is_empty() || allowed_origins.is_empty() {
+			return Err(format!("Expected <method>=<origins>, got {s:?}"));
+		}
+		Ok(Self {
+			method: method.to_string(),
+			allowed_origins,
+		})
+	}
+}
+
+/// Allowed origins of the RPC requests.
+#[derive(Clone, Debug, Default)]
+pub struct CorsConfig {
+	/// Origins allowed for the methods without an override, any origin if `None`.
+	pub allowed_origins: Option<Vec<String>>,
+	/// Overrides of the allowed origins of some methods.
+	pub methods: Vec<MethodCorsConfig>,
+}
+
+impl CorsConfig {
+	/// Whether `origin` may call `method`, according to the most specific rule.
+	pub fn allows(&self, method: &str, origin: &str) -> bool {
+		let method_origins = self
+			.methods
+			.iter()
+			.filter_map(|config| Some((config.matches(method)?, config)))
+			.max_by_key(|(specificity, _)| *specificity)
+			.map(|(_, config)| &config.allowed_origins);
+		match method_origins.or(self.allowed_origins.as_ref()) {
+			Some(patterns) => patterns
+				.iter()
+				.any(|pattern| origin_matches(pattern, origin)),
+			None => true,
+		}
+	}
+
+	/// Whether some override sets the origins allowed to call `method`.
+	pub fn overrides(&self, method: &str) -> bool {
+		self.methods
+			.iter()
+			.any(|config| config.matches(method).is_some())
+	}
+
+	/// Whether `origin` may call any method, for the preflight requests which don't say which.
+	pub fn allows_any(&self, origin: &str) -> bool {
+		let Some(allowed_origins) = &self.allowed_origins else {
+			return true;
+		};
+		allowed_origins
+			.iter()
+			.chain(
+				self.methods
+					.iter()
+					.flat_map(|config| &config.allowed_origins),
+			)
+			.any(|pattern| origin_matches(pattern, origin))
+	}
+}
+
+/// Whether `origin` matches `pattern`, which is either `*`, an origin, an origin with a `*` port
+/// like `http://localhost:*`, or a host allowed with any scheme and port, like `localhost`.
+fn origin_matches(pattern: &str, origin: &str) -> bool {
+	if pattern == "*" {
+		return true;
+	}
+	if !pattern.contains("://") {
+		let host = origin
+			.split_once("://")
+			.map_or(origin, |(_, authority)| authority);
+		let host = host.rsplit_once(':').map_or(host, |(host, _)| host);
+		return host == pattern;
+	}
+	match pattern.strip_suffix(":*") {
+		Some(base) => {
+			origin == base
+				|| origin
+					.strip_prefix(base)
+					.and_then(|port| port.strip_prefix(':'))
+					.map_or(false, |port| port.bytes().all(|b| b.is_ascii_digit()))
+		}
+		None => origin == pattern,
+	}
+}
+
+/// Methods called by a JSON-RPC request or batch, empty if it cannot be parsed.
+fn called_methods(body: &[u8]) -> Vec<String> {
+	let method = |call: &serde_json::Value| call.get("method")?.as_str().map(str::to_string);
+	match serde_json::from_slice::<serde_json::Value>(body) {
+		Ok(serde_json::Value::Array(calls)) => calls.iter().filter_map(method).collect(),
+		Ok(call) => method(&call).into_iter().collect(),
+		Err(_) => Vec::new(),
+	}
+}
+
+/// Layer of [`MethodCors`], to be added to the HTTP middleware of the server.
+#[derive(Clone, Debug)]
+pub struct MethodCorsLayer {
+	config: Arc<CorsConfig>,
+	max_request_size: usize,
+}
+
+impl MethodCorsLayer {
+	/// Requests are read to find their methods, up to `max_request_size` bytes.
+	pub fn new(config: CorsConfig, max_request_size: usize) -> Self {
+		Self {
+			config: Arc::new(config),
+			max_request_size,
+		}
+	}
+}
+
+impl<S> tower::Layer<S> for MethodCorsLayer {
+	type Service = MethodCors<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		MethodCors {
+			service,
+			config: self.config.clone(),
+			max_request_size: self.max_request_size,
+		}
+	}
+}
+
+/// Rejects the requests from an origin which may not call one of their methods.
+///
+/// The CORS headers are left to the CORS layer of the server, which must allow the origins of
+/// the method overrides for the browsers to send them.
+#[derive(Clone, Debug)]
+pub struct MethodCors<S> {
+	service: S,
+	config: Arc<CorsConfig>,
+	max_request_size: usize,
+}
+
+impl<S> tower::Service<Request<Body>> for MethodCors<S>
+where
+	S: tower::Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+	S::Future: Send,
+{
+	type Response = Response<Body>;
+	type Error = S::Error;
+	type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+	fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		self.service.poll_ready(cx)
+	}
+
+	fn call(&mut self, request: Request<Body>) -> Self::Future {
+		let origin = request
+			.headers()
+			.get(header::ORIGIN)
+			.and_then(|origin| origin.to_str().ok())
+			.map(str::to_string);
+		// The service polled ready is the one to call.
+		let clone = self.service.clone();
+		let mut service = std::mem::replace(&mut self.service, clone);
+		let Some(origin) = origin else {
+			return Box::pin(service.call(request));
+		};
+
+		let config = self.config.clone();
+		let max_request_size = self.max_request_size;
+		Box::pin(async move {
+			if request.method() == Method::OPTIONS {
+				if !config.allows_any(&origin) {
+					return Ok(error_response(
+						StatusCode::FORBIDDEN,
+						format!("Origin {origin} is not allowed"),
+					));
+				}
+				return service.call(request).await;
+			}
+
+			let (parts, mut body) = request.into_parts();
+			let mut bytes = Vec::new();
+			while let Some(chunk) = body.data().await {
+				let chunk = match chunk {
+					Ok(chunk) => chunk,
+					Err(err) => {
+						return Ok(error_response(StatusCode::BAD_REQUEST, err.to_string()))
+					}
+				};
+				if bytes.len() + chunk.len() > max_request_size {
+					return Ok(error_response(
+						StatusCode::PAYLOAD_TOO_LARGE,
+						format!("Request is larger than {max_request_size} bytes"),
+					));
+				}
+				bytes.extend_from_slice(&chunk);
+			}
+			if let Some(method) = called_methods(&bytes)
+				.into_iter()
+				.find(|method| !config.allows(method, &origin))
+			{
+				return Ok(error_response(
+					StatusCode::FORBIDDEN,
+					format!("Origin {origin} is not allowed to call {method}"),
+				));
+			}
+			service
+				.call(Request::from_parts(parts, Body::from(bytes)))
+				.await
+		})
+	}
+}
+
+fn error_response(status: StatusCode, reason: String) -> Response<Body> {
+	Response::builder()
+		.status(status)
+		.body(Body::from(reason))
+		.expect("Valid response; qed")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn config() -> CorsConfig {
+		CorsConfig {
+			allowed_origins: Some(vec!["https://app.example".into()]),
+			methods: vec![
+				"debug_*=http://localhost:*".parse().unwrap(),
+				"debug_getRawBlock=*".parse().unwrap(),
+				"eth_*=*".parse().unwrap(),
+			],
+		}
+	}
+
+	#[test]
+	fn most_specific_rule_applies() {
+		let config = config();
+		assert!(config.allows("debug_getRawHeader", "http://localhost:9944"));
+		assert!(!config.allows("debug_getRawHeader", "https://app.example"));
+		assert!(config.allows("debug_getRawBlock", "https://app.example"));
+		assert!(config.allows("eth_call", "https://other.example"));
+		assert!(config.allows("net_version", "https://app.example"));
+		assert!(!config.allows("net_version", "http://localhost:9944"));
+	}
+
+	#[test]
+	fn origin_patterns() {
+		assert!(origin_matches("http://localhost:*", "http://localhost"));
+		assert!(origin_matches(
+			"http://localhost:*",
+			"http://localhost:8080"
+		));
+		assert!(!origin_matches(
+			"http://localhost:*",
+			"http://localhost.evil:8080"
+		));
+		assert!(!origin_matches(
+			"http://localhost:*",
+			"https://localhost:8080"
+		));
+		assert!(origin_matches("localhost", "https://localhost:8080"));
+		assert!(!origin_matches("localhost", "https://localhost.evil"));
+		assert!(origin_matches("https://app.example", "https://app.example"));
+		assert!(!origin_matches(
+			"https://app.example",
+			"https://app.example:8443"
+		));
+	}
+
+	#[test]
+	fn parses_method_overrides() {
+		assert_eq!(
+			"debug_*=localhost, http://127.0.0.1:*".parse(),
+			Ok(MethodCorsConfig {
+				method: "debug_*".into(),
+				allowed_origins: vec!["localhost".into(), "http://127.0.0.1:*".into()],
+			})
+		);
+		assert!("debug_*".parse::<MethodCorsConfig>().is_err());
+		assert!("debug_*=".parse::<MethodCorsConfig>().is_err());
+	}
+
+	#[test]
+	fn batches_are_checked_method_by_method() {
+		assert_eq!(
+			called_methods(br#"[{"method":"eth_call"},{"method":"debug_getRawBlock"}]"#),
+			vec!["eth_call".to_string(), "debug_getRawBlock".to_string()]
+		);
+		assert_eq!(
+			called_methods(br#"{"method":"eth_call"}"#),
+			vec!["eth_call"]
+		);
+		assert!(called_methods(b"not json").is_empty());
+	}
+}
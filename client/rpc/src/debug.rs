@@ -26,13 +26,16 @@ use rlp::Encodable;
 use sc_client_api::backend::{Backend, StorageProvider};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
-use sp_runtime::traits::Block as BlockT;
+use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
 // Frontier
 use fc_rpc_core::{types::*, DebugApiServer};
 use fc_storage::StorageOverride;
 use fp_rpc::EthereumRuntimeRPCApi;
 
-use crate::{cache::EthBlockDataCacheTask, frontier_backend_client, internal_err};
+use crate::{
+	cache::EthBlockDataCacheTask, frontier_backend_client, internal_err, request::RequestTracer,
+	state_unavailable_err,
+};
 
 /// Debug API implementation.
 pub struct Debug<B: BlockT, C, BE> {
@@ -40,6 +43,7 @@ pub struct Debug<B: BlockT, C, BE> {
 	backend: Arc<dyn fc_api::Backend<B>>,
 	storage_override: Arc<dyn StorageOverride<B>>,
 	block_data_cache: Arc<EthBlockDataCacheTask<B>>,
+	request_tracer: RequestTracer,
 	_marker: PhantomData<BE>,
 }
 
@@ -49,16 +53,40 @@ impl<B: BlockT, C, BE> Debug<B, C, BE> {
 		backend: Arc<dyn fc_api::Backend<B>>,
 		storage_override: Arc<dyn StorageOverride<B>>,
 		block_data_cache: Arc<EthBlockDataCacheTask<B>>,
+		request_tracer: RequestTracer,
 	) -> Self {
 		Self {
 			client,
 			backend,
 			storage_override,
 			block_data_cache,
+			request_tracer,
 			_marker: PhantomData,
 		}
 	}
 
+	/// Fails right away with a descriptive error if the state of the block was pruned, rather
+	/// than reading the Ethereum data from a missing state.
+	fn ensure_state_available(&self, substrate_hash: B::Hash) -> RpcResult<()>
+	where
+		C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
+		BE: Backend<B>,
+	{
+		let client = self.client.as_ref();
+		if frontier_backend_client::is_state_available::<B, C, BE>(client, substrate_hash) {
+			return Ok(());
+		}
+		let number = client
+			.number(substrate_hash)
+			.map_err(|err| internal_err(format!("{:?}", err)))?
+			.ok_or_else(|| internal_err("Block not found"))?;
+		let earliest_block = frontier_backend_client::earliest_state_block::<B, C, BE>(client)?;
+		Err(state_unavailable_err(
+			number.unique_saturated_into(),
+			earliest_block,
+		))
+	}
+
 	async fn block_by(&self, number: BlockNumberOrHash) -> RpcResult<Option<ethereum::BlockV2>>
 	where
 		C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
@@ -79,6 +107,7 @@ impl<B: BlockT, C, BE> Debug<B, C, BE> {
 			.client
 			.expect_block_hash_from_id(&id)
 			.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?;
+		self.ensure_state_available(substrate_hash)?;
 		let block = self.block_data_cache.current_block(substrate_hash).await;
 		Ok(block)
 	}
@@ -114,6 +143,7 @@ impl<B: BlockT, C, BE> Debug<B, C, BE> {
 			None => return Ok(None),
 		};
 
+		self.ensure_state_available(substrate_hash)?;
 		let block = self.block_data_cache.current_block(substrate_hash).await;
 		if let Some(block) = block {
 			Ok(Some(block.transactions[index].clone()))
@@ -146,6 +176,7 @@ impl<B: BlockT, C, BE> Debug<B, C, BE> {
 			.expect_block_hash_from_id(&id)
 			.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?;
 
+		self.ensure_state_available(substrate_hash)?;
 		// TODO: use data cache in the future
 		let receipts = self.storage_override.current_receipts(substrate_hash);
 		Ok(receipts)
@@ -162,26 +193,48 @@ where
 	BE: Backend<B> + 'static,
 {
 	async fn raw_header(&self, number: BlockNumberOrHash) -> RpcResult<Option<Bytes>> {
-		let block = self.block_by(number).await?;
-		Ok(block.map(|block| Bytes::new(block.header.rlp_bytes().to_vec())))
+		let params = || format!("{:?}", number);
+		self.request_tracer
+			.trace("debug_getRawHeader", params, async {
+				let block = self.block_by(number).await?;
+				// Blocks built by pallet-ethereum carry none of the post-London header fields,
+				// their hash commits to the pre-London encoding which is what gets returned here.
+				Ok(block.map(|block| Bytes::new(block.header.rlp_bytes().to_vec())))
+			})
+			.await
 	}
 
 	async fn raw_block(&self, number: BlockNumberOrHash) -> RpcResult<Option<Bytes>> {
-		let block = self.block_by(number).await?;
-		Ok(block.map(|block| Bytes::new(block.rlp_bytes().to_vec())))
+		let params = || format!("{:?}", number);
+		self.request_tracer
+			.trace("debug_getRawBlock", params, async {
+				let block = self.block_by(number).await?;
+				Ok(block.map(|block| Bytes::new(block.rlp_bytes().to_vec())))
+			})
+			.await
 	}
 
 	async fn raw_transaction(&self, hash: H256) -> RpcResult<Option<Bytes>> {
-		let transaction = self.transaction_by(hash).await?;
-		Ok(transaction.map(|transaction| Bytes::new(transaction.encode().to_vec())))
+		let params = || format!("{:?}", hash);
+		self.request_tracer
+			.trace("debug_getRawTransaction", params, async {
+				let transaction = self.transaction_by(hash).await?;
+				Ok(transaction.map(|transaction| Bytes::new(transaction.encode().to_vec())))
+			})
+			.await
 	}
 
 	async fn raw_receipts(&self, number: BlockNumberOrHash) -> RpcResult<Vec<Bytes>> {
-		let receipts = self.receipts_by(number).await?.unwrap_or_default();
-		Ok(receipts
-			.into_iter()
-			.map(|receipt| Bytes::new(receipt.encode().to_vec()))
-			.collect::<Vec<_>>())
+		let params = || format!("{:?}", number);
+		self.request_tracer
+			.trace("debug_getRawReceipts", params, async {
+				let receipts = self.receipts_by(number).await?.unwrap_or_default();
+				Ok(receipts
+					.into_iter()
+					.map(|receipt| Bytes::new(receipt.encode().to_vec()))
+					.collect::<Vec<_>>())
+			})
+			.await
 	}
 
 	fn bad_blocks(&self, _number: BlockNumberOrHash) -> RpcResult<Vec<()>> {
@@ -192,3 +245,187 @@ where
 		Ok(vec![])
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum_types::U256;
+
+	fn header() -> ethereum::Header {
+		ethereum::Header {
+			parent_hash: H256::repeat_byte(1),
+			ommers_hash: H256::repeat_byte(2),
+			beneficiary: ethereum_types::H160::repeat_byte(3),
+			state_root: H256::repeat_byte(4),
+			transactions_root: H256::repeat_byte(5),
+			receipts_root: H256::repeat_byte(6),
+			logs_bloom: ethereum_types::Bloom::zero(),
+			difficulty: U256::zero(),
+			number: U256::from(7),
+			gas_limit: U256::from(15_000_000),
+			gas_used: U256::from(21_000),
+			timestamp: 8,
+			extra_data: vec![9],
+			mix_hash: H256::repeat_byte(10),
+			nonce: ethereum_types::H64::zero(),
+		}
+	}
+
+	fn transactions() -> Vec<ethereum::TransactionV2> {
+		let action = ethereum::TransactionAction::Call(ethereum_types::H160::repeat_byte(0x35));
+		vec![
+			ethereum::TransactionV2::Legacy(ethereum::LegacyTransaction {
+				nonce: U256::zero(),
+				gas_price: U256::from(1_000_000_000),
+				gas_limit: U256::from(21_000),
+				action,
+				value: U256::one(),
+				input: vec![],
+				signature: ethereum::TransactionSignature::new(
+					37,
+					H256::repeat_byte(0x01),
+					H256::repeat_byte(0x02),
+				)
+				.expect("signature is valid"),
+			}),
+			ethereum::TransactionV2::EIP2930(ethereum::EIP2930Transaction {
+				chain_id: 42,
+				nonce: U256::one(),
+				gas_price: U256::from(1_000_000_000),
+				gas_limit: U256::from(30_000),
+				action,
+				value: U256::one(),
+				input: vec![],
+				access_list: vec![ethereum::AccessListItem {
+					address: ethereum_types::H160::repeat_byte(0x11),
+					storage_keys: vec![H256::zero()],
+				}],
+				odd_y_parity: false,
+				r: H256::repeat_byte(0x03),
+				s: H256::repeat_byte(0x04),
+			}),
+			ethereum::TransactionV2::EIP1559(ethereum::EIP1559Transaction {
+				chain_id: 42,
+				nonce: U256::from(2),
+				max_priority_fee_per_gas: U256::one(),
+				max_fee_per_gas: U256::from(1_000_000_000),
+				gas_limit: U256::from(53_000),
+				action: ethereum::TransactionAction::Create,
+				value: U256::zero(),
+				input: vec![0x60, 0x00],
+				access_list: vec![],
+				odd_y_parity: true,
+				r: H256::repeat_byte(0x05),
+				s: H256::repeat_byte(0x06),
+			}),
+		]
+	}
+
+	#[test]
+	fn raw_block_round_trips_typed_transactions() {
+		let block = ethereum::BlockV2 {
+			header: header(),
+			transactions: transactions(),
+			ommers: vec![],
+		};
+		let raw = block.rlp_bytes();
+		let rlp = rlp::Rlp::new(&raw);
+		assert_eq!(rlp.item_count().unwrap(), 3);
+		assert_eq!(rlp.val_at::<ethereum::Header>(0).unwrap(), block.header);
+		assert_eq!(rlp.at(2).unwrap().item_count().unwrap(), 0);
+
+		let mut types = vec![];
+		let mut decoded = vec![];
+		for item in rlp.at(1).unwrap().iter() {
+			if item.is_list() {
+				types.push(0u8);
+				decoded.push(ethereum::TransactionV2::Legacy(item.as_val().unwrap()));
+			} else {
+				let envelope = item.data().unwrap();
+				types.push(envelope[0]);
+				decoded.push(
+					<ethereum::TransactionV2 as ethereum::EnvelopedDecodable>::decode(envelope)
+						.unwrap(),
+				);
+			}
+		}
+		assert_eq!(types, vec![0, 1, 2]);
+		assert_eq!(decoded, block.transactions);
+	}
+
+	fn receipt_data(status_code: u8) -> ethereum::EIP658ReceiptData {
+		ethereum::EIP658ReceiptData {
+			status_code,
+			used_gas: U256::from(21_000),
+			logs_bloom: ethereum_types::Bloom::zero(),
+			logs: vec![],
+		}
+	}
+
+	/// `rlp([status, 21000, zero bloom, []])`.
+	fn receipt_payload(status: u8) -> Vec<u8> {
+		let status = match status {
+			0 => 0x80,
+			status => status,
+		};
+		let mut payload = vec![0xf9, 0x01, 0x08, status, 0x82, 0x52, 0x08, 0xb9, 0x01, 0x00];
+		payload.extend([0u8; 256]);
+		payload.push(0xc0);
+		payload
+	}
+
+	#[test]
+	fn raw_receipts_use_eip658_status() {
+		assert_eq!(
+			ethereum::ReceiptV3::Legacy(receipt_data(1))
+				.encode()
+				.to_vec(),
+			receipt_payload(1)
+		);
+		assert_eq!(
+			ethereum::ReceiptV3::Legacy(receipt_data(0))
+				.encode()
+				.to_vec(),
+			receipt_payload(0)
+		);
+	}
+
+	#[test]
+	fn raw_receipts_are_type_prefixed() {
+		let mut eip2930 = vec![0x01];
+		eip2930.extend(receipt_payload(1));
+		assert_eq!(
+			ethereum::ReceiptV3::EIP2930(receipt_data(1))
+				.encode()
+				.to_vec(),
+			eip2930
+		);
+
+		let mut eip1559 = vec![0x02];
+		eip1559.extend(receipt_payload(0));
+		assert_eq!(
+			ethereum::ReceiptV3::EIP1559(receipt_data(0))
+				.encode()
+				.to_vec(),
+			eip1559
+		);
+	}
+
+	#[test]
+	fn raw_transactions_are_enveloped() {
+		for (transaction, type_byte) in transactions().into_iter().zip([None, Some(1u8), Some(2)]) {
+			let raw = transaction.encode().to_vec();
+			match type_byte {
+				Some(type_byte) => {
+					assert_eq!(raw[0], type_byte);
+					assert!(rlp::Rlp::new(&raw[1..]).is_list());
+				}
+				None => assert!(rlp::Rlp::new(&raw).is_list()),
+			}
+			assert_eq!(
+				<ethereum::TransactionV2 as ethereum::EnvelopedDecodable>::decode(&raw).unwrap(),
+				transaction
+			);
+		}
+	}
+}
@@ -0,0 +1,153 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use alloy_dyn_abi::TypedData;
+use ethereum_types::{H160, H256};
+use jsonrpsee::{
+	core::RpcResult,
+	types::{error::INVALID_PARAMS_CODE, ErrorObjectOwned},
+};
+// Frontier
+use fc_rpc_core::{types::Bytes, DevApiServer};
+
+use crate::{
+	err,
+	signer::{sign_hash, EthSigner},
+};
+
+/// Dev API implementation, signing with the dev accounts.
+pub struct Dev {
+	signers: Vec<Box<dyn EthSigner>>,
+}
+
+impl Dev {
+	pub fn new(signers: Vec<Box<dyn EthSigner>>) -> Self {
+		Self { signers }
+	}
+}
+
+/// EIP-712 signing hash of `typed_data`, `keccak256(0x19 || 0x01 || domainSeparator || hashStruct)`.
+fn typed_data_hash(typed_data: serde_json::Value) -> Result<H256, ErrorObjectOwned> {
+	// Wallets usually send the typed data as a JSON string.
+	let typed_data: TypedData = match typed_data {
+		serde_json::Value::String(json) => serde_json::from_str(&json),
+		typed_data => serde_json::from_value(typed_data),
+	}
+	.map_err(|e| {
+		err(
+			INVALID_PARAMS_CODE,
+			format!("Invalid typed data: {e}"),
+			None,
+		)
+	})?;
+	let hash = typed_data.eip712_signing_hash().map_err(|e| {
+		err(
+			INVALID_PARAMS_CODE,
+			format!("Invalid typed data: {e}"),
+			None,
+		)
+	})?;
+	Ok(H256(hash.0))
+}
+
+impl DevApiServer for Dev {
+	fn sign_typed_data_v4(&self, address: H160, typed_data: serde_json::Value) -> RpcResult<Bytes> {
+		let hash = typed_data_hash(typed_data)?;
+		Ok(Bytes::new(
+			sign_hash(&self.signers, hash, address)?.to_vec(),
+		))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::signer::EthDevSigner;
+
+	/// The `Mail` example of EIP-712.
+	fn mail() -> serde_json::Value {
+		serde_json::json!({
+			"types": {
+				"EIP712Domain": [
+					{ "name": "name", "type": "string" },
+					{ "name": "version", "type": "string" },
+					{ "name": "chainId", "type": "uint256" },
+					{ "name": "verifyingContract", "type": "address" }
+				],
+				"Person": [
+					{ "name": "name", "type": "string" },
+					{ "name": "wallet", "type": "address" }
+				],
+				"Mail": [
+					{ "name": "from", "type": "Person" },
+					{ "name": "to", "type": "Person" },
+					{ "name": "contents", "type": "string" }
+				]
+			},
+			"primaryType": "Mail",
+			"domain": {
+				"name": "Ether Mail",
+				"version": "1",
+				"chainId": 1,
+				"verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+			},
+			"message": {
+				"from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+				"to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+				"contents": "Hello, Bob!"
+			}
+		})
+	}
+
+	#[test]
+	fn typed_data_hash_matches_eip712() {
+		let expected: H256 = "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+			.parse()
+			.unwrap();
+		assert_eq!(typed_data_hash(mail()).unwrap(), expected);
+		assert_eq!(
+			typed_data_hash(serde_json::Value::String(mail().to_string())).unwrap(),
+			expected
+		);
+		assert!(typed_data_hash(serde_json::json!({ "primaryType": "Mail" })).is_err());
+	}
+
+	#[test]
+	fn signs_typed_data_with_dev_accounts() {
+		let signer = EthDevSigner::new();
+		let address = signer.accounts()[0];
+		let dev = Dev::new(vec![Box::new(signer)]);
+
+		let signature = dev.sign_typed_data_v4(address, mail()).unwrap().into_vec();
+		assert_eq!(signature.len(), 65);
+		assert!(signature[64] == 27 || signature[64] == 28);
+
+		let hash = typed_data_hash(mail()).unwrap();
+		let recovered = libsecp256k1::recover(
+			&libsecp256k1::Message::parse(&hash.0),
+			&libsecp256k1::Signature::parse_standard_slice(&signature[..64]).unwrap(),
+			&libsecp256k1::RecoveryId::parse_rpc(signature[64]).unwrap(),
+		)
+		.unwrap();
+		assert_eq!(crate::signer::public_key_address(&recovered), address);
+
+		assert!(dev
+			.sign_typed_data_v4(H160::repeat_byte(0x01), mail())
+			.is_err());
+	}
+}
@@ -33,7 +33,7 @@ use fc_rpc_core::types::*;
 use fp_rpc::EthereumRuntimeRPCApi;
 
 use crate::{
-	eth::{rich_block_build, BlockInfo, Eth},
+	eth::{rich_block_build, set_withdrawals, BlockInfo, Eth},
 	frontier_backend_client, internal_err,
 };
 
@@ -64,6 +64,11 @@ where
 					full,
 					Some(base_fee),
 					false,
+					&self.sender_recovery,
+				);
+				set_withdrawals(
+					&mut rich_block,
+					self.storage_override.current_withdrawals(substrate_hash),
 				);
 
 				let substrate_hash = H256::from_slice(substrate_hash.as_ref());
@@ -120,6 +125,11 @@ where
 							full,
 							base_fee,
 							false,
+							&self.sender_recovery,
+						);
+						set_withdrawals(
+							&mut rich_block,
+							self.storage_override.current_withdrawals(substrate_hash),
 						);
 
 						let substrate_hash = H256::from_slice(substrate_hash.as_ref());
@@ -175,6 +185,7 @@ where
 						full,
 						base_fee,
 						true,
+						&self.sender_recovery,
 					))),
 					_ => Ok(None),
 				}
@@ -31,6 +31,13 @@ use fp_rpc::EthereumRuntimeRPCApi;
 
 use crate::{eth::Eth, internal_err};
 
+/// Finds the Ethereum address of the node's own authority key, returned by `eth_coinbase`.
+pub trait CoinbaseProvider: Send + Sync {
+	/// Ethereum address of the authority key, `None` if the node has no key in the current
+	/// authority set.
+	fn coinbase(&self) -> Option<H160>;
+}
+
 impl<B, C, P, CT, BE, A, CIDP, EC> Eth<B, C, P, CT, BE, A, CIDP, EC>
 where
 	B: BlockT,
@@ -70,12 +77,11 @@ where
 	}
 
 	pub fn author(&self) -> RpcResult<H160> {
-		let hash = self.client.info().best_hash;
-		let current_block = self
-			.storage_override
-			.current_block(hash)
-			.ok_or_else(|| internal_err("fetching author through override failed"))?;
-		Ok(current_block.header.beneficiary)
+		Ok(self
+			.coinbase_provider
+			.as_ref()
+			.and_then(|provider| provider.coinbase())
+			.unwrap_or_default())
 	}
 
 	pub fn accounts(&self) -> RpcResult<Vec<H160>> {
@@ -92,13 +98,13 @@ where
 		Ok(U256::from(best_number))
 	}
 
-	pub fn chain_id(&self) -> RpcResult<Option<U64>> {
+	pub fn chain_id(&self) -> RpcResult<U64> {
 		let hash = self.client.info().best_hash;
 		let chain_id = self
 			.client
 			.runtime_api()
 			.chain_id(hash)
 			.map_err(|err| internal_err(format!("fetch runtime chain id failed: {err:?}")))?;
-		Ok(Some(U64::from(chain_id)))
+		Ok(U64::from(chain_id))
 	}
 }
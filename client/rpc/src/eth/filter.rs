@@ -24,7 +24,7 @@ use std::{
 };
 
 use ethereum::BlockV2 as EthereumBlock;
-use ethereum_types::{H256, U256};
+use ethereum_types::{Bloom, H256, U256};
 use jsonrpsee::core::{async_trait, RpcResult};
 // Substrate
 use sc_client_api::backend::{Backend, StorageProvider};
@@ -38,10 +38,14 @@ use sp_runtime::{
 	traits::{Block as BlockT, NumberFor, One, Saturating, UniqueSaturatedInto},
 };
 // Frontier
+#[cfg(feature = "logs-pagination")]
+use fc_rpc_core::EthLogsPageApiServer;
 use fc_rpc_core::{types::*, EthFilterApiServer};
 use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
 
-use crate::{cache::EthBlockDataCacheTask, frontier_backend_client, internal_err};
+use crate::{
+	cache::EthBlockDataCacheTask, frontier_backend_client, internal_err, request::RequestTracer,
+};
 
 pub struct EthFilter<B: BlockT, C, BE, A: ChainApi> {
 	client: Arc<C>,
@@ -51,6 +55,7 @@ pub struct EthFilter<B: BlockT, C, BE, A: ChainApi> {
 	max_stored_filters: usize,
 	max_past_logs: u32,
 	block_data_cache: Arc<EthBlockDataCacheTask<B>>,
+	request_tracer: RequestTracer,
 	_marker: PhantomData<BE>,
 }
 
@@ -63,6 +68,7 @@ impl<B: BlockT, C, BE, A: ChainApi> EthFilter<B, C, BE, A> {
 		max_stored_filters: usize,
 		max_past_logs: u32,
 		block_data_cache: Arc<EthBlockDataCacheTask<B>>,
+		request_tracer: RequestTracer,
 	) -> Self {
 		Self {
 			client,
@@ -72,6 +78,7 @@ impl<B: BlockT, C, BE, A: ChainApi> EthFilter<B, C, BE, A> {
 			max_stored_filters,
 			max_past_logs,
 			block_data_cache,
+			request_tracer,
 			_marker: PhantomData,
 		}
 	}
@@ -145,6 +152,101 @@ where
 	}
 }
 
+impl<B, C, BE, A> EthFilter<B, C, BE, A>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: EthereumRuntimeRPCApi<B>,
+	C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
+	BE: Backend<B> + 'static,
+	A: ChainApi<Block = B> + 'static,
+{
+	/// Logs matching `filter`, from block `from` instead of the `fromBlock` of the filter when
+	/// given. Fails on more than `max_logs` logs or on timeout, unless `truncate` which stops the
+	/// search instead, leaving more than `max_logs` logs or returning the first block left to
+	/// search on timeout.
+	async fn filter_logs(
+		&self,
+		filter: &Filter,
+		from: Option<u64>,
+		max_logs: u32,
+		truncate: bool,
+	) -> RpcResult<(Vec<Log>, Option<u64>)> {
+		let client = Arc::clone(&self.client);
+		let block_data_cache = Arc::clone(&self.block_data_cache);
+		let backend = Arc::clone(&self.backend);
+
+		let mut ret: Vec<Log> = Vec::new();
+		let mut resume = None;
+		if let Some(hash) = filter.block_hash {
+			let substrate_hash = match frontier_backend_client::load_hash::<B, C>(
+				client.as_ref(),
+				backend.as_ref(),
+				hash,
+			)
+			.await
+			.map_err(|err| internal_err(format!("{:?}", err)))?
+			{
+				Some(hash) => hash,
+				_ => return Err(crate::err(-32000, "unknown block", None)),
+			};
+
+			let block = block_data_cache.current_block(substrate_hash).await;
+			let statuses = block_data_cache
+				.current_transaction_statuses(substrate_hash)
+				.await;
+			if let (Some(block), Some(statuses)) = (block, statuses) {
+				filter_block_logs(&mut ret, filter, block, statuses);
+			}
+		} else {
+			let best_number = client.info().best_number;
+			let mut current_number = filter
+				.to_block
+				.and_then(|v| v.to_min_block_num())
+				.map(|s| s.unique_saturated_into())
+				.unwrap_or(best_number);
+
+			if current_number > best_number {
+				current_number = best_number;
+			}
+
+			let from_number = from
+				.or_else(|| filter.from_block.and_then(|v| v.to_min_block_num()))
+				.map(|s| s.unique_saturated_into())
+				.unwrap_or(best_number);
+
+			resume = if backend.is_indexed() {
+				filter_range_logs_indexed(
+					client.as_ref(),
+					backend.log_indexer(),
+					&block_data_cache,
+					&mut ret,
+					max_logs,
+					truncate,
+					filter,
+					from_number,
+					current_number,
+				)
+				.await?
+			} else {
+				filter_range_logs(
+					client.as_ref(),
+					backend.bloom_index(),
+					&block_data_cache,
+					&mut ret,
+					max_logs,
+					truncate,
+					filter,
+					from_number,
+					current_number,
+				)
+				.await?
+			};
+		}
+		Ok((ret, resume))
+	}
+}
+
 #[async_trait]
 impl<B, C, BE, A> EthFilterApiServer for EthFilter<B, C, BE, A>
 where
@@ -347,6 +449,7 @@ where
 						&block_data_cache,
 						&mut ret,
 						max_past_logs,
+						false,
 						&filter,
 						from_number,
 						current_number,
@@ -355,9 +458,11 @@ where
 				} else {
 					let _ = filter_range_logs(
 						client.as_ref(),
+						backend.bloom_index(),
 						&block_data_cache,
 						&mut ret,
 						max_past_logs,
+						false,
 						&filter,
 						from_number,
 						current_number,
@@ -372,78 +477,86 @@ where
 
 	async fn filter_logs(&self, index: Index) -> RpcResult<Vec<Log>> {
 		let key = U256::from(index.value());
-		let pool = self.filter_pool.clone();
+		let params = || format!("{:?}", key);
+		self.request_tracer
+			.trace("eth_getFilterLogs", params, async {
+				let pool = self.filter_pool.clone();
+
+				// We want to get the filter, while releasing the pool lock outside
+				// of the async block.
+				let filter_result: RpcResult<Filter> = (|| {
+					let pool = pool
+						.lock()
+						.map_err(|_| internal_err("Filter pool is not available."))?;
+
+					let pool_item = pool.get(&key).ok_or_else(|| {
+						internal_err(format!("Filter id {:?} does not exist.", key))
+					})?;
 
-		// We want to get the filter, while releasing the pool lock outside
-		// of the async block.
-		let filter_result: RpcResult<Filter> = (|| {
-			let pool = pool
-				.lock()
-				.map_err(|_| internal_err("Filter pool is not available."))?;
-
-			let pool_item = pool
-				.get(&key)
-				.ok_or_else(|| internal_err(format!("Filter id {:?} does not exist.", key)))?;
-
-			match &pool_item.filter_type {
-				FilterType::Log(filter) => Ok(filter.clone()),
-				_ => Err(internal_err(format!(
-					"Filter id {:?} is not a Log filter.",
-					key
-				))),
-			}
-		})();
+					match &pool_item.filter_type {
+						FilterType::Log(filter) => Ok(filter.clone()),
+						_ => Err(internal_err(format!(
+							"Filter id {:?} is not a Log filter.",
+							key
+						))),
+					}
+				})();
 
-		let client = Arc::clone(&self.client);
-		let backend = Arc::clone(&self.backend);
-		let block_data_cache = Arc::clone(&self.block_data_cache);
-		let max_past_logs = self.max_past_logs;
+				let client = Arc::clone(&self.client);
+				let backend = Arc::clone(&self.backend);
+				let block_data_cache = Arc::clone(&self.block_data_cache);
+				let max_past_logs = self.max_past_logs;
 
-		let filter = filter_result?;
+				let filter = filter_result?;
 
-		let best_number = client.info().best_number;
-		let mut current_number = filter
-			.to_block
-			.and_then(|v| v.to_min_block_num())
-			.map(|s| s.unique_saturated_into())
-			.unwrap_or(best_number);
+				let best_number = client.info().best_number;
+				let mut current_number = filter
+					.to_block
+					.and_then(|v| v.to_min_block_num())
+					.map(|s| s.unique_saturated_into())
+					.unwrap_or(best_number);
 
-		if current_number > best_number {
-			current_number = best_number;
-		}
+				if current_number > best_number {
+					current_number = best_number;
+				}
 
-		let from_number = filter
-			.from_block
-			.and_then(|v| v.to_min_block_num())
-			.map(|s| s.unique_saturated_into())
-			.unwrap_or(best_number);
+				let from_number = filter
+					.from_block
+					.and_then(|v| v.to_min_block_num())
+					.map(|s| s.unique_saturated_into())
+					.unwrap_or(best_number);
 
-		let mut ret: Vec<Log> = Vec::new();
-		if backend.is_indexed() {
-			let _ = filter_range_logs_indexed(
-				client.as_ref(),
-				backend.log_indexer(),
-				&block_data_cache,
-				&mut ret,
-				max_past_logs,
-				&filter,
-				from_number,
-				current_number,
-			)
-			.await?;
-		} else {
-			let _ = filter_range_logs(
-				client.as_ref(),
-				&block_data_cache,
-				&mut ret,
-				max_past_logs,
-				&filter,
-				from_number,
-				current_number,
-			)
-			.await?;
-		}
-		Ok(ret)
+				let mut ret: Vec<Log> = Vec::new();
+				if backend.is_indexed() {
+					let _ = filter_range_logs_indexed(
+						client.as_ref(),
+						backend.log_indexer(),
+						&block_data_cache,
+						&mut ret,
+						max_past_logs,
+						false,
+						&filter,
+						from_number,
+						current_number,
+					)
+					.await?;
+				} else {
+					let _ = filter_range_logs(
+						client.as_ref(),
+						backend.bloom_index(),
+						&block_data_cache,
+						&mut ret,
+						max_past_logs,
+						false,
+						&filter,
+						from_number,
+						current_number,
+					)
+					.await?;
+				}
+				Ok(ret)
+			})
+			.await
 	}
 
 	fn uninstall_filter(&self, index: Index) -> RpcResult<bool> {
@@ -463,89 +576,91 @@ where
 	}
 
 	async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>> {
-		let client = Arc::clone(&self.client);
-		let block_data_cache = Arc::clone(&self.block_data_cache);
-		let backend = Arc::clone(&self.backend);
-		let max_past_logs = self.max_past_logs;
-
-		let mut ret: Vec<Log> = Vec::new();
-		if let Some(hash) = filter.block_hash {
-			let substrate_hash = match frontier_backend_client::load_hash::<B, C>(
-				client.as_ref(),
-				backend.as_ref(),
-				hash,
-			)
+		let params = || format!("{:?}", filter);
+		self.request_tracer
+			.trace("eth_getLogs", params, async {
+				let (logs, _) = self
+					.filter_logs(&filter, None, self.max_past_logs, false)
+					.await?;
+				Ok(logs)
+			})
 			.await
-			.map_err(|err| internal_err(format!("{:?}", err)))?
-			{
-				Some(hash) => hash,
-				_ => return Err(crate::err(-32000, "unknown block", None)),
-			};
-
-			let block = block_data_cache.current_block(substrate_hash).await;
-			let statuses = block_data_cache
-				.current_transaction_statuses(substrate_hash)
-				.await;
-			if let (Some(block), Some(statuses)) = (block, statuses) {
-				filter_block_logs(&mut ret, &filter, block, statuses);
-			}
-		} else {
-			let best_number = client.info().best_number;
-			let mut current_number = filter
-				.to_block
-				.and_then(|v| v.to_min_block_num())
-				.map(|s| s.unique_saturated_into())
-				.unwrap_or(best_number);
-
-			if current_number > best_number {
-				current_number = best_number;
-			}
+	}
+}
 
-			let from_number = filter
-				.from_block
-				.and_then(|v| v.to_min_block_num())
-				.map(|s| s.unique_saturated_into())
-				.unwrap_or(best_number);
+#[cfg(feature = "logs-pagination")]
+#[async_trait]
+impl<B, C, BE, A> EthLogsPageApiServer for EthFilter<B, C, BE, A>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: EthereumRuntimeRPCApi<B>,
+	C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
+	BE: Backend<B> + 'static,
+	A: ChainApi<Block = B> + 'static,
+{
+	async fn logs_page(&self, filter: Filter, cursor: Option<LogCursor>) -> RpcResult<LogsPage> {
+		// The logs of the cursor block before the cursor are searched again, then skipped.
+		let max_logs = self
+			.max_past_logs
+			.saturating_add(cursor.map_or(0, |cursor| cursor.log_index));
+		let params = || format!("{:?}, {:?}", filter, cursor);
+		self.request_tracer
+			.trace("eth_getLogsPage", params, async {
+				let (logs, resume) = self
+					.filter_logs(
+						&filter,
+						cursor.map(|cursor| cursor.block_number),
+						max_logs,
+						true,
+					)
+					.await?;
+				Ok(logs_page(logs, cursor, self.max_past_logs, resume))
+			})
+			.await
+	}
+}
 
-			if backend.is_indexed() {
-				let _ = filter_range_logs_indexed(
-					client.as_ref(),
-					backend.log_indexer(),
-					&block_data_cache,
-					&mut ret,
-					max_past_logs,
-					&filter,
-					from_number,
-					current_number,
-				)
-				.await?;
-			} else {
-				let _ = filter_range_logs(
-					client.as_ref(),
-					&block_data_cache,
-					&mut ret,
-					max_past_logs,
-					&filter,
-					from_number,
-					current_number,
-				)
-				.await?;
-			}
-		}
-		Ok(ret)
+/// Page of the `logs` from `cursor` on, truncated to `max_logs` logs. `resume` is the first block
+/// left to search when the search stopped early, the next page starting there unless some logs
+/// were left out.
+#[cfg(feature = "logs-pagination")]
+fn logs_page(
+	mut logs: Vec<Log>,
+	cursor: Option<LogCursor>,
+	max_logs: u32,
+	resume: Option<u64>,
+) -> LogsPage {
+	let position = |log: &Log| LogCursor {
+		block_number: log.block_number.unwrap_or_default().low_u64(),
+		log_index: log.log_index.unwrap_or_default().low_u32(),
+	};
+	if let Some(cursor) = cursor {
+		logs.retain(|log| position(log) >= cursor);
 	}
+	let next_cursor = logs.get(max_logs as usize).map(position).or_else(|| {
+		resume.map(|block_number| LogCursor {
+			block_number,
+			log_index: 0,
+		})
+	});
+	logs.truncate(max_logs as usize);
+	LogsPage { logs, next_cursor }
 }
 
+/// Push the logs of the blocks `from..=to` matching `filter` to `ret`, returning the first block
+/// left to search when out of time in `truncate` mode.
 async fn filter_range_logs_indexed<B, C, BE>(
 	_client: &C,
 	backend: &dyn fc_api::LogIndexerBackend<B>,
 	block_data_cache: &EthBlockDataCacheTask<B>,
 	ret: &mut Vec<Log>,
 	max_past_logs: u32,
+	truncate: bool,
 	filter: &Filter,
 	from: NumberFor<B>,
 	to: NumberFor<B>,
-) -> RpcResult<()>
+) -> RpcResult<Option<u64>>
 where
 	B: BlockT,
 	C: ProvideRuntimeApi<B>,
@@ -599,7 +714,18 @@ where
 
 		let mut statuses_cache: BTreeMap<B::Hash, Option<Vec<TransactionStatus>>> = BTreeMap::new();
 
+		let mut previous_block = None;
 		for log in logs.iter() {
+			// Out of time in `truncate` mode, stop between two blocks so that the search can
+			// resume at the next one.
+			if truncate
+				&& begin_request.elapsed() > max_duration
+				&& previous_block != Some(log.block_number)
+			{
+				return Ok(Some(log.block_number as u64));
+			}
+			previous_block = Some(log.block_number);
+
 			let substrate_hash = log.substrate_block_hash;
 
 			let ethereum_block_hash = log.ethereum_block_hash;
@@ -645,13 +771,16 @@ where
 				}
 			}
 			// Check for restrictions
+			if ret.len() as u32 > max_past_logs && truncate {
+				return Ok(None);
+			}
 			if ret.len() as u32 > max_past_logs {
 				return Err(internal_err(format!(
 					"query returned more than {} results",
 					max_past_logs
 				)));
 			}
-			if begin_request.elapsed() > max_duration {
+			if begin_request.elapsed() > max_duration && !truncate {
 				return Err(internal_err(format!(
 					"query timeout of {} seconds exceeded",
 					max_duration.as_secs()
@@ -676,18 +805,22 @@ where
 		time_prepare,
 		timer_fetch.elapsed().as_millis(),
 	);
-	Ok(())
+	Ok(None)
 }
 
+/// Push the logs of the blocks `from..=to` matching `filter` to `ret`, returning the first block
+/// left to search when stopping early in `truncate` mode.
 async fn filter_range_logs<B, C, BE>(
 	client: &C,
+	bloom_index: Option<&dyn fc_api::BloomIndexBackend<B>>,
 	block_data_cache: &EthBlockDataCacheTask<B>,
 	ret: &mut Vec<Log>,
 	max_past_logs: u32,
+	truncate: bool,
 	filter: &Filter,
 	from: NumberFor<B>,
 	to: NumberFor<B>,
-) -> RpcResult<()>
+) -> RpcResult<Option<u64>>
 where
 	B: BlockT,
 	C: ProvideRuntimeApi<B>,
@@ -698,6 +831,31 @@ where
 	// Max request duration of 10 seconds.
 	let max_duration = Duration::from_secs(10);
 	let begin_request = Instant::now();
+	let to_number = UniqueSaturatedInto::<u64>::unique_saturated_into(to);
+	// Whether the search is over, having truncated results or run out of time in `truncate` mode.
+	let check_restrictions = |ret: &Vec<Log>| -> RpcResult<bool> {
+		if ret.len() as u32 > max_past_logs && truncate {
+			return Ok(true);
+		}
+		if ret.len() as u32 > max_past_logs {
+			return Err(internal_err(format!(
+				"query returned more than {} results",
+				max_past_logs
+			)));
+		}
+		if begin_request.elapsed() > max_duration {
+			if truncate {
+				return Ok(true);
+			}
+			return Err(internal_err(format!(
+				"query timeout of {} seconds exceeded",
+				max_duration.as_secs()
+			)));
+		}
+		Ok(false)
+	};
+	// First block left to search after block `number`.
+	let resume = |number: u64| number.checked_add(1).filter(|next| *next <= to_number);
 
 	let mut current_number = from;
 
@@ -711,38 +869,54 @@ where
 	let address_bloom_filter = FilteredParams::address_bloom_filter(&filter.address);
 	let topics_bloom_filter = FilteredParams::topics_bloom_filter(&topics_input);
 
-	while current_number <= to {
-		let id = BlockId::Number(current_number);
-		let substrate_hash = client
-			.expect_block_hash_from_id(&id)
-			.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?;
-
-		let block = block_data_cache.current_block(substrate_hash).await;
-
-		if let Some(block) = block {
-			if FilteredParams::address_in_bloom(block.header.logs_bloom, &address_bloom_filter)
-				&& FilteredParams::topics_in_bloom(block.header.logs_bloom, &topics_bloom_filter)
-			{
-				let statuses = block_data_cache
-					.current_transaction_statuses(substrate_hash)
-					.await;
-				if let Some(statuses) = statuses {
-					filter_block_logs(ret, filter, block, statuses);
+	// Only visit the candidate blocks of the range covered by the bloom index.
+	if let Some(bloom_index) = bloom_index {
+		let candidates = bloom_index_candidates(
+			client,
+			bloom_index,
+			UniqueSaturatedInto::<u64>::unique_saturated_into(from),
+			to_number,
+			&address_bloom_filter,
+			&topics_bloom_filter,
+		)
+		.map_err(|err| internal_err(format!("Bloom index error: {}", err)))?;
+		if let Some((candidates, next)) = candidates {
+			for number in candidates {
+				filter_number_logs(
+					client,
+					block_data_cache,
+					ret,
+					filter,
+					number.unique_saturated_into(),
+					&address_bloom_filter,
+					&topics_bloom_filter,
+				)
+				.await?;
+				if check_restrictions(ret)? {
+					return Ok(resume(number));
 				}
 			}
+			if next > to_number {
+				return Ok(None);
+			}
+			current_number = next.unique_saturated_into();
 		}
+	}
+
+	while current_number <= to {
+		filter_number_logs(
+			client,
+			block_data_cache,
+			ret,
+			filter,
+			current_number,
+			&address_bloom_filter,
+			&topics_bloom_filter,
+		)
+		.await?;
 		// Check for restrictions
-		if ret.len() as u32 > max_past_logs {
-			return Err(internal_err(format!(
-				"query returned more than {} results",
-				max_past_logs
-			)));
-		}
-		if begin_request.elapsed() > max_duration {
-			return Err(internal_err(format!(
-				"query timeout of {} seconds exceeded",
-				max_duration.as_secs()
-			)));
+		if check_restrictions(ret)? {
+			return Ok(resume(current_number.unique_saturated_into()));
 		}
 		if current_number == to {
 			break;
@@ -750,9 +924,182 @@ where
 			current_number = current_number.saturating_add(One::one());
 		}
 	}
+	Ok(None)
+}
+
+async fn filter_number_logs<B, C>(
+	client: &C,
+	block_data_cache: &EthBlockDataCacheTask<B>,
+	ret: &mut Vec<Log>,
+	filter: &Filter,
+	number: NumberFor<B>,
+	address_bloom_filter: &BloomFilter<'_>,
+	topics_bloom_filter: &[BloomFilter<'_>],
+) -> RpcResult<()>
+where
+	B: BlockT,
+	C: HeaderBackend<B>,
+{
+	let id = BlockId::Number(number);
+	let substrate_hash = client
+		.expect_block_hash_from_id(&id)
+		.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?;
+
+	let block = block_data_cache.current_block(substrate_hash).await;
+
+	if let Some(block) = block {
+		if FilteredParams::address_in_bloom(block.header.logs_bloom, address_bloom_filter)
+			&& FilteredParams::topics_in_bloom(block.header.logs_bloom, topics_bloom_filter)
+		{
+			let statuses = block_data_cache
+				.current_transaction_statuses(substrate_hash)
+				.await;
+			if let Some(statuses) = statuses {
+				filter_block_logs(ret, filter, block, statuses);
+			}
+		}
+	}
 	Ok(())
 }
 
+/// Returns the blocks of `[from, to]` covered by the bloom index whose bloom may match the
+/// filter, along with the first block number after the covered range.
+fn bloom_index_candidates<B, C>(
+	client: &C,
+	bloom_index: &dyn fc_api::BloomIndexBackend<B>,
+	from: u64,
+	to: u64,
+	address_bloom_filter: &BloomFilter<'_>,
+	topics_bloom_filter: &[BloomFilter<'_>],
+) -> Result<Option<(Vec<u64>, u64)>, String>
+where
+	B: BlockT,
+	C: HeaderBackend<B>,
+{
+	let Some((tip, tip_hash)) = bloom_index.indexed_tip()? else {
+		return Ok(None);
+	};
+	// The index can lag behind a reorg, it is only up to date if its tip is still canonical.
+	let canonical_hash = client
+		.hash(tip.unique_saturated_into())
+		.map_err(|e| format!("{:?}", e))?;
+	if canonical_hash != Some(tip_hash) {
+		return Ok(None);
+	}
+	let end = to.min(tip);
+	if from > end {
+		return Ok(None);
+	}
+
+	let candidates = section_candidates(
+		bloom_index,
+		from,
+		end,
+		address_bloom_filter,
+		topics_bloom_filter,
+	)?;
+	Ok(Some((candidates, end + 1)))
+}
+
+/// Returns the blocks of `[from, end]`, all indexed, whose bloom may match the filter according
+/// to the bit vectors of their sections.
+fn section_candidates<B: BlockT>(
+	bloom_index: &dyn fc_api::BloomIndexBackend<B>,
+	from: u64,
+	end: u64,
+	address_bloom_filter: &BloomFilter<'_>,
+	topics_bloom_filter: &[BloomFilter<'_>],
+) -> Result<Vec<u64>, String> {
+	let section_size = bloom_index.section_size();
+	let mut candidates = Vec::new();
+	for section in from / section_size..=end / section_size {
+		let mut section_bits = SectionBits {
+			bloom_index,
+			section,
+			len: (section_size / 8) as usize,
+			cache: BTreeMap::new(),
+		};
+		let mut mask = section_bits.any_of(address_bloom_filter)?;
+		if !topics_bloom_filter.is_empty() {
+			let mut topics_mask = section_bits.none();
+			for subset in topics_bloom_filter {
+				let mut subset_mask = section_bits.all();
+				for bloom in subset {
+					and_assign(&mut subset_mask, &section_bits.bloom(bloom)?);
+				}
+				or_assign(&mut topics_mask, &subset_mask);
+			}
+			and_assign(&mut mask, &topics_mask);
+		}
+
+		let first = section * section_size;
+		for number in from.max(first)..=end.min(first + section_size - 1) {
+			let offset = (number - first) as usize;
+			if mask[offset / 8] & (1 << (offset % 8)) != 0 {
+				candidates.push(number);
+			}
+		}
+	}
+	Ok(candidates)
+}
+
+/// Bloom bit vectors of a bloom index section, fetched on demand.
+struct SectionBits<'a, B: BlockT> {
+	bloom_index: &'a dyn fc_api::BloomIndexBackend<B>,
+	section: u64,
+	len: usize,
+	cache: BTreeMap<usize, Vec<u8>>,
+}
+
+impl<'a, B: BlockT> SectionBits<'a, B> {
+	fn all(&self) -> Vec<u8> {
+		vec![0xff; self.len]
+	}
+
+	fn none(&self) -> Vec<u8> {
+		vec![0; self.len]
+	}
+
+	/// Blocks of the section whose bloom may contain the given input. Wildcards match all.
+	fn bloom(&mut self, bloom: &Option<Bloom>) -> Result<Vec<u8>, String> {
+		let mut mask = self.all();
+		if let Some(bloom) = bloom {
+			for bit in fc_api::bloom_bit_positions(bloom.as_bytes()) {
+				if !self.cache.contains_key(&bit) {
+					let bits = self
+						.bloom_index
+						.section_bits(bit, self.section)?
+						.unwrap_or_else(|| self.none());
+					self.cache.insert(bit, bits);
+				}
+				and_assign(&mut mask, &self.cache[&bit]);
+			}
+		}
+		Ok(mask)
+	}
+
+	/// Blocks of the section whose bloom may contain any of the given inputs. An empty
+	/// filter matches all.
+	fn any_of(&mut self, bloom_filter: &BloomFilter<'_>) -> Result<Vec<u8>, String> {
+		if bloom_filter.is_empty() {
+			return Ok(self.all());
+		}
+		let mut mask = self.none();
+		for bloom in bloom_filter {
+			or_assign(&mut mask, &self.bloom(bloom)?);
+		}
+		Ok(mask)
+	}
+}
+
+fn and_assign(mask: &mut [u8], other: &[u8]) {
+	mask.iter_mut().zip(other).for_each(|(a, b)| *a &= b);
+}
+
+fn or_assign(mask: &mut [u8], other: &[u8]) {
+	mask.iter_mut().zip(other).for_each(|(a, b)| *a |= b);
+}
+
 fn filter_block_logs<'a>(
 	ret: &'a mut Vec<Log>,
 	filter: &'a Filter,
@@ -812,3 +1159,217 @@ fn filter_block_logs<'a>(
 	}
 	ret
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum_types::{BloomInput, H160};
+
+	/// Bloom index over 16 block sections.
+	struct TestBloomIndex(BTreeMap<(usize, u64), Vec<u8>>);
+
+	impl TestBloomIndex {
+		const SECTION_SIZE: u64 = 16;
+
+		fn new(blooms: &[Bloom]) -> Self {
+			let mut vectors = BTreeMap::new();
+			for (number, bloom) in blooms.iter().enumerate() {
+				let section = number as u64 / Self::SECTION_SIZE;
+				let offset = number % Self::SECTION_SIZE as usize;
+				for bit in fc_api::bloom_bit_positions(bloom.as_bytes()) {
+					let bits = vectors
+						.entry((bit, section))
+						.or_insert_with(|| vec![0; Self::SECTION_SIZE as usize / 8]);
+					bits[offset / 8] |= 1 << (offset % 8);
+				}
+			}
+			Self(vectors)
+		}
+	}
+
+	impl fc_api::BloomIndexBackend<substrate_test_runtime_client::runtime::Block> for TestBloomIndex {
+		fn section_size(&self) -> u64 {
+			Self::SECTION_SIZE
+		}
+
+		fn indexed_tip(&self) -> Result<Option<(u64, H256)>, String> {
+			Ok(None)
+		}
+
+		fn section_bits(&self, bit: usize, section: u64) -> Result<Option<Vec<u8>>, String> {
+			Ok(self.0.get(&(bit, section)).cloned())
+		}
+	}
+
+	fn bloom(inputs: &[&[u8]]) -> Bloom {
+		let mut bloom = Bloom::default();
+		for input in inputs {
+			bloom.accrue(BloomInput::Raw(input));
+		}
+		bloom
+	}
+
+	#[test]
+	fn section_candidates_match_the_block_blooms() {
+		let (alice, bob) = (H160::repeat_byte(0xaa), H160::repeat_byte(0xbb));
+		let (transfer, approval) = (H256::repeat_byte(0x01), H256::repeat_byte(0x02));
+		// 3 sections, the last one partially filled.
+		let mut blooms = vec![Bloom::default(); 40];
+		blooms[3] = bloom(&[alice.as_bytes(), transfer.as_bytes()]);
+		blooms[15] = bloom(&[bob.as_bytes(), approval.as_bytes()]);
+		blooms[16] = bloom(&[alice.as_bytes(), approval.as_bytes()]);
+		blooms[33] = bloom(&[
+			alice.as_bytes(),
+			bob.as_bytes(),
+			transfer.as_bytes(),
+			approval.as_bytes(),
+		]);
+		let bloom_index = TestBloomIndex::new(&blooms);
+
+		let addresses = |addresses: &[H160]| -> BloomFilter<'static> {
+			addresses
+				.iter()
+				.map(|address| Some(bloom(&[address.as_bytes()])))
+				.collect()
+		};
+		let topic = |topic: &H256| Some(bloom(&[topic.as_bytes()]));
+		let filters: Vec<(BloomFilter, Vec<BloomFilter>)> = vec![
+			(addresses(&[]), vec![]),
+			(addresses(&[alice]), vec![]),
+			(addresses(&[alice, bob]), vec![]),
+			(vec![None], vec![]),
+			(addresses(&[bob]), vec![vec![topic(&approval)]]),
+			// Both topics.
+			(
+				addresses(&[alice]),
+				vec![vec![topic(&transfer), topic(&approval)]],
+			),
+			// Either topic.
+			(
+				addresses(&[]),
+				vec![vec![topic(&transfer)], vec![None, topic(&approval)]],
+			),
+			(addresses(&[H160::repeat_byte(0xcc)]), vec![]),
+		];
+
+		for (from, end) in [(0, 39), (3, 3), (4, 33), (15, 16), (17, 32)] {
+			for (address_bloom_filter, topics_bloom_filter) in &filters {
+				let candidates = section_candidates(
+					&bloom_index,
+					from,
+					end,
+					address_bloom_filter,
+					topics_bloom_filter,
+				)
+				.expect("sections are read");
+				let expected = (from..=end)
+					.filter(|number| {
+						let block_bloom = blooms[*number as usize];
+						FilteredParams::address_in_bloom(block_bloom, address_bloom_filter)
+							&& FilteredParams::topics_in_bloom(block_bloom, topics_bloom_filter)
+					})
+					.collect::<Vec<_>>();
+				assert_eq!(candidates, expected, "blocks {from} to {end}");
+			}
+		}
+
+		assert_eq!(
+			section_candidates(&bloom_index, 0, 39, &addresses(&[alice]), &[]).unwrap(),
+			vec![3, 16, 33]
+		);
+		assert_eq!(
+			section_candidates(
+				&bloom_index,
+				0,
+				39,
+				&addresses(&[]),
+				&[vec![topic(&transfer), topic(&approval)]],
+			)
+			.unwrap(),
+			vec![33]
+		);
+	}
+
+	#[cfg(feature = "logs-pagination")]
+	fn log(block_number: u64, log_index: u32) -> Log {
+		Log {
+			address: Default::default(),
+			topics: Vec::new(),
+			data: Bytes(Vec::new()),
+			block_hash: None,
+			block_number: Some(U256::from(block_number)),
+			transaction_hash: None,
+			transaction_index: None,
+			log_index: Some(U256::from(log_index)),
+			transaction_log_index: None,
+			removed: false,
+		}
+	}
+
+	#[cfg(feature = "logs-pagination")]
+	fn positions(page: &LogsPage) -> Vec<(u64, u32)> {
+		page.logs
+			.iter()
+			.map(|log| {
+				(
+					log.block_number.unwrap().low_u64(),
+					log.log_index.unwrap().low_u32(),
+				)
+			})
+			.collect()
+	}
+
+	#[cfg(feature = "logs-pagination")]
+	#[test]
+	fn logs_pages_continue_at_their_cursor() {
+		let logs = vec![log(1, 0), log(1, 1), log(1, 2), log(3, 0), log(4, 0)];
+
+		let page = logs_page(logs.clone(), None, 2, None);
+		assert_eq!(positions(&page), vec![(1, 0), (1, 1)]);
+		let cursor = page.next_cursor.unwrap();
+		assert_eq!(
+			cursor,
+			LogCursor {
+				block_number: 1,
+				log_index: 2
+			}
+		);
+
+		// The search restarts at the cursor block.
+		let page = logs_page(logs.clone(), Some(cursor), 2, None);
+		assert_eq!(positions(&page), vec![(1, 2), (3, 0)]);
+		let cursor = page.next_cursor.unwrap();
+
+		let page = logs_page(logs[3..].to_vec(), Some(cursor), 2, None);
+		assert_eq!(positions(&page), vec![(4, 0)]);
+		assert_eq!(page.next_cursor, None);
+	}
+
+	#[cfg(feature = "logs-pagination")]
+	#[test]
+	fn timed_out_logs_pages_continue_at_the_next_block() {
+		let logs = vec![log(1, 0), log(1, 1), log(3, 0)];
+
+		// Out of time after block 5, every log found so far is returned.
+		let page = logs_page(logs.clone(), None, 4, Some(6));
+		assert_eq!(positions(&page), vec![(1, 0), (1, 1), (3, 0)]);
+		assert_eq!(
+			page.next_cursor,
+			Some(LogCursor {
+				block_number: 6,
+				log_index: 0
+			})
+		);
+
+		// Logs left out come first.
+		let page = logs_page(logs, None, 2, Some(6));
+		assert_eq!(positions(&page), vec![(1, 0), (1, 1)]);
+		assert_eq!(
+			page.next_cursor,
+			Some(LogCursor {
+				block_number: 3,
+				log_index: 0
+			})
+		);
+	}
+}
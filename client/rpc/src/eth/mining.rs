@@ -31,14 +31,17 @@ where
 	B: BlockT,
 	A: ChainApi<Block = B>,
 {
+	/// Always false: Frontier uses PoS consensus.
 	pub fn is_mining(&self) -> RpcResult<bool> {
-		Ok(self.is_authority)
+		Ok(false)
 	}
 
+	/// Always zero, there is no proof of work to hash.
 	pub fn hashrate(&self) -> RpcResult<U256> {
 		Ok(U256::zero())
 	}
 
+	/// Always three zero hashes, for mining software probing the node to fail gracefully.
 	pub fn work(&self) -> RpcResult<Work> {
 		Ok(Work::default())
 	}
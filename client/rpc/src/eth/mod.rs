@@ -28,7 +28,12 @@ mod state;
 mod submit;
 mod transaction;
 
-use std::{collections::BTreeMap, marker::PhantomData, sync::Arc};
+use std::{
+	cmp::Reverse,
+	collections::{BTreeMap, BinaryHeap, VecDeque},
+	marker::PhantomData,
+	sync::Arc,
+};
 
 use ethereum::{BlockV2 as EthereumBlock, TransactionV2 as EthereumTransaction};
 use ethereum_types::{H160, H256, H64, U256, U64};
@@ -53,11 +58,11 @@ use fp_rpc::{
 };
 
 use crate::{
-	cache::EthBlockDataCacheTask, frontier_backend_client, internal_err, public_key,
-	signer::EthSigner,
+	cache::EthBlockDataCacheTask, frontier_backend_client, internal_err, request::RequestTracer,
+	sender_recovery::SenderRecovery, signer::EthSigner, submission::SubmissionLimiter,
 };
 
-pub use self::{execute::EstimateGasAdapter, filter::EthFilter};
+pub use self::{client::CoinbaseProvider, execute::EstimateGasAdapter, filter::EthFilter};
 
 // Configuration trait for RPC configuration.
 pub trait EthConfig<B: BlockT, C>: Send + Sync + 'static {
@@ -77,7 +82,8 @@ pub struct Eth<B: BlockT, C, P, CT, BE, A: ChainApi, CIDP, EC> {
 	client: Arc<C>,
 	convert_transaction: Option<CT>,
 	sync: Arc<SyncingService<B>>,
-	is_authority: bool,
+	/// Finds the address returned by eth_coinbase, zero when unset.
+	coinbase_provider: Option<Box<dyn CoinbaseProvider>>,
 	signers: Vec<Box<dyn EthSigner>>,
 	storage_override: Arc<dyn StorageOverride<B>>,
 	backend: Arc<dyn fc_api::Backend<B>>,
@@ -87,6 +93,12 @@ pub struct Eth<B: BlockT, C, P, CT, BE, A: ChainApi, CIDP, EC> {
 	/// When using eth_call/eth_estimateGas, the maximum allowed gas limit will be
 	/// block.gas_limit * execute_gas_limit_multiplier
 	execute_gas_limit_multiplier: u64,
+	/// Logs eth_call/eth_estimateGas under a request id.
+	request_tracer: RequestTracer,
+	/// Throttles eth_sendRawTransaction.
+	submission_limiter: Arc<SubmissionLimiter>,
+	/// Builds the transaction objects of blocks and of the pool over several threads.
+	sender_recovery: SenderRecovery,
 	forced_parent_hashes: Option<BTreeMap<H256, H256>>,
 	/// Something that can create the inherent data providers for pending state.
 	pending_create_inherent_data_providers: CIDP,
@@ -112,11 +124,14 @@ where
 		signers: Vec<Box<dyn EthSigner>>,
 		storage_override: Arc<dyn StorageOverride<B>>,
 		backend: Arc<dyn fc_api::Backend<B>>,
-		is_authority: bool,
+		coinbase_provider: Option<Box<dyn CoinbaseProvider>>,
 		block_data_cache: Arc<EthBlockDataCacheTask<B>>,
 		fee_history_cache: FeeHistoryCache,
 		fee_history_cache_limit: FeeHistoryCacheLimit,
 		execute_gas_limit_multiplier: u64,
+		request_tracer: RequestTracer,
+		submission_limiter: Arc<SubmissionLimiter>,
+		sender_recovery: SenderRecovery,
 		forced_parent_hashes: Option<BTreeMap<H256, H256>>,
 		pending_create_inherent_data_providers: CIDP,
 		pending_consensus_data_provider: Option<Box<dyn pending::ConsensusDataProvider<B>>>,
@@ -127,7 +142,7 @@ where
 			graph,
 			convert_transaction,
 			sync,
-			is_authority,
+			coinbase_provider,
 			signers,
 			storage_override,
 			backend,
@@ -135,6 +150,9 @@ where
 			fee_history_cache,
 			fee_history_cache_limit,
 			execute_gas_limit_multiplier,
+			request_tracer,
+			submission_limiter,
+			sender_recovery,
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			pending_consensus_data_provider,
@@ -260,7 +278,7 @@ where
 			graph,
 			convert_transaction,
 			sync,
-			is_authority,
+			coinbase_provider,
 			signers,
 			storage_override,
 			backend,
@@ -268,6 +286,9 @@ where
 			fee_history_cache,
 			fee_history_cache_limit,
 			execute_gas_limit_multiplier,
+			request_tracer,
+			submission_limiter,
+			sender_recovery,
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			pending_consensus_data_provider,
@@ -280,7 +301,7 @@ where
 			graph,
 			convert_transaction,
 			sync,
-			is_authority,
+			coinbase_provider,
 			signers,
 			storage_override,
 			backend,
@@ -288,6 +309,9 @@ where
 			fee_history_cache,
 			fee_history_cache_limit,
 			execute_gas_limit_multiplier,
+			request_tracer,
+			submission_limiter,
+			sender_recovery,
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			pending_consensus_data_provider,
@@ -334,7 +358,7 @@ where
 		self.block_number()
 	}
 
-	fn chain_id(&self) -> RpcResult<Option<U64>> {
+	fn chain_id(&self) -> RpcResult<U64> {
 		self.chain_id()
 	}
 
@@ -426,6 +450,10 @@ where
 		self.transaction_receipt(&block_info, hash, index).await
 	}
 
+	fn pending_transactions(&self) -> RpcResult<Vec<Transaction>> {
+		self.pending_transactions()
+	}
+
 	// ########################################################################
 	// State
 	// ########################################################################
@@ -473,7 +501,14 @@ where
 		number_or_hash: Option<BlockNumberOrHash>,
 		state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
 	) -> RpcResult<Bytes> {
-		self.call(request, number_or_hash, state_overrides).await
+		let params = || format!("{}, {:?}", request_summary(&request), number_or_hash);
+		self.request_tracer
+			.trace(
+				"eth_call",
+				params,
+				self.call(request.clone(), number_or_hash, state_overrides),
+			)
+			.await
 	}
 
 	async fn estimate_gas(
@@ -481,7 +516,14 @@ where
 		request: TransactionRequest,
 		number_or_hash: Option<BlockNumberOrHash>,
 	) -> RpcResult<U256> {
-		self.estimate_gas(request, number_or_hash).await
+		let params = || format!("{}, {:?}", request_summary(&request), number_or_hash);
+		self.request_tracer
+			.trace(
+				"eth_estimateGas",
+				params,
+				self.estimate_gas(request.clone(), number_or_hash),
+			)
+			.await
 	}
 
 	// ########################################################################
@@ -550,6 +592,7 @@ fn rich_block_build(
 	full_transactions: bool,
 	base_fee: Option<U256>,
 	is_pending: bool,
+	sender_recovery: &SenderRecovery,
 ) -> RichBlock {
 	let (hash, miner, nonce, total_difficulty) = if !is_pending {
 		(
@@ -586,16 +629,31 @@ fn rich_block_build(
 			uncles: vec![],
 			transactions: {
 				if full_transactions {
+					// Only recover the senders when they are not known from the statuses.
+					let senders = match statuses
+						.iter()
+						.map(|status| status.as_ref().map(|status| status.from))
+						.collect::<Option<Vec<_>>>()
+					{
+						Some(senders) => senders,
+						None => sender_recovery
+							.senders(&block.transactions)
+							.into_iter()
+							.map(Option::unwrap_or_default)
+							.collect(),
+					};
 					BlockTransactions::Full(
 						block
 							.transactions
 							.iter()
-							.enumerate()
-							.map(|(index, transaction)| {
+							.zip(senders)
+							.zip(&statuses)
+							.map(|((transaction, from), status)| {
 								transaction_build(
 									transaction,
+									from,
 									Some(&block),
-									statuses[index].as_ref(),
+									status.as_ref(),
 									base_fee,
 								)
 							})
@@ -613,31 +671,61 @@ fn rich_block_build(
 			},
 			size: Some(U256::from(rlp::encode(&block).len() as u32)),
 			base_fee_per_gas: base_fee,
+			withdrawals: None,
+			withdrawals_root: None,
 		},
 		extra_info: BTreeMap::new(),
 	}
 }
 
+/// Set the EIP-4895 fields of `rich_block` to the `withdrawals` its block processed, if any.
+fn set_withdrawals(rich_block: &mut RichBlock, withdrawals: Option<Vec<fp_rpc::Withdrawal>>) {
+	if let Some(withdrawals) = withdrawals {
+		rich_block.inner.withdrawals_root = Some(withdrawals_root(&withdrawals));
+		rich_block.inner.withdrawals = Some(
+			withdrawals
+				.into_iter()
+				.map(|withdrawal| Withdrawal {
+					index: U64::from(withdrawal.index),
+					validator_index: U64::from(withdrawal.validator_index),
+					address: withdrawal.address,
+					amount: U64::from(withdrawal.amount),
+				})
+				.collect(),
+		);
+	}
+}
+
+/// Root of the trie of the RLP encoded `withdrawals`, keyed by their position.
+fn withdrawals_root(withdrawals: &[fp_rpc::Withdrawal]) -> H256 {
+	ethereum::util::ordered_trie_root(withdrawals.iter().map(|withdrawal| {
+		let mut stream = rlp::RlpStream::new_list(4);
+		stream.append(&withdrawal.index);
+		stream.append(&withdrawal.validator_index);
+		stream.append(&withdrawal.address);
+		stream.append(&withdrawal.amount);
+		stream.out().to_vec()
+	}))
+}
+
+/// Summary of a call request for the logs, leaving the input out.
+fn request_summary(request: &TransactionRequest) -> String {
+	format!(
+		"from: {:?}, to: {:?}, gas: {:?}, input: {} bytes",
+		request.from,
+		request.to,
+		request.gas,
+		request.data().map_or(0, |data| data.0.len()),
+	)
+}
+
 fn transaction_build(
 	ethereum_transaction: &EthereumTransaction,
+	from: H160,
 	block: Option<&EthereumBlock>,
 	status: Option<&TransactionStatus>,
 	base_fee: Option<U256>,
 ) -> Transaction {
-	let pubkey = match public_key(ethereum_transaction) {
-		Ok(p) => Some(p),
-		Err(_) => None,
-	};
-	let from = status.map_or(
-		{
-			match pubkey {
-				Some(pk) => H160::from(H256::from(keccak_256(&pk))),
-				_ => H160::default(),
-			}
-		},
-		|status| status.from,
-	);
-
 	let mut transaction: Transaction = Transaction::build_from(from, ethereum_transaction);
 
 	if let EthereumTransaction::EIP1559(_) = ethereum_transaction {
@@ -673,6 +761,57 @@ fn transaction_build(
 	transaction
 }
 
+/// Fee per gas paid to the block author on top of `base_fee`.
+fn priority_fee(ethereum_transaction: &EthereumTransaction, base_fee: U256) -> U256 {
+	match ethereum_transaction {
+		EthereumTransaction::Legacy(t) => t.gas_price.saturating_sub(base_fee),
+		EthereumTransaction::EIP2930(t) => t.gas_price.saturating_sub(base_fee),
+		EthereumTransaction::EIP1559(t) => t
+			.max_fee_per_gas
+			.saturating_sub(base_fee)
+			.min(t.max_priority_fee_per_gas),
+	}
+}
+
+/// Order `transactions`, given as `(sender, priority fee, transaction)` in pool order, by
+/// decreasing priority fee.
+///
+/// Only the next transaction of each sender competes on fee, so that the transactions of a sender
+/// keep their nonce order. Ties keep the pool order.
+fn order_by_priority_fee<T>(transactions: Vec<(H160, U256, T)>) -> Vec<T> {
+	let len = transactions.len();
+	let mut by_sender: BTreeMap<H160, VecDeque<(usize, U256, T)>> = BTreeMap::new();
+	for (index, (sender, fee, transaction)) in transactions.into_iter().enumerate() {
+		by_sender
+			.entry(sender)
+			.or_default()
+			.push_back((index, fee, transaction));
+	}
+
+	let mut heads: BinaryHeap<(U256, Reverse<usize>, H160)> = by_sender
+		.iter()
+		.filter_map(|(sender, queue)| {
+			queue
+				.front()
+				.map(|(index, fee, _)| (*fee, Reverse(*index), *sender))
+		})
+		.collect();
+	let mut ordered = Vec::with_capacity(len);
+	while let Some((_, _, sender)) = heads.pop() {
+		let queue = by_sender
+			.get_mut(&sender)
+			.expect("heads are taken from the queued senders; qed");
+		let (_, _, transaction) = queue
+			.pop_front()
+			.expect("a sender is in the heads while its queue is not empty; qed");
+		ordered.push(transaction);
+		if let Some((index, fee, _)) = queue.front() {
+			heads.push((*fee, Reverse(*index), sender));
+		}
+	}
+	ordered
+}
+
 /// The most commonly used block information in the rpc interfaces.
 #[derive(Clone, Default)]
 pub struct BlockInfo<H> {
@@ -703,3 +842,108 @@ impl<H> BlockInfo<H> {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn withdrawals_root_is_the_trie_root_of_the_withdrawals() {
+		let empty_trie_root: H256 =
+			"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+				.parse()
+				.unwrap();
+		assert_eq!(withdrawals_root(&[]), empty_trie_root);
+
+		let withdrawal = fp_rpc::Withdrawal {
+			index: 0,
+			validator_index: 1,
+			address: H160::repeat_byte(0x42),
+			amount: 100,
+		};
+		let expected: H256 = "0xc4367a6674adfa592605892c9788a70bb475f1d949ebf85d05bbea40e6564af8"
+			.parse()
+			.unwrap();
+		assert_eq!(withdrawals_root(&[withdrawal]), expected);
+	}
+
+	#[test]
+	fn priority_fee_is_capped_by_the_max_fee() {
+		let base_fee = U256::from(1_000);
+		let legacy = EthereumTransaction::Legacy(ethereum::LegacyTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(1_500),
+			gas_limit: U256::from(21_000),
+			action: ethereum::TransactionAction::Call(H160::repeat_byte(0x35)),
+			value: U256::one(),
+			input: vec![],
+			signature: ethereum::TransactionSignature::new(
+				80,
+				H256::repeat_byte(0x01),
+				H256::repeat_byte(0x02),
+			)
+			.unwrap(),
+		});
+		assert_eq!(priority_fee(&legacy, base_fee), U256::from(500));
+		assert_eq!(priority_fee(&legacy, U256::from(2_000)), U256::zero());
+
+		let eip1559 = |max_priority_fee_per_gas: u64, max_fee_per_gas: u64| {
+			EthereumTransaction::EIP1559(ethereum::EIP1559Transaction {
+				chain_id: 42,
+				nonce: U256::zero(),
+				max_priority_fee_per_gas: U256::from(max_priority_fee_per_gas),
+				max_fee_per_gas: U256::from(max_fee_per_gas),
+				gas_limit: U256::from(21_000),
+				action: ethereum::TransactionAction::Call(H160::repeat_byte(0x35)),
+				value: U256::one(),
+				input: vec![],
+				access_list: vec![],
+				odd_y_parity: false,
+				r: H256::repeat_byte(0x01),
+				s: H256::repeat_byte(0x02),
+			})
+		};
+		assert_eq!(
+			priority_fee(&eip1559(100, 2_000), base_fee),
+			U256::from(100)
+		);
+		assert_eq!(
+			priority_fee(&eip1559(800, 1_300), base_fee),
+			U256::from(300)
+		);
+	}
+
+	#[test]
+	fn order_by_priority_fee_keeps_the_nonce_order_of_each_sender() {
+		let alice = H160::repeat_byte(0x01);
+		let bob = H160::repeat_byte(0x02);
+		let transactions = vec![
+			(alice, U256::from(1), "alice nonce 0"),
+			(alice, U256::from(10), "alice nonce 1"),
+			(bob, U256::from(5), "bob nonce 0"),
+			(bob, U256::from(5), "bob nonce 1"),
+		];
+		assert_eq!(
+			order_by_priority_fee(transactions),
+			vec![
+				"bob nonce 0",
+				"bob nonce 1",
+				"alice nonce 0",
+				"alice nonce 1"
+			]
+		);
+	}
+
+	#[test]
+	fn order_by_priority_fee_keeps_the_pool_order_of_ties() {
+		let transactions = vec![
+			(H160::repeat_byte(0x02), U256::from(5), "first"),
+			(H160::repeat_byte(0x01), U256::from(5), "second"),
+			(H160::repeat_byte(0x03), U256::from(7), "highest"),
+		];
+		assert_eq!(
+			order_by_priority_fee(transactions),
+			vec!["highest", "first", "second"]
+		);
+	}
+}
@@ -73,14 +73,11 @@ where
 			},
 		};
 
-		let chain_id = match (request.chain_id, self.chain_id()) {
-			(Some(id), Ok(Some(chain_id))) if id != chain_id => {
-				return Err(internal_err("chain id is mismatch"))
-			}
-			(_, Ok(Some(chain_id))) => chain_id.as_u64(),
-			(_, Ok(None)) => return Err(internal_err("chain id not available")),
-			(_, Err(e)) => return Err(e),
-		};
+		let chain_id = self.chain_id()?;
+		if matches!(request.chain_id, Some(id) if id != chain_id) {
+			return Err(internal_err("chain id is mismatch"));
+		}
+		let chain_id = chain_id.as_u64();
 
 		let block_hash = self.client.info().best_hash;
 
@@ -167,13 +164,18 @@ where
 				Err(_) => return Err(internal_err("decode transaction failed")),
 			};
 		let transaction_hash = transaction.hash();
+		self.submission_limiter.check_known(transaction_hash)?;
+		self.submission_limiter.check_sender(&transaction)?;
 
 		let block_hash = self.client.info().best_hash;
 		let extrinsic = self.convert_transaction(block_hash, transaction)?;
 
 		self.pool
 			.submit_one(block_hash, TransactionSource::Local, extrinsic)
-			.map_ok(move |_| transaction_hash)
+			.map_ok(move |_| {
+				self.submission_limiter.submitted(transaction_hash);
+				transaction_hash
+			})
 			.map_err(|err| internal_err(format::Geth::pool_error(err)))
 			.await
 	}
@@ -34,7 +34,7 @@ use fc_rpc_core::types::*;
 use fp_rpc::EthereumRuntimeRPCApi;
 
 use crate::{
-	eth::{transaction_build, BlockInfo, Eth},
+	eth::{order_by_priority_fee, priority_fee, transaction_build, BlockInfo, Eth},
 	frontier_backend_client, internal_err,
 };
 
@@ -114,7 +114,8 @@ where
 				for txn in ethereum_transactions {
 					let inner_hash = txn.hash();
 					if hash == inner_hash {
-						return Ok(Some(transaction_build(&txn, None, None, None)));
+						let from = self.sender_recovery.sender(&txn).unwrap_or_default();
+						return Ok(Some(transaction_build(&txn, from, None, None, None)));
 					}
 				}
 				// Unknown transaction.
@@ -131,6 +132,7 @@ where
 		match (block, statuses) {
 			(Some(block), Some(statuses)) => Ok(Some(transaction_build(
 				&block.transactions[index],
+				statuses[index].from,
 				Some(&block),
 				Some(&statuses[index]),
 				Some(base_fee),
@@ -159,6 +161,7 @@ where
 				{
 					Ok(Some(transaction_build(
 						transaction,
+						status.from,
 						Some(&block),
 						Some(status),
 						Some(base_fee),
@@ -191,6 +194,7 @@ where
 				{
 					Ok(Some(transaction_build(
 						transaction,
+						status.from,
 						Some(&block),
 						Some(status),
 						Some(base_fee),
@@ -371,4 +375,35 @@ where
 			_ => Ok(None),
 		}
 	}
+
+	pub fn pending_transactions(&self) -> RpcResult<Vec<Transaction>> {
+		// Only the ready queue, the future one waits for missing nonces.
+		let xts: Vec<<B as BlockT>::Extrinsic> = self
+			.graph
+			.validated_pool()
+			.ready()
+			.map(|in_pool_tx| in_pool_tx.data().clone())
+			.collect();
+
+		let best_block = self.client.info().best_hash;
+		let api = self.client.runtime_api();
+		let ethereum_transactions = api.extrinsic_filter(best_block, xts).map_err(|err| {
+			internal_err(format!("fetch runtime extrinsic filter failed: {:?}", err))
+		})?;
+		let base_fee = api
+			.gas_price(best_block)
+			.map_err(|err| internal_err(format!("fetch runtime base fee failed: {:?}", err)))?;
+
+		let senders = self.sender_recovery.senders(&ethereum_transactions);
+		let transactions = ethereum_transactions
+			.iter()
+			.zip(senders)
+			.map(|(txn, from)| {
+				let from = from.unwrap_or_default();
+				let transaction = transaction_build(txn, from, None, None, None);
+				(from, priority_fee(txn, base_fee), transaction)
+			})
+			.collect();
+		Ok(order_by_priority_fee(transactions))
+	}
 }
@@ -0,0 +1,553 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+mod resumable;
+
+use std::{collections::VecDeque, marker::PhantomData, sync::Arc};
+
+use ethereum::{BlockV2 as EthereumBlock, TransactionV2 as EthereumTransaction};
+use ethereum_types::{H256, U256};
+use futures::{future, stream::BoxStream, FutureExt as _, StreamExt as _};
+use jsonrpsee::{
+	core::traits::IdProvider,
+	server::{PendingSubscriptionSink, SubscriptionSink},
+	types::{ErrorObjectOwned, SubscriptionId},
+};
+// Substrate
+use sc_client_api::{
+	backend::{Backend, StorageProvider},
+	client::BlockchainEvents,
+};
+use sc_network_sync::SyncingService;
+use sc_rpc::{
+	utils::{pipe_from_stream, to_sub_message},
+	SubscriptionTaskExecutor,
+};
+use sc_transaction_pool_api::{InPoolTransaction, TransactionPool, TxHash};
+use sc_utils::mpsc::TracingUnboundedReceiver;
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_consensus::SyncOracle;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto};
+// Frontier
+use fc_mapping_sync::{EthereumBlockNotification, EthereumBlockNotificationSinks};
+use fc_rpc_core::{
+	types::{
+		pubsub::{Kind, Params, PubSubResult, PubSubSyncing, ResumableResult, SyncingStatus},
+		FilteredParams,
+	},
+	EthPubSubApiServer,
+};
+use fc_storage::StorageOverride;
+use fp_rpc::EthereumRuntimeRPCApi;
+
+use self::resumable::ResumableSubscriptions;
+pub use self::resumable::{ResumableSubscriptionConfig, RESUME_GAP_ERROR_CODE};
+use crate::internal_err;
+
+#[derive(Debug)]
+pub struct EthereumSubIdProvider;
+impl IdProvider for EthereumSubIdProvider {
+	fn next_id(&self) -> jsonrpsee::types::SubscriptionId<'static> {
+		format!("0x{}", hex::encode(rand::random::<u128>().to_le_bytes())).into()
+	}
+}
+
+/// Recent heads notified to a `newHeads` subscriber, used to detect reorgs and missed blocks.
+struct NotifiedHeads {
+	/// Notified heads as `(number, hash)`, oldest first.
+	heads: VecDeque<(U256, H256)>,
+	capacity: usize,
+}
+
+impl NotifiedHeads {
+	fn new(backfill_limit: u32) -> Self {
+		let capacity = backfill_limit as usize + 1;
+		Self {
+			heads: VecDeque::with_capacity(capacity),
+			capacity,
+		}
+	}
+
+	fn last_hash(&self) -> Option<H256> {
+		self.heads.back().map(|(_, hash)| *hash)
+	}
+
+	fn contains(&self, hash: H256) -> bool {
+		self.heads.iter().any(|(_, notified)| *notified == hash)
+	}
+
+	fn count_from(&self, number: U256) -> u64 {
+		self.heads.iter().filter(|(n, _)| *n >= number).count() as u64
+	}
+
+	fn remove_from(&mut self, number: U256) {
+		self.heads.retain(|(n, _)| *n < number);
+	}
+
+	fn push(&mut self, block: &EthereumBlock) {
+		if self.heads.len() == self.capacity {
+			self.heads.pop_front();
+		}
+		self.heads
+			.push_back((block.header.number, block.header.hash()));
+	}
+}
+
+/// Eth pub-sub API implementation.
+pub struct EthPubSub<B: BlockT, P, C, BE> {
+	pool: Arc<P>,
+	client: Arc<C>,
+	sync: Arc<SyncingService<B>>,
+	executor: SubscriptionTaskExecutor,
+	storage_override: Arc<dyn StorageOverride<B>>,
+	starting_block: u64,
+	pubsub_notification_sinks: Arc<EthereumBlockNotificationSinks<EthereumBlockNotification<B>>>,
+	new_heads_backfill_limit: u32,
+	resumable: Arc<ResumableSubscriptions>,
+	_marker: PhantomData<BE>,
+}
+
+impl<B: BlockT, P, C, BE> Clone for EthPubSub<B, P, C, BE> {
+	fn clone(&self) -> Self {
+		Self {
+			pool: self.pool.clone(),
+			client: self.client.clone(),
+			sync: self.sync.clone(),
+			executor: self.executor.clone(),
+			storage_override: self.storage_override.clone(),
+			starting_block: self.starting_block,
+			pubsub_notification_sinks: self.pubsub_notification_sinks.clone(),
+			new_heads_backfill_limit: self.new_heads_backfill_limit,
+			resumable: self.resumable.clone(),
+			_marker: PhantomData::<BE>,
+		}
+	}
+}
+
+impl<B: BlockT, P, C, BE> EthPubSub<B, P, C, BE>
+where
+	P: TransactionPool<Block = B> + 'static,
+	C: ProvideRuntimeApi<B>,
+	C::Api: EthereumRuntimeRPCApi<B>,
+	C: HeaderBackend<B> + StorageProvider<B, BE>,
+	BE: Backend<B> + 'static,
+{
+	pub fn new(
+		pool: Arc<P>,
+		client: Arc<C>,
+		sync: Arc<SyncingService<B>>,
+		executor: SubscriptionTaskExecutor,
+		storage_override: Arc<dyn StorageOverride<B>>,
+		pubsub_notification_sinks: Arc<
+			EthereumBlockNotificationSinks<EthereumBlockNotification<B>>,
+		>,
+		new_heads_backfill_limit: u32,
+		resumable_subscription_config: ResumableSubscriptionConfig,
+	) -> Self {
+		// Capture the best block as seen on initialization. Used for syncing subscriptions.
+		let best_number = client.info().best_number;
+		let starting_block = UniqueSaturatedInto::<u64>::unique_saturated_into(best_number);
+		Self {
+			pool,
+			client,
+			sync,
+			executor,
+			storage_override,
+			starting_block,
+			pubsub_notification_sinks,
+			new_heads_backfill_limit,
+			resumable: Arc::new(ResumableSubscriptions::new(resumable_subscription_config)),
+			_marker: PhantomData,
+		}
+	}
+
+	fn notify_header(
+		&self,
+		notification: EthereumBlockNotification<B>,
+		notified: &mut NotifiedHeads,
+	) -> future::Ready<Option<Vec<PubSubResult>>> {
+		if !notification.is_new_best {
+			return future::ready(None);
+		}
+		let Some(block) = self.storage_override.current_block(notification.hash) else {
+			return future::ready(None);
+		};
+
+		// Canonical blocks the subscriber missed, newest first.
+		let mut missed = Vec::new();
+		let mut found_ancestor = false;
+		if notified
+			.last_hash()
+			.is_some_and(|hash| hash != block.header.parent_hash)
+		{
+			// Walk back the new canonical chain until reaching a head the subscriber already knows.
+			let mut parent = self.parent_hash(notification.hash);
+			for _ in 0..self.new_heads_backfill_limit {
+				let Some(hash) = parent else {
+					break;
+				};
+				let Some(parent_block) = self.storage_override.current_block(hash) else {
+					break;
+				};
+				if notified.contains(parent_block.header.hash()) {
+					found_ancestor = true;
+					break;
+				}
+				parent = self.parent_hash(hash);
+				missed.push(parent_block);
+			}
+		}
+
+		// Every notified head at or above the lowest block checked against the new canonical
+		// chain has been replaced.
+		let first_number = missed
+			.last()
+			.map_or(block.header.number, |missed| missed.header.number);
+		let reorg_depth = notified.count_from(first_number);
+		if !found_ancestor {
+			// The gap can't be filled without a common ancestor, only notify the new head.
+			missed.clear();
+		}
+		notified.remove_from(first_number);
+
+		let results = missed
+			.into_iter()
+			.rev()
+			.chain(std::iter::once(block))
+			.enumerate()
+			.map(|(index, block)| {
+				notified.push(&block);
+				PubSubResult::header(block, if index == 0 { reorg_depth } else { 0 })
+			})
+			.collect();
+		future::ready(Some(results))
+	}
+
+	fn parent_hash(&self, hash: B::Hash) -> Option<B::Hash> {
+		self.client
+			.header(hash)
+			.ok()
+			.flatten()
+			.map(|header| *header.parent_hash())
+	}
+
+	fn notify_logs(
+		&self,
+		notification: EthereumBlockNotification<B>,
+		params: &FilteredParams,
+	) -> future::Ready<Option<impl Iterator<Item = PubSubResult>>> {
+		let res = if notification.is_new_best {
+			let substrate_hash = notification.hash;
+
+			let block = self.storage_override.current_block(substrate_hash);
+			let receipts = self.storage_override.current_receipts(substrate_hash);
+
+			match (block, receipts) {
+				(Some(block), Some(receipts)) => Some((block, receipts)),
+				_ => None,
+			}
+		} else {
+			None
+		};
+		future::ready(res.map(|(block, receipts)| PubSubResult::logs(block, receipts, params)))
+	}
+
+	fn pending_transaction(&self, hash: &TxHash<P>) -> future::Ready<Option<PubSubResult>> {
+		let res = if let Some(xt) = self.pool.ready_transaction(hash) {
+			let best_block = self.client.info().best_hash;
+
+			let api = self.client.runtime_api();
+
+			let api_version = if let Ok(Some(api_version)) =
+				api.api_version::<dyn EthereumRuntimeRPCApi<B>>(best_block)
+			{
+				api_version
+			} else {
+				return future::ready(None);
+			};
+
+			let xts = vec![xt.data().clone()];
+
+			let txs: Option<Vec<EthereumTransaction>> = if api_version > 1 {
+				api.extrinsic_filter(best_block, xts).ok()
+			} else {
+				#[allow(deprecated)]
+				if let Ok(legacy) = api.extrinsic_filter_before_version_2(best_block, xts) {
+					Some(legacy.into_iter().map(|tx| tx.into()).collect())
+				} else {
+					None
+				}
+			};
+
+			match txs {
+				Some(txs) => {
+					if txs.len() == 1 {
+						Some(txs[0].clone())
+					} else {
+						None
+					}
+				}
+				_ => None,
+			}
+		} else {
+			None
+		};
+		future::ready(res.map(|tx| PubSubResult::transaction_hash(&tx)))
+	}
+
+	async fn syncing_status(&self) -> PubSubSyncing {
+		if self.sync.is_major_syncing() {
+			// Best imported block.
+			let current_number = self.client.info().best_number;
+			// Get the target block to sync.
+			let highest_number = self.sync.best_seen_block().await.ok().flatten();
+
+			PubSubSyncing::Syncing(SyncingStatus {
+				starting_block: self.starting_block,
+				current_block: UniqueSaturatedInto::<u64>::unique_saturated_into(current_number),
+				highest_block: highest_number
+					.map(UniqueSaturatedInto::<u64>::unique_saturated_into),
+			})
+		} else {
+			PubSubSyncing::Synced(false)
+		}
+	}
+}
+
+impl<B: BlockT, P, C, BE> EthPubSub<B, P, C, BE>
+where
+	P: TransactionPool<Block = B> + 'static,
+	C: ProvideRuntimeApi<B>,
+	C::Api: EthereumRuntimeRPCApi<B>,
+	C: BlockchainEvents<B> + 'static,
+	C: HeaderBackend<B> + StorageProvider<B, BE>,
+	BE: Backend<B> + 'static,
+{
+	/// Notifications of a subscription, `None` for `syncing` subscriptions which are notified
+	/// of the syncing status changes instead.
+	fn notification_stream(
+		&self,
+		kind: Kind,
+		params: Option<Params>,
+	) -> Option<BoxStream<'static, PubSubResult>> {
+		let filtered_params = match params {
+			Some(Params::Logs(filter)) => FilteredParams::new(Some(filter)),
+			_ => FilteredParams::default(),
+		};
+
+		let pubsub = self.clone();
+		let block_notification_stream = || {
+			// Everytime a new subscription is created, a new mpsc channel is added to the sink pool.
+			let (inner_sink, block_notification_stream) =
+				sc_utils::mpsc::tracing_unbounded("pubsub_notification_stream", 100_000);
+			self.pubsub_notification_sinks.lock().push(inner_sink);
+			block_notification_stream
+		};
+		match kind {
+			Kind::NewHeads => {
+				let mut notified = NotifiedHeads::new(pubsub.new_heads_backfill_limit);
+				let stream = block_notification_stream()
+					.filter_map(move |notification| {
+						pubsub.notify_header(notification, &mut notified)
+					})
+					.flat_map(futures::stream::iter);
+				Some(stream.boxed())
+			}
+			Kind::Logs => {
+				let stream = block_notification_stream()
+					.filter_map(move |notification| {
+						pubsub.notify_logs(notification, &filtered_params)
+					})
+					.flat_map(futures::stream::iter);
+				Some(stream.boxed())
+			}
+			Kind::NewPendingTransactions => {
+				let pool = pubsub.pool.clone();
+				let stream = pool
+					.import_notification_stream()
+					.filter_map(move |hash| pubsub.pending_transaction(&hash));
+				Some(stream.boxed())
+			}
+			Kind::Syncing => None,
+		}
+	}
+
+	fn reject(&self, pending: PendingSubscriptionSink, e: ErrorObjectOwned) {
+		let fut = async move {
+			let _ = pending.reject(e).await;
+		};
+		self.executor
+			.spawn("frontier-rpc-subscription", Some("rpc"), fut.boxed());
+	}
+
+	fn subscribe_resumable(
+		&self,
+		pending: PendingSubscriptionSink,
+		kind: Kind,
+		params: Option<Params>,
+	) {
+		let stream = match self.notification_stream(kind, params) {
+			Some(stream) => self.resumable.ensure_capacity().map(|()| stream),
+			None => Err(internal_err("Syncing subscriptions can't be resumed")),
+		};
+		let stream = match stream {
+			Ok(stream) => stream,
+			Err(e) => return self.reject(pending, e),
+		};
+
+		let pubsub = self.clone();
+		let fut = async move {
+			let Ok(sink) = pending.accept().await else {
+				return;
+			};
+			// The id assigned by the server is the resume token of the subscription.
+			let resume_token = match sink.subscription_id() {
+				SubscriptionId::Num(id) => id.to_string(),
+				SubscriptionId::Str(id) => id.into_owned(),
+			};
+			let attached = match pubsub
+				.resumable
+				.register(&resume_token)
+				.and_then(|()| pubsub.resumable.attach(&resume_token, None))
+			{
+				Ok(attached) => attached,
+				// Registration raced with other subscriptions, dropping the sink closes it.
+				Err(_) => return,
+			};
+
+			// The subscription is fed independently of its subscriber, to keep buffering the
+			// notifications while it is disconnected.
+			let resumable = pubsub.resumable.clone();
+			let token = resume_token.clone();
+			pubsub.executor.spawn(
+				"frontier-rpc-resumable-subscription",
+				Some("rpc"),
+				async move { resumable.run(token, stream).await }.boxed(),
+			);
+			pubsub.pipe_resumable(sink, resume_token, attached).await;
+		};
+		self.executor
+			.spawn("frontier-rpc-subscription", Some("rpc"), fut.boxed());
+	}
+
+	/// Send the notifications of a resumable subscription to `sink`, until either ends.
+	async fn pipe_resumable(
+		&self,
+		sink: SubscriptionSink,
+		resume_token: String,
+		(generation, mut notifications): (u64, TracingUnboundedReceiver<ResumableResult>),
+	) {
+		loop {
+			match future::select(sink.closed().boxed(), notifications.next()).await {
+				future::Either::Right((Some(notification), _)) => {
+					let msg = to_sub_message(&sink, &notification);
+					if sink.send(msg).await.is_err() {
+						break;
+					}
+				}
+				_ => break,
+			}
+		}
+		self.resumable.detach(&resume_token, generation);
+	}
+}
+
+impl<B: BlockT, P, C, BE> EthPubSubApiServer for EthPubSub<B, P, C, BE>
+where
+	B: BlockT,
+	P: TransactionPool<Block = B> + 'static,
+	C: ProvideRuntimeApi<B>,
+	C::Api: EthereumRuntimeRPCApi<B>,
+	C: BlockchainEvents<B> + 'static,
+	C: HeaderBackend<B> + StorageProvider<B, BE>,
+	BE: Backend<B> + 'static,
+{
+	fn subscribe(
+		&self,
+		pending: PendingSubscriptionSink,
+		kind: Kind,
+		params: Option<Params>,
+		resumable: Option<bool>,
+	) {
+		if resumable.unwrap_or(false) {
+			return self.subscribe_resumable(pending, kind, params);
+		}
+
+		let pubsub = self.clone();
+		let stream = self.notification_stream(kind, params);
+		let fut = async move {
+			match stream {
+				Some(stream) => pipe_from_stream(pending, stream).await,
+				None => {
+					let Ok(sink) = pending.accept().await else {
+						return;
+					};
+					// On connection subscriber expects a value.
+					// Because import notifications are only emitted when the node is synced or
+					// in case of reorg, the first event is emitted right away.
+					let syncing_status = pubsub.syncing_status().await;
+					let msg = to_sub_message(&sink, &PubSubResult::SyncingStatus(syncing_status));
+					let _ = sink.send(msg).await;
+
+					// When the node is not under a major syncing (i.e. from genesis), react
+					// normally to import notifications.
+					//
+					// Only send new notifications down the pipe when the syncing status changed.
+					let mut stream = pubsub.client.import_notification_stream();
+					let mut last_syncing_status = pubsub.sync.is_major_syncing();
+					while (stream.next().await).is_some() {
+						let syncing_status = pubsub.sync.is_major_syncing();
+						if syncing_status != last_syncing_status {
+							let syncing_status = pubsub.syncing_status().await;
+							let msg =
+								to_sub_message(&sink, &PubSubResult::SyncingStatus(syncing_status));
+							let _ = sink.send(msg).await;
+						}
+						last_syncing_status = syncing_status;
+					}
+				}
+			}
+		}
+		.boxed();
+
+		self.executor
+			.spawn("frontier-rpc-subscription", Some("rpc"), fut);
+	}
+
+	fn resubscribe(
+		&self,
+		pending: PendingSubscriptionSink,
+		resume_token: String,
+		last_seen_cursor: u64,
+	) {
+		let attached = match self.resumable.attach(&resume_token, Some(last_seen_cursor)) {
+			Ok(attached) => attached,
+			Err(e) => return self.reject(pending, e),
+		};
+		let pubsub = self.clone();
+		let fut = async move {
+			match pending.accept().await {
+				Ok(sink) => pubsub.pipe_resumable(sink, resume_token, attached).await,
+				Err(_) => pubsub.resumable.detach(&resume_token, attached.0),
+			}
+		};
+		self.executor
+			.spawn("frontier-rpc-subscription", Some("rpc"), fut.boxed());
+	}
+}
@@ -0,0 +1,386 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Resumable subscriptions, whose notifications are buffered to be replayed after a reconnect.
+//!
+//! The resume token of a subscription is the id the server assigned to the `eth_subscribe` call
+//! that created it, so it can't be chosen or guessed by other clients.
+
+use std::{
+	collections::{HashMap, VecDeque},
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+use futures::{
+	future::{self, Either},
+	stream::BoxStream,
+	StreamExt as _,
+};
+use jsonrpsee::types::error::ErrorObjectOwned;
+// Substrate
+use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
+// Frontier
+use fc_rpc_core::types::pubsub::{PubSubResult, ResumableResult};
+
+use crate::{err, internal_err};
+
+/// Maximum number of resumable subscriptions kept at once.
+const MAX_RESUMABLE_SUBSCRIPTIONS: usize = 512;
+
+/// Longest delay before a subscription without notification notices it expired.
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Error code of a resumed subscription whose missed notifications are not buffered anymore.
+pub const RESUME_GAP_ERROR_CODE: i32 = -32010;
+
+/// Configuration of the resumable subscriptions.
+#[derive(Clone, Copy, Debug)]
+pub struct ResumableSubscriptionConfig {
+	/// Maximum number of notifications buffered per subscription, 0 disables resumable
+	/// subscriptions.
+	pub buffer_size: usize,
+	/// How long a subscription is kept without subscriber.
+	pub ttl: Duration,
+}
+
+struct Resumable {
+	/// Notifications still buffered, oldest first.
+	buffer: VecDeque<ResumableResult>,
+	next_cursor: u64,
+	/// Attached subscriber and the generation it was attached with.
+	subscriber: Option<(u64, TracingUnboundedSender<ResumableResult>)>,
+	/// When the subscription is dropped if no subscriber attaches, unset while attached.
+	expires_at: Option<Instant>,
+}
+
+/// Resumable subscriptions by resume token.
+pub(super) struct ResumableSubscriptions {
+	config: ResumableSubscriptionConfig,
+	subscriptions: Mutex<HashMap<String, Resumable>>,
+	next_generation: Mutex<u64>,
+}
+
+impl ResumableSubscriptions {
+	pub fn new(config: ResumableSubscriptionConfig) -> Self {
+		Self {
+			config,
+			subscriptions: Mutex::new(HashMap::new()),
+			next_generation: Mutex::new(0),
+		}
+	}
+
+	fn purge_expired(subscriptions: &mut HashMap<String, Resumable>, now: Instant) {
+		subscriptions.retain(|_, resumable| resumable.expires_at.map_or(true, |at| at > now));
+	}
+
+	fn check_capacity(
+		&self,
+		subscriptions: &mut HashMap<String, Resumable>,
+		now: Instant,
+	) -> Result<(), ErrorObjectOwned> {
+		if self.config.buffer_size == 0 {
+			return Err(internal_err("Resumable subscriptions are disabled"));
+		}
+		Self::purge_expired(subscriptions, now);
+		if subscriptions.len() >= MAX_RESUMABLE_SUBSCRIPTIONS {
+			return Err(internal_err("Too many resumable subscriptions"));
+		}
+		Ok(())
+	}
+
+	/// Check that a new resumable subscription can be registered, before accepting it.
+	pub fn ensure_capacity(&self) -> Result<(), ErrorObjectOwned> {
+		let mut subscriptions = self.subscriptions.lock().expect("lock poisoned");
+		self.check_capacity(&mut subscriptions, Instant::now())
+	}
+
+	/// Register a new resumable subscription under `token`, without subscriber.
+	pub fn register(&self, token: &str) -> Result<(), ErrorObjectOwned> {
+		let now = Instant::now();
+		let mut subscriptions = self.subscriptions.lock().expect("lock poisoned");
+		self.check_capacity(&mut subscriptions, now)?;
+		if subscriptions.contains_key(token) {
+			return Err(internal_err("Resume token already in use"));
+		}
+		subscriptions.insert(
+			token.to_string(),
+			Resumable {
+				buffer: VecDeque::with_capacity(self.config.buffer_size),
+				next_cursor: 1,
+				subscriber: None,
+				expires_at: Some(now + self.config.ttl),
+			},
+		);
+		Ok(())
+	}
+
+	/// Attach a subscriber to the subscription registered under `token`, replacing the
+	/// previous one. The notifications after `last_seen_cursor` are replayed first.
+	///
+	/// Returns the generation of the subscriber, to detach it, and its notifications.
+	pub fn attach(
+		&self,
+		token: &str,
+		last_seen_cursor: Option<u64>,
+	) -> Result<(u64, TracingUnboundedReceiver<ResumableResult>), ErrorObjectOwned> {
+		let mut subscriptions = self.subscriptions.lock().expect("lock poisoned");
+		Self::purge_expired(&mut subscriptions, Instant::now());
+		let Some(resumable) = subscriptions.get_mut(token) else {
+			return Err(internal_err("Unknown or expired resume token"));
+		};
+
+		let (sender, receiver) = tracing_unbounded("pubsub_resumable_subscription_stream", 100_000);
+		if let Some(last_seen_cursor) = last_seen_cursor {
+			if last_seen_cursor >= resumable.next_cursor {
+				return Err(internal_err(format!(
+					"Cursor {} was not notified yet",
+					last_seen_cursor
+				)));
+			}
+			let oldest_cursor = resumable
+				.buffer
+				.front()
+				.map_or(resumable.next_cursor, |notification| notification.cursor);
+			if last_seen_cursor + 1 < oldest_cursor {
+				return Err(err(
+					RESUME_GAP_ERROR_CODE,
+					format!(
+						"Notifications before cursor {} are not buffered anymore, backfill with eth_getLogs",
+						oldest_cursor
+					),
+					None,
+				));
+			}
+			for notification in &resumable.buffer {
+				if notification.cursor > last_seen_cursor {
+					let _ = sender.unbounded_send(notification.clone());
+				}
+			}
+		}
+
+		let generation = {
+			let mut next_generation = self.next_generation.lock().expect("lock poisoned");
+			*next_generation += 1;
+			*next_generation
+		};
+		resumable.subscriber = Some((generation, sender));
+		resumable.expires_at = None;
+		Ok((generation, receiver))
+	}
+
+	/// Detach the subscriber of the given generation, starting the expiry of the subscription.
+	pub fn detach(&self, token: &str, generation: u64) {
+		let mut subscriptions = self.subscriptions.lock().expect("lock poisoned");
+		if let Some(resumable) = subscriptions.get_mut(token) {
+			if matches!(resumable.subscriber, Some((attached, _)) if attached == generation) {
+				resumable.subscriber = None;
+				resumable.expires_at = Some(Instant::now() + self.config.ttl);
+			}
+		}
+	}
+
+	/// Buffer a notification and send it to the attached subscriber, if any.
+	///
+	/// Returns false once the subscription expired.
+	fn push(&self, token: &str, result: PubSubResult) -> bool {
+		let now = Instant::now();
+		let mut subscriptions = self.subscriptions.lock().expect("lock poisoned");
+		let Some(resumable) = subscriptions.get_mut(token) else {
+			return false;
+		};
+		if resumable.expires_at.is_some_and(|at| at <= now) {
+			subscriptions.remove(token);
+			return false;
+		}
+
+		let notification = ResumableResult {
+			cursor: resumable.next_cursor,
+			result,
+		};
+		resumable.next_cursor += 1;
+		if resumable.buffer.len() == self.config.buffer_size {
+			resumable.buffer.pop_front();
+		}
+		resumable.buffer.push_back(notification.clone());
+		if let Some((_, sender)) = &resumable.subscriber {
+			if sender.unbounded_send(notification).is_err() {
+				resumable.subscriber = None;
+				resumable.expires_at = Some(now + self.config.ttl);
+			}
+		}
+		true
+	}
+
+	/// Drop the subscription registered under `token` if it expired.
+	///
+	/// Returns false once the subscription expired.
+	fn check_expiry(&self, token: &str) -> bool {
+		let mut subscriptions = self.subscriptions.lock().expect("lock poisoned");
+		match subscriptions.get(token) {
+			Some(resumable) if resumable.expires_at.is_some_and(|at| at <= Instant::now()) => {
+				subscriptions.remove(token);
+				false
+			}
+			Some(_) => true,
+			None => false,
+		}
+	}
+
+	/// Feed the subscription registered under `token` until it expires.
+	///
+	/// The expiry is also checked periodically, so that a subscription without notifications
+	/// is dropped along with its stream.
+	pub async fn run(&self, token: String, mut stream: BoxStream<'static, PubSubResult>) {
+		let check_interval = self.config.ttl.min(EXPIRY_CHECK_INTERVAL);
+		loop {
+			let timeout = futures_timer::Delay::new(check_interval);
+			match future::select(stream.next(), timeout).await {
+				Either::Left((Some(result), _)) => {
+					if !self.push(&token, result) {
+						return;
+					}
+				}
+				Either::Left((None, _)) => return,
+				Either::Right(_) => {
+					if !self.check_expiry(&token) {
+						return;
+					}
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum_types::H256;
+
+	fn subscriptions(buffer_size: usize) -> ResumableSubscriptions {
+		ResumableSubscriptions::new(ResumableSubscriptionConfig {
+			buffer_size,
+			ttl: Duration::from_secs(60),
+		})
+	}
+
+	fn result(n: u64) -> PubSubResult {
+		PubSubResult::TransactionHash(H256::from_low_u64_be(n))
+	}
+
+	fn drain(receiver: &mut TracingUnboundedReceiver<ResumableResult>) -> Vec<u64> {
+		let mut cursors = Vec::new();
+		while let Ok(notification) = receiver.try_recv() {
+			cursors.push(notification.cursor);
+		}
+		cursors
+	}
+
+	#[test]
+	fn resubscribe_replays_missed_notifications() {
+		let subscriptions = subscriptions(8);
+		subscriptions
+			.register("token")
+			.expect("token is registered");
+		let (generation, mut receiver) = subscriptions.attach("token", None).unwrap();
+		assert!(subscriptions.push("token", result(1)));
+		assert!(subscriptions.push("token", result(2)));
+		assert_eq!(drain(&mut receiver), vec![1, 2]);
+
+		subscriptions.detach("token", generation);
+		assert!(subscriptions.push("token", result(3)));
+		assert!(subscriptions.push("token", result(4)));
+
+		let (_, mut receiver) = subscriptions.attach("token", Some(2)).unwrap();
+		assert_eq!(drain(&mut receiver), vec![3, 4]);
+		assert!(subscriptions.push("token", result(5)));
+		assert_eq!(drain(&mut receiver), vec![5]);
+	}
+
+	#[test]
+	fn resubscribe_reports_gaps() {
+		let subscriptions = subscriptions(2);
+		subscriptions
+			.register("token")
+			.expect("token is registered");
+		for n in 1..=4 {
+			assert!(subscriptions.push("token", result(n)));
+		}
+
+		let error = subscriptions.attach("token", Some(1)).unwrap_err();
+		assert_eq!(error.code(), RESUME_GAP_ERROR_CODE);
+		let (_, mut receiver) = subscriptions.attach("token", Some(2)).unwrap();
+		assert_eq!(drain(&mut receiver), vec![3, 4]);
+	}
+
+	#[test]
+	fn expired_subscriptions_are_dropped() {
+		let subscriptions = ResumableSubscriptions::new(ResumableSubscriptionConfig {
+			buffer_size: 8,
+			ttl: Duration::ZERO,
+		});
+		subscriptions
+			.register("token")
+			.expect("token is registered");
+
+		assert!(!subscriptions.push("token", result(1)));
+		assert!(subscriptions.attach("token", Some(0)).is_err());
+		subscriptions
+			.register("token")
+			.expect("expired token can be registered again");
+	}
+
+	#[test]
+	fn expired_subscriptions_stop_without_notifications() {
+		let subscriptions = ResumableSubscriptions::new(ResumableSubscriptionConfig {
+			buffer_size: 8,
+			ttl: Duration::ZERO,
+		});
+		subscriptions
+			.register("token")
+			.expect("token is registered");
+
+		futures::executor::block_on(
+			subscriptions.run("token".to_string(), futures::stream::pending().boxed()),
+		);
+		assert!(subscriptions.subscriptions.lock().unwrap().is_empty());
+	}
+
+	#[test]
+	fn resume_tokens_are_registered_once() {
+		let subscriptions = subscriptions(8);
+		subscriptions
+			.register("token")
+			.expect("token is registered");
+		assert!(subscriptions.register("token").is_err());
+	}
+
+	#[test]
+	fn only_the_latest_subscriber_is_detached() {
+		let subscriptions = subscriptions(8);
+		subscriptions
+			.register("token")
+			.expect("token is registered");
+		let (first, _first_receiver) = subscriptions.attach("token", None).unwrap();
+		let (_, mut receiver) = subscriptions.attach("token", Some(0)).unwrap();
+
+		subscriptions.detach("token", first);
+		assert!(subscriptions.push("token", result(1)));
+		assert_eq!(drain(&mut receiver), vec![1]);
+	}
+}
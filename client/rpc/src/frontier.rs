@@ -0,0 +1,339 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use std::{marker::PhantomData, sync::Arc};
+
+use ethereum_types::{H160, H256, U256};
+use jsonrpsee::core::{async_trait, RpcResult};
+use scale_codec::{Compact, Decode, DecodeAll, Encode};
+// Substrate
+use sc_client_api::{
+	backend::{Backend, StorageProvider},
+	BlockBackend,
+};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto};
+// Frontier
+use fc_rpc_core::{
+	types::{
+		ContractCreation, EthereumBlockMapping, HashOrNumber, SubstrateBlockMapping,
+		SubstrateExtrinsicMapping, UnmappedReason,
+	},
+	FrontierApiServer,
+};
+use fp_consensus::{FindLogError, Log, PostLog, PreLog};
+
+use crate::{frontier_backend_client, internal_err, EthereumTransaction};
+
+/// Frontier API implementation.
+pub struct Frontier<B: BlockT, C, BE> {
+	client: Arc<C>,
+	backend: Arc<dyn fc_api::Backend<B>>,
+	_marker: PhantomData<(B, BE)>,
+}
+
+impl<B: BlockT, C, BE> Frontier<B, C, BE> {
+	pub fn new(client: Arc<C>, backend: Arc<dyn fc_api::Backend<B>>) -> Self {
+		Self {
+			client,
+			backend,
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<B, C, BE> Frontier<B, C, BE>
+where
+	B: BlockT,
+	C: HeaderBackend<B> + 'static,
+{
+	/// Ethereum block hash of the given substrate block, read from its digest.
+	fn ethereum_block_hash(
+		&self,
+		substrate_hash: B::Hash,
+	) -> RpcResult<Result<H256, UnmappedReason>> {
+		let Some(header) = self
+			.client
+			.header(substrate_hash)
+			.map_err(|err| internal_err(format!("{:?}", err)))?
+		else {
+			return Ok(Err(UnmappedReason::UnknownBlock));
+		};
+		let hash = match fp_consensus::find_log(header.digest()) {
+			Ok(Log::Pre(PreLog::Block(block))) | Ok(Log::Post(PostLog::Block(block))) => {
+				block.header.hash()
+			}
+			Ok(Log::Post(PostLog::Hashes(hashes))) => hashes.block_hash,
+			Ok(Log::Post(PostLog::BlockHash(hash))) => hash,
+			Err(FindLogError::NotFound) => return Ok(Err(UnmappedReason::NonEthereumBlock)),
+			Err(FindLogError::MultipleLogs) => {
+				return Err(internal_err("multiple ethereum digests found"))
+			}
+		};
+		Ok(Ok(hash))
+	}
+
+	/// Ethereum block hash of the given substrate block, if indexed by the frontier backend.
+	async fn indexed_ethereum_block_hash(
+		&self,
+		substrate_hash: B::Hash,
+	) -> RpcResult<Result<H256, UnmappedReason>> {
+		let ethereum_hash = match self.ethereum_block_hash(substrate_hash)? {
+			Ok(ethereum_hash) => ethereum_hash,
+			Err(reason) => return Ok(Err(reason)),
+		};
+		let substrate_hashes = self
+			.backend
+			.block_hash(&ethereum_hash)
+			.await
+			.map_err(|err| internal_err(format!("fetch aux store failed: {:?}", err)))?;
+		if substrate_hashes.is_some_and(|hashes| hashes.contains(&substrate_hash)) {
+			Ok(Ok(ethereum_hash))
+		} else {
+			Ok(Err(UnmappedReason::NotIndexed))
+		}
+	}
+}
+
+/// The substrate block hash of the RPC, which is a `H256` in any runtime using frontier.
+fn substrate_hash<B: BlockT>(hash: H256) -> RpcResult<B::Hash> {
+	B::Hash::decode(&mut hash.as_bytes())
+		.map_err(|_| internal_err("substrate block hash is not a H256"))
+}
+
+fn unmapped_block(reason: UnmappedReason) -> SubstrateBlockMapping {
+	SubstrateBlockMapping {
+		substrate_block_hash: None,
+		reason: Some(reason),
+	}
+}
+
+fn unmapped_extrinsic(reason: UnmappedReason) -> SubstrateExtrinsicMapping {
+	SubstrateExtrinsicMapping {
+		substrate_block_hash: None,
+		extrinsic_index: None,
+		reason: Some(reason),
+	}
+}
+
+/// Ethereum transaction of `extrinsic`, if it is an unsigned extrinsic whose call only takes
+/// the transaction, like the `transact` call of pallet-ethereum.
+fn ethereum_transaction<E: Encode>(extrinsic: &E) -> Option<EthereumTransaction> {
+	// Unsigned extrinsics of the version 4 format.
+	const UNSIGNED_EXTRINSIC_VERSION: u8 = 4;
+
+	let encoded = extrinsic.encode();
+	let mut input = &encoded[..];
+	Compact::<u32>::decode(&mut input).ok()?;
+	let (&version, call) = input.split_first()?;
+	if version != UNSIGNED_EXTRINSIC_VERSION {
+		return None;
+	}
+	// Skip the pallet and call indices.
+	EthereumTransaction::decode_all(&mut call.get(2..)?).ok()
+}
+
+#[async_trait]
+impl<B, C, BE> FrontierApiServer for Frontier<B, C, BE>
+where
+	B: BlockT,
+	C: HeaderBackend<B> + BlockBackend<B> + StorageProvider<B, BE> + 'static,
+	BE: Backend<B> + 'static,
+{
+	fn contract_creation(&self, address: H160) -> RpcResult<Option<ContractCreation>> {
+		let Some(index) = self.backend.contract_creation_index() else {
+			return Err(internal_err(
+				"contract creation index not supported by the frontier backend",
+			));
+		};
+		let Some((substrate_hash, creation)) =
+			index.contract_creation(address).map_err(internal_err)?
+		else {
+			return Ok(None);
+		};
+		// The creation may have been retracted by a reorg the index did not catch up with yet.
+		let canonical_hash = self
+			.client
+			.hash(creation.block_number.unique_saturated_into())
+			.map_err(|err| internal_err(format!("{:?}", err)))?;
+		if canonical_hash != Some(substrate_hash) {
+			return Ok(None);
+		}
+		Ok(Some(ContractCreation {
+			transaction_hash: creation.transaction_hash,
+			block_number: U256::from(creation.block_number),
+			creator: creation.creator,
+			is_internal: creation.is_internal,
+		}))
+	}
+
+	fn earliest_traceable_block(&self) -> RpcResult<U256> {
+		frontier_backend_client::earliest_state_block::<B, C, BE>(self.client.as_ref())
+			.map(U256::from)
+	}
+
+	async fn substrate_block_to_eth_block(
+		&self,
+		substrate_hash: H256,
+	) -> RpcResult<EthereumBlockMapping> {
+		let substrate_hash = self::substrate_hash::<B>(substrate_hash)?;
+		Ok(
+			match self.indexed_ethereum_block_hash(substrate_hash).await? {
+				Ok(ethereum_hash) => EthereumBlockMapping {
+					ethereum_block_hash: Some(ethereum_hash),
+					reason: None,
+				},
+				Err(reason) => EthereumBlockMapping {
+					ethereum_block_hash: None,
+					reason: Some(reason),
+				},
+			},
+		)
+	}
+
+	async fn eth_block_to_substrate_block(
+		&self,
+		block: HashOrNumber,
+	) -> RpcResult<SubstrateBlockMapping> {
+		let substrate_hash = match block {
+			HashOrNumber::Hash(hash) => {
+				match frontier_backend_client::load_hash::<B, C>(
+					self.client.as_ref(),
+					self.backend.as_ref(),
+					hash,
+				)
+				.await?
+				{
+					Some(substrate_hash) => substrate_hash,
+					None => return Ok(unmapped_block(UnmappedReason::NotIndexed)),
+				}
+			}
+			HashOrNumber::Number(number) => {
+				let Ok(number) = u64::try_from(number) else {
+					return Ok(unmapped_block(UnmappedReason::UnknownBlock));
+				};
+				let Some(substrate_hash) = self
+					.client
+					.hash(number.unique_saturated_into())
+					.map_err(|err| internal_err(format!("{:?}", err)))?
+				else {
+					return Ok(unmapped_block(UnmappedReason::UnknownBlock));
+				};
+				if let Err(reason) = self.indexed_ethereum_block_hash(substrate_hash).await? {
+					return Ok(unmapped_block(reason));
+				}
+				substrate_hash
+			}
+		};
+		Ok(SubstrateBlockMapping {
+			substrate_block_hash: Some(H256::from_slice(substrate_hash.as_ref())),
+			reason: None,
+		})
+	}
+
+	async fn eth_tx_to_substrate_extrinsic(
+		&self,
+		transaction_hash: H256,
+	) -> RpcResult<SubstrateExtrinsicMapping> {
+		let transaction_metadata = self
+			.backend
+			.transaction_metadata(&transaction_hash)
+			.await
+			.map_err(|err| internal_err(format!("fetch aux store failed: {:?}", err)))?;
+		let Some(metadata) = transaction_metadata.into_iter().find(|metadata| {
+			frontier_backend_client::is_canon::<B, C>(
+				self.client.as_ref(),
+				metadata.substrate_block_hash,
+			)
+		}) else {
+			return Ok(unmapped_extrinsic(UnmappedReason::NotIndexed));
+		};
+
+		let substrate_hash = metadata.substrate_block_hash;
+		let extrinsics = self
+			.client
+			.block_body(substrate_hash)
+			.map_err(|err| internal_err(format!("{:?}", err)))?
+			.ok_or_else(|| internal_err("block body not found"))?;
+
+		// The mapped index is the one of the transaction among the ethereum transactions of
+		// the block, in the order of their extrinsics.
+		let Some((extrinsic_index, transaction)) = extrinsics
+			.iter()
+			.enumerate()
+			.filter_map(|(index, extrinsic)| Some((index, ethereum_transaction(extrinsic)?)))
+			.nth(metadata.ethereum_index as usize)
+		else {
+			return Err(internal_err("extrinsic of the transaction not found"));
+		};
+		if transaction.hash() != transaction_hash {
+			return Err(internal_err("extrinsic of the transaction not found"));
+		}
+		Ok(SubstrateExtrinsicMapping {
+			substrate_block_hash: Some(H256::from_slice(substrate_hash.as_ref())),
+			extrinsic_index: Some(U256::from(extrinsic_index)),
+			reason: None,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn transaction() -> EthereumTransaction {
+		EthereumTransaction::Legacy(ethereum::LegacyTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(1_000),
+			gas_limit: U256::from(21_000),
+			action: ethereum::TransactionAction::Call(H160::repeat_byte(0x35)),
+			value: U256::one(),
+			input: vec![],
+			signature: ethereum::TransactionSignature::new(
+				80,
+				H256::repeat_byte(0x01),
+				H256::repeat_byte(0x02),
+			)
+			.unwrap(),
+		})
+	}
+
+	/// Extrinsic of the given version calling `args` at the pallet 3 and call 0, encoded like
+	/// the extrinsics of a block body.
+	fn extrinsic(version: u8, args: &[u8]) -> Vec<u8> {
+		[&[version, 3, 0][..], args].concat()
+	}
+
+	#[test]
+	fn ethereum_transactions_are_decoded_from_unsigned_extrinsics() {
+		let transaction = transaction();
+		let args = transaction.encode();
+		assert_eq!(
+			ethereum_transaction(&extrinsic(4, &args)),
+			Some(transaction.clone())
+		);
+
+		// Signed extrinsics, other calls and other formats are not ethereum transactions.
+		assert_eq!(ethereum_transaction(&extrinsic(0x84, &args)), None);
+		assert_eq!(
+			ethereum_transaction(&extrinsic(4, &(transaction, 7u8).encode())),
+			None
+		);
+		assert_eq!(ethereum_transaction(&extrinsic(5, &args)), None);
+		assert_eq!(ethereum_transaction(&Vec::<u8>::new()), None);
+	}
+}
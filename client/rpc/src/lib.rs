@@ -27,11 +27,20 @@
 #![warn(unused_crate_dependencies)]
 
 mod cache;
+mod cors;
 mod debug;
+mod dev;
 mod eth;
 mod eth_pubsub;
+mod frontier;
+mod middleware;
+mod modules;
 mod net;
+mod personal;
+mod request;
+mod sender_recovery;
 mod signer;
+mod submission;
 #[cfg(feature = "txpool")]
 mod txpool;
 mod web3;
@@ -39,20 +48,36 @@ mod web3;
 #[cfg(feature = "txpool")]
 pub use self::txpool::TxPool;
 pub use self::{
-	cache::{EthBlockDataCacheTask, EthTask},
+	cache::{EthBlockDataCacheTask, EthTask, FeeOracleConfig},
+	cors::{CorsConfig, MethodCors, MethodCorsConfig, MethodCorsLayer},
 	debug::Debug,
-	eth::{format, pending, EstimateGasAdapter, Eth, EthConfig, EthFilter},
-	eth_pubsub::{EthPubSub, EthereumSubIdProvider},
+	dev::Dev,
+	eth::{format, pending, CoinbaseProvider, EstimateGasAdapter, Eth, EthConfig, EthFilter},
+	eth_pubsub::{
+		EthPubSub, EthereumSubIdProvider, ResumableSubscriptionConfig, RESUME_GAP_ERROR_CODE,
+	},
+	frontier::Frontier,
+	middleware::{LoggingLayer, LoggingMiddleware, RPC_LOG_ENV},
+	modules::Modules,
 	net::Net,
+	personal::Personal,
+	request::{RequestId, RequestIdLayer, RequestTracer, REQUEST_ID_HEADER},
+	sender_recovery::SenderRecovery,
 	signer::{EthDevSigner, EthSigner},
+	submission::{
+		ConnectionLimit, ConnectionLimitLayer, SubmissionLimiter, SubmissionLimits,
+		RATE_LIMITED_ERROR_CODE,
+	},
 	web3::Web3,
 };
 pub use ethereum::TransactionV2 as EthereumTransaction;
+#[cfg(feature = "logs-pagination")]
+pub use fc_rpc_core::EthLogsPageApiServer;
 #[cfg(feature = "txpool")]
 pub use fc_rpc_core::TxPoolApiServer;
 pub use fc_rpc_core::{
-	DebugApiServer, EthApiServer, EthFilterApiServer, EthPubSubApiServer, NetApiServer,
-	Web3ApiServer,
+	DebugApiServer, DevApiServer, EthApiServer, EthFilterApiServer, EthPubSubApiServer,
+	FrontierApiServer, ModulesApiServer, NetApiServer, PersonalApiServer, Web3ApiServer,
 };
 pub use fc_storage::{overrides::*, StorageOverrideHandler};
 
@@ -256,6 +281,48 @@ pub mod frontier_backend_client {
 		false
 	}
 
+	/// Whether the state of the given block is still available, i.e. was not pruned.
+	pub fn is_state_available<B: BlockT, C, BE>(client: &C, hash: B::Hash) -> bool
+	where
+		C: StorageProvider<B, BE>,
+		BE: Backend<B>,
+	{
+		client
+			.storage(
+				hash,
+				&StorageKey(sp_storage::well_known_keys::CODE.to_vec()),
+			)
+			.is_ok()
+	}
+
+	/// Number of the earliest canonical block whose state is available.
+	///
+	/// Pruning only discards the oldest states, so this bisects the canonical chain.
+	pub fn earliest_state_block<B: BlockT, C, BE>(client: &C) -> RpcResult<u64>
+	where
+		C: HeaderBackend<B> + StorageProvider<B, BE>,
+		BE: Backend<B>,
+	{
+		let is_available = |number: u64| -> RpcResult<bool> {
+			let hash = client
+				.hash(number.unique_saturated_into())
+				.map_err(|err| internal_err(format!("{:?}", err)))?;
+			Ok(hash.is_some_and(|hash| is_state_available::<B, C, BE>(client, hash)))
+		};
+
+		let mut low = 0;
+		let mut high: u64 = client.info().best_number.unique_saturated_into();
+		while low < high {
+			let middle = low + (high - low) / 2;
+			if is_available(middle)? {
+				high = middle;
+			} else {
+				low = middle + 1;
+			}
+		}
+		Ok(low)
+	}
+
 	pub async fn load_transactions<B: BlockT, C>(
 		client: &C,
 		backend: &dyn fc_api::Backend<B>,
@@ -305,6 +372,29 @@ pub fn err<T: ToString>(
 	)
 }
 
+/// Error code of a request needing the state of a pruned block.
+pub const STATE_UNAVAILABLE_ERROR_CODE: i32 = -32011;
+
+/// Error for a request needing the pruned state of block `number`, with the earliest block whose
+/// state is still available.
+pub fn state_unavailable_err(
+	number: u64,
+	earliest_block: u64,
+) -> jsonrpsee::types::error::ErrorObjectOwned {
+	jsonrpsee::types::error::ErrorObject::owned(
+		STATE_UNAVAILABLE_ERROR_CODE,
+		format!(
+			"state of block #{} is not available, the earliest block with state is #{}; \
+			 use an archive node to query older blocks",
+			number, earliest_block
+		),
+		Some(serde_json::json!({
+			"requestedBlock": number,
+			"earliestBlock": earliest_block,
+		})),
+	)
+}
+
 pub fn internal_err<T: ToString>(message: T) -> jsonrpsee::types::error::ErrorObjectOwned {
 	err(jsonrpsee::types::error::INTERNAL_ERROR_CODE, message, None)
 }
@@ -346,6 +436,16 @@ pub fn public_key(transaction: &EthereumTransaction) -> Result<[u8; 64], sp_io::
 	sp_io::crypto::secp256k1_ecdsa_recover(&sig, &msg)
 }
 
+/// Recover the sender of a transaction, `None` if its signature is invalid.
+///
+/// Recovery is costly, [`SenderRecovery`] caches the senders it recovers.
+pub fn transaction_sender(transaction: &EthereumTransaction) -> Option<ethereum_types::H160> {
+	use ethereum_types::{H160, H256};
+
+	let pubkey = public_key(transaction).ok()?;
+	Some(H160::from(H256::from(sp_core::keccak_256(&pubkey))))
+}
+
 #[cfg(test)]
 mod tests {
 	use std::{path::PathBuf, sync::Arc};
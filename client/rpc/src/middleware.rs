@@ -0,0 +1,158 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Logging of the raw JSON-RPC requests and responses.
+
+use futures::future::{BoxFuture, Either, FutureExt};
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse};
+
+const LOG_TARGET: &str = "rpc-log";
+
+/// Environment variable enabling the logging when set to `trace`.
+pub const RPC_LOG_ENV: &str = "FRONTIER_RPC_LOG";
+
+/// Responses are logged up to this many bytes.
+const MAX_LOGGED_RESPONSE: usize = 1024;
+
+/// Methods whose params are not logged, as they carry what is to be signed.
+const REDACTED_METHODS: &[&str] = &[
+	"eth_sign",
+	"eth_sendTransaction",
+	"eth_signTypedData_v4",
+	"personal_sign",
+	"personal_sendTransaction",
+];
+
+/// Layer of [`LoggingMiddleware`], to be added to the RPC middleware of the server.
+#[derive(Clone, Copy, Debug)]
+pub struct LoggingLayer {
+	enabled: bool,
+}
+
+impl LoggingLayer {
+	pub fn new(enabled: bool) -> Self {
+		Self { enabled }
+	}
+
+	/// Logging enabled when [`RPC_LOG_ENV`] is `trace`.
+	pub fn from_env() -> Self {
+		Self::new(std::env::var(RPC_LOG_ENV).map_or(false, |value| value == "trace"))
+	}
+}
+
+impl<S> tower::Layer<S> for LoggingLayer {
+	type Service = LoggingMiddleware<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		LoggingMiddleware {
+			service,
+			enabled: self.enabled,
+		}
+	}
+}
+
+/// Logs the method, params and response of each request at `trace` level.
+///
+/// Requests go straight to the inner service when the logging is disabled.
+#[derive(Clone, Debug)]
+pub struct LoggingMiddleware<S> {
+	service: S,
+	enabled: bool,
+}
+
+impl<'a, S> RpcServiceT<'a> for LoggingMiddleware<S>
+where
+	S: RpcServiceT<'a>,
+	S::Future: 'a,
+{
+	type Future = Either<S::Future, BoxFuture<'a, MethodResponse>>;
+
+	fn call(&self, request: Request<'a>) -> Self::Future {
+		if !self.enabled || !log::log_enabled!(target: LOG_TARGET, log::Level::Trace) {
+			return Either::Left(self.service.call(request));
+		}
+
+		let method = request.method_name().to_owned();
+		log::trace!(
+			target: LOG_TARGET,
+			"{method} request: {}",
+			logged_params(&method, request.params().as_str())
+		);
+
+		let response = self.service.call(request);
+		Either::Right(
+			async move {
+				let response = response.await;
+				log::trace!(
+					target: LOG_TARGET,
+					"{method} response: {}",
+					truncated(response.as_result(), MAX_LOGGED_RESPONSE)
+				);
+				response
+			}
+			.boxed(),
+		)
+	}
+}
+
+fn logged_params<'a>(method: &str, params: Option<&'a str>) -> &'a str {
+	if REDACTED_METHODS.contains(&method) {
+		"<redacted>"
+	} else {
+		params.unwrap_or("[]")
+	}
+}
+
+/// The first `max` bytes of `text`, cut at a character boundary.
+fn truncated(text: &str, max: usize) -> &str {
+	if text.len() <= max {
+		return text;
+	}
+	let mut end = max;
+	while !text.is_char_boundary(end) {
+		end -= 1;
+	}
+	&text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn sensitive_params_are_redacted() {
+		let params = Some(r#"["0x00", "0x01"]"#);
+		assert_eq!(logged_params("eth_sign", params), "<redacted>");
+		assert_eq!(logged_params("eth_sendTransaction", params), "<redacted>");
+		assert_eq!(logged_params("eth_signTypedData_v4", params), "<redacted>");
+		assert_eq!(logged_params("personal_sign", params), "<redacted>");
+		assert_eq!(
+			logged_params("personal_sendTransaction", params),
+			"<redacted>"
+		);
+		assert_eq!(logged_params("eth_call", params), r#"["0x00", "0x01"]"#);
+		assert_eq!(logged_params("eth_blockNumber", None), "[]");
+	}
+
+	#[test]
+	fn responses_are_truncated_at_a_char_boundary() {
+		assert_eq!(truncated("0x1234", 1024), "0x1234");
+		assert_eq!(truncated("0x1234", 4), "0x12");
+		assert_eq!(truncated("aé", 2), "a");
+	}
+}
@@ -0,0 +1,86 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+
+use jsonrpsee::core::RpcResult;
+// Frontier
+use fc_rpc_core::{types::RpcModuleInfo, ModulesApiServer};
+
+/// Version reported for every namespace.
+const MODULE_VERSION: &str = "1.0";
+
+/// Rpc modules API implementation.
+pub struct Modules {
+	modules: BTreeMap<String, RpcModuleInfo>,
+}
+
+impl Modules {
+	/// Group `methods` by namespace, the part of their name before the first `_`.
+	/// `rpc_modules` is listed as well.
+	pub fn new<'a>(methods: impl IntoIterator<Item = &'a str>) -> Self {
+		let mut modules = BTreeMap::<String, RpcModuleInfo>::new();
+		for method in methods.into_iter().chain(["rpc_modules"]) {
+			let namespace = method
+				.split_once('_')
+				.map_or(method, |(namespace, _)| namespace);
+			let module = modules
+				.entry(namespace.to_string())
+				.or_insert_with(|| RpcModuleInfo {
+					version: MODULE_VERSION.to_string(),
+					methods: Vec::new(),
+				});
+			module.methods.push(method.to_string());
+		}
+		for module in modules.values_mut() {
+			module.methods.sort();
+			module.methods.dedup();
+		}
+		Self { modules }
+	}
+}
+
+impl ModulesApiServer for Modules {
+	fn modules(&self) -> RpcResult<BTreeMap<String, RpcModuleInfo>> {
+		Ok(self.modules.clone())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn groups_methods_by_namespace() {
+		let modules = Modules::new(["eth_chainId", "net_version", "eth_blockNumber"])
+			.modules()
+			.unwrap();
+		assert_eq!(
+			modules.keys().collect::<Vec<_>>(),
+			vec!["eth", "net", "rpc"]
+		);
+		assert_eq!(
+			modules["eth"],
+			RpcModuleInfo {
+				version: "1.0".to_string(),
+				methods: vec!["eth_blockNumber".to_string(), "eth_chainId".to_string()],
+			}
+		);
+		assert_eq!(modules["rpc"].methods, vec!["rpc_modules".to_string()]);
+	}
+}
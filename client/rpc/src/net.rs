@@ -16,11 +16,20 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::sync::Arc;
+use std::{
+	future::Future,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+};
 
+use futures::StreamExt;
 use jsonrpsee::core::RpcResult;
 // Substrate
-use sc_network::{service::traits::NetworkService, NetworkPeers};
+use sc_network::{
+	service::traits::NetworkService, Event, NetworkEventStream, NetworkPeers, NetworkStateInfo,
+};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::Block as BlockT;
@@ -35,17 +44,48 @@ pub struct Net<B: BlockT, C> {
 	client: Arc<C>,
 	network: Arc<dyn NetworkService>,
 	peer_count_as_hex: bool,
+	network_id: Option<u64>,
+	/// Whether the node listens for connections, kept up to date by [`Net::listening_task`].
+	listening: Arc<AtomicBool>,
 	_phantom_data: std::marker::PhantomData<B>,
 }
+
 impl<B: BlockT, C> Net<B, C> {
-	pub fn new(client: Arc<C>, network: Arc<dyn NetworkService>, peer_count_as_hex: bool) -> Self {
+	/// `network_id` is returned by `net_version`, the runtime chain id is used when unset.
+	pub fn new(
+		client: Arc<C>,
+		network: Arc<dyn NetworkService>,
+		peer_count_as_hex: bool,
+		network_id: Option<u64>,
+	) -> Self {
+		let listening = !network.listen_addresses().is_empty();
 		Self {
 			client,
 			network,
 			peer_count_as_hex,
+			network_id,
+			listening: Arc::new(AtomicBool::new(listening)),
 			_phantom_data: Default::default(),
 		}
 	}
+
+	/// Task refreshing the `net_listening` cache whenever the network reports a peer connecting
+	/// or disconnecting, to be spawned along the RPC server.
+	pub fn listening_task(&self) -> impl Future<Output = ()> + Send + 'static {
+		let network = self.network.clone();
+		let listening = self.listening.clone();
+		async move {
+			let mut events = network.event_stream("frontier-net-listening");
+			while let Some(event) = events.next().await {
+				if matches!(
+					event,
+					Event::NotificationStreamOpened { .. } | Event::NotificationStreamClosed { .. }
+				) {
+					listening.store(!network.listen_addresses().is_empty(), Ordering::Relaxed);
+				}
+			}
+		}
+	}
 }
 
 impl<B, C> NetApiServer for Net<B, C>
@@ -56,13 +96,18 @@ where
 	C: HeaderBackend<B> + 'static,
 {
 	fn version(&self) -> RpcResult<String> {
+		if let Some(network_id) = self.network_id {
+			return Ok(network_id.to_string());
+		}
+		// The chain id can be changed after genesis, e.g. through `pallet-evm-chain-id`, so it is
+		// read at the best block rather than cached.
 		let hash = self.client.info().best_hash;
-		Ok(self
+		let version = self
 			.client
 			.runtime_api()
 			.chain_id(hash)
-			.map_err(|_| internal_err("fetch runtime chain id failed"))?
-			.to_string())
+			.map_err(|_| internal_err("fetch runtime chain id failed"))?;
+		Ok(version.to_string())
 	}
 
 	fn peer_count(&self) -> RpcResult<PeerCount> {
@@ -74,6 +119,12 @@ where
 	}
 
 	fn is_listening(&self) -> RpcResult<bool> {
-		Ok(true)
+		if self.listening.load(Ordering::Relaxed) {
+			return Ok(true);
+		}
+		// The network may start listening before any peer connects, as on a dev node.
+		let listening = !self.network.listen_addresses().is_empty();
+		self.listening.store(listening, Ordering::Relaxed);
+		Ok(listening)
 	}
 }
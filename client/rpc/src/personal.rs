@@ -0,0 +1,128 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum_types::{H160, H256};
+use jsonrpsee::{
+	core::RpcResult,
+	types::{error::INVALID_PARAMS_CODE, ErrorObjectOwned},
+};
+// Substrate
+use sp_core::hashing::keccak_256;
+// Frontier
+use fc_rpc_core::{types::Bytes, PersonalApiServer};
+
+use crate::{
+	err,
+	signer::{public_key_address, sign_hash, EthSigner},
+};
+
+/// Personal API implementation, signing with the dev accounts.
+pub struct Personal {
+	signers: Vec<Box<dyn EthSigner>>,
+}
+
+impl Personal {
+	/// `signers` are empty out of dev mode, only `personal_ecRecover` being served then.
+	pub fn new(signers: Vec<Box<dyn EthSigner>>) -> Self {
+		Self { signers }
+	}
+}
+
+/// `keccak256("\x19Ethereum Signed Message:\n" + len(message) + message)`.
+fn message_hash(message: &[u8]) -> H256 {
+	let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+	prefixed.extend_from_slice(message);
+	H256(keccak_256(&prefixed))
+}
+
+fn invalid_signature() -> ErrorObjectOwned {
+	err(INVALID_PARAMS_CODE, "Invalid signature", None)
+}
+
+impl PersonalApiServer for Personal {
+	fn sign(&self, message: Bytes, address: H160) -> RpcResult<Bytes> {
+		let signature = sign_hash(&self.signers, message_hash(&message.0), address)?;
+		Ok(Bytes::new(signature.to_vec()))
+	}
+
+	fn ec_recover(&self, message: Bytes, signature: Bytes) -> RpcResult<H160> {
+		if signature.0.len() != 65 {
+			return Err(invalid_signature());
+		}
+		// `v` is 27 or 28, some signers using 0 or 1.
+		let v = match signature.0[64] {
+			v @ (27 | 28) => v - 27,
+			v => v,
+		};
+		let recovery_id = libsecp256k1::RecoveryId::parse(v).map_err(|_| invalid_signature())?;
+		let signature = libsecp256k1::Signature::parse_standard_slice(&signature.0[..64])
+			.map_err(|_| invalid_signature())?;
+		let public = libsecp256k1::recover(
+			&libsecp256k1::Message::parse(&message_hash(&message.0).0),
+			&signature,
+			&recovery_id,
+		)
+		.map_err(|_| invalid_signature())?;
+		Ok(public_key_address(&public))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::signer::EthDevSigner;
+
+	#[test]
+	fn message_hash_is_prefixed() {
+		// `hashMessage("Hello World")` of ethers.
+		let expected: H256 = "0xa1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2"
+			.parse()
+			.unwrap();
+		assert_eq!(message_hash(b"Hello World"), expected);
+	}
+
+	#[test]
+	fn recovers_the_signer_of_personal_sign() {
+		let signer = EthDevSigner::new();
+		let address = signer.accounts()[0];
+		let personal = Personal::new(vec![Box::new(signer)]);
+		let message = Bytes::new(b"Hello Frontier".to_vec());
+
+		let signature = personal.sign(message.clone(), address).unwrap();
+		assert_eq!(
+			personal
+				.ec_recover(message.clone(), signature.clone())
+				.unwrap(),
+			address
+		);
+		assert_ne!(
+			personal
+				.ec_recover(Bytes::new(b"Hello World".to_vec()), signature.clone())
+				.unwrap(),
+			address
+		);
+		assert!(personal
+			.ec_recover(message.clone(), Bytes::new(signature.0[..64].to_vec()))
+			.is_err());
+
+		// Out of dev mode, only recovering is served.
+		let personal = Personal::new(Vec::new());
+		assert!(personal.sign(message.clone(), address).is_err());
+		assert_eq!(personal.ec_recover(message, signature).unwrap(), address);
+	}
+}
@@ -0,0 +1,260 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Correlation of the execution requests with the node logs.
+
+use std::{
+	future::Future,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	task::{Context, Poll},
+	time::{Duration, Instant},
+};
+
+use futures::future::{BoxFuture, Either};
+use hyper::Request;
+use jsonrpsee::{
+	core::RpcResult,
+	types::error::{ErrorObject, ErrorObjectOwned},
+};
+use tracing::Instrument;
+
+const LOG_TARGET: &str = "rpc-request";
+
+/// Header of the HTTP requests carrying the request id chosen by the client.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest request id taken from a client, longer ones being replaced by a generated one.
+const MAX_REQUEST_ID_LEN: usize = 64;
+
+tokio::task_local! {
+	/// Request id of the HTTP request being served, set by [`RequestIdLayer`].
+	static INCOMING_REQUEST_ID: String;
+}
+
+/// Logs requests under a request id, which is also returned in the `data` of their errors.
+///
+/// The id is the one sent by the client in [`REQUEST_ID_HEADER`] when the server has a
+/// [`RequestIdLayer`], a generated one otherwise. Requests run in an `rpc_request` tracing span
+/// carrying the id and the method, so that whatever they log can be correlated with them.
+#[derive(Clone, Debug)]
+pub struct RequestTracer {
+	/// Requests taking longer are logged as slow.
+	slow_request_threshold: Duration,
+	next_id: Arc<AtomicU64>,
+}
+
+impl RequestTracer {
+	pub fn new(slow_request_threshold: Duration) -> Self {
+		Self {
+			slow_request_threshold,
+			next_id: Arc::new(AtomicU64::new(1)),
+		}
+	}
+
+	fn request_id(&self) -> String {
+		INCOMING_REQUEST_ID
+			.try_with(Clone::clone)
+			.unwrap_or_else(|_| format!("{:x}", self.next_id.fetch_add(1, Ordering::Relaxed)))
+	}
+
+	/// Run `request` under its request id, `params` summarizing its parameters in the logs.
+	pub async fn trace<T>(
+		&self,
+		method: &str,
+		params: impl FnOnce() -> String,
+		request: impl Future<Output = RpcResult<T>>,
+	) -> RpcResult<T> {
+		let id = self.request_id();
+		log::debug!(target: LOG_TARGET, "[{id}] {method} started");
+
+		let span =
+			tracing::debug_span!(target: LOG_TARGET, "rpc_request", request_id = %id, method);
+		let started = Instant::now();
+		let result = request.instrument(span).await;
+		let elapsed = started.elapsed();
+		if elapsed >= self.slow_request_threshold {
+			log::warn!(
+				target: LOG_TARGET,
+				"[{id}] Slow request {method}({}) took {elapsed:?}",
+				params()
+			);
+		}
+
+		result.map_err(|err| {
+			log::debug!(target: LOG_TARGET, "[{id}] {method} failed: {}", err.message());
+			with_request_id(err, &id)
+		})
+	}
+}
+
+/// Layer of [`RequestId`], to be added to the HTTP middleware of the server.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RequestIdLayer;
+
+impl<S> tower::Layer<S> for RequestIdLayer {
+	type Service = RequestId<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		RequestId { service }
+	}
+}
+
+/// Serves the HTTP requests under the id of their [`REQUEST_ID_HEADER`], for the
+/// [`RequestTracer`] to use.
+///
+/// The calls of a batch share the id. Over WebSocket, only the upgrade request goes through the
+/// HTTP middleware, so the calls get generated ids.
+#[derive(Clone, Debug)]
+pub struct RequestId<S> {
+	service: S,
+}
+
+impl<S, Body> tower::Service<Request<Body>> for RequestId<S>
+where
+	S: tower::Service<Request<Body>>,
+	S::Future: Send + 'static,
+{
+	type Response = S::Response;
+	type Error = S::Error;
+	type Future = Either<S::Future, BoxFuture<'static, Result<S::Response, S::Error>>>;
+
+	fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		self.service.poll_ready(cx)
+	}
+
+	fn call(&mut self, request: Request<Body>) -> Self::Future {
+		let id = request
+			.headers()
+			.get(REQUEST_ID_HEADER)
+			.and_then(|id| id.to_str().ok())
+			.filter(|id| {
+				!id.is_empty()
+					&& id.len() <= MAX_REQUEST_ID_LEN
+					&& id.bytes().all(|byte| byte.is_ascii_graphic())
+			})
+			.map(str::to_string);
+		let future = self.service.call(request);
+		match id {
+			Some(id) => Either::Right(Box::pin(INCOMING_REQUEST_ID.scope(id, future))),
+			None => Either::Left(future),
+		}
+	}
+}
+
+/// Add the request id to the `data` of an error, unless it already has some, e.g. revert data.
+fn with_request_id(err: ErrorObjectOwned, id: &str) -> ErrorObjectOwned {
+	if err.data().is_some() {
+		return err;
+	}
+	ErrorObject::owned(
+		err.code(),
+		err.message().to_string(),
+		Some(serde_json::json!({ "requestId": id })),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{internal_err, internal_err_with_data};
+
+	fn trace(tracer: &RequestTracer, result: RpcResult<()>) -> RpcResult<()> {
+		futures::executor::block_on(tracer.trace("eth_call", String::new, async { result }))
+	}
+
+	#[test]
+	fn errors_carry_the_request_id() {
+		let tracer = RequestTracer::new(Duration::from_secs(1));
+		let first = trace(&tracer, Err(internal_err("failed"))).unwrap_err();
+		let second = trace(&tracer, Err(internal_err("failed"))).unwrap_err();
+
+		assert_eq!(first.message(), "failed");
+		assert_eq!(first.data().unwrap().get(), r#"{"requestId":"1"}"#);
+		assert_eq!(second.data().unwrap().get(), r#"{"requestId":"2"}"#);
+	}
+
+	#[test]
+	fn incoming_request_ids_are_used() {
+		let tracer = RequestTracer::new(Duration::from_secs(1));
+		let error = futures::executor::block_on(INCOMING_REQUEST_ID.scope(
+			"client-7".to_string(),
+			tracer.trace("eth_call", String::new, async {
+				Err::<(), _>(internal_err("failed"))
+			}),
+		))
+		.unwrap_err();
+		assert_eq!(error.data().unwrap().get(), r#"{"requestId":"client-7"}"#);
+
+		// Generated ids are used outside of a request with an id.
+		let error = trace(&tracer, Err(internal_err("failed"))).unwrap_err();
+		assert_eq!(error.data().unwrap().get(), r#"{"requestId":"1"}"#);
+	}
+
+	/// Returns the request id it is called under.
+	#[derive(Clone)]
+	struct IncomingRequestId;
+
+	impl tower::Service<Request<()>> for IncomingRequestId {
+		type Response = Option<String>;
+		type Error = ();
+		type Future = std::future::Ready<Result<Option<String>, ()>>;
+
+		fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), ()>> {
+			Poll::Ready(Ok(()))
+		}
+
+		fn call(&mut self, _: Request<()>) -> Self::Future {
+			std::future::ready(Ok(INCOMING_REQUEST_ID.try_with(Clone::clone).ok()))
+		}
+	}
+
+	#[test]
+	fn request_id_header_is_honored() {
+		use tower::{Layer, Service};
+
+		let mut service = RequestIdLayer.layer(IncomingRequestId);
+		let mut call = |id: Option<&str>| {
+			let mut request = Request::builder();
+			if let Some(id) = id {
+				request = request.header(REQUEST_ID_HEADER, id);
+			}
+			futures::executor::block_on(service.call(request.body(()).unwrap())).unwrap()
+		};
+
+		assert_eq!(call(Some("a1b2")), Some("a1b2".to_string()));
+		assert_eq!(call(None), None);
+		assert_eq!(call(Some("")), None);
+		assert_eq!(call(Some("with space")), None);
+		assert_eq!(call(Some(&"x".repeat(MAX_REQUEST_ID_LEN + 1))), None);
+	}
+
+	#[test]
+	fn error_data_is_kept() {
+		let tracer = RequestTracer::new(Duration::from_secs(1));
+		let error = trace(
+			&tracer,
+			Err(internal_err_with_data("reverted", &[0xde, 0xad])),
+		)
+		.unwrap_err();
+
+		assert_eq!(error.data().unwrap().get(), r#""0xdead""#);
+	}
+}
@@ -0,0 +1,274 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use ethereum::TransactionV2 as EthereumTransaction;
+use ethereum_types::{H160, H256};
+use schnellru::{ByLength, LruMap};
+
+use crate::transaction_sender;
+
+/// Number of items below which a batch is processed on the calling thread only.
+const MIN_ITEMS_PER_THREAD: usize = 16;
+
+/// Maximum number of senders kept by a [`SenderRecovery`].
+const SENDER_CACHE_SIZE: u32 = 65_536;
+
+type SenderCache = LruMap<H256, H160, ByLength>;
+
+/// Recovers the senders of transactions, fanning the recovery of batches out over a bounded
+/// number of threads.
+///
+/// Recovery is pure and costly, so the senders are cached by transaction hash, the clones of a
+/// `SenderRecovery` sharing its cache. A batch locks the cache once to look its transactions up
+/// and once to insert the senders it recovered, the recovery itself running unlocked.
+#[derive(Clone)]
+pub struct SenderRecovery {
+	threads: usize,
+	cache: Arc<Mutex<SenderCache>>,
+}
+
+impl SenderRecovery {
+	/// Use at most `threads` threads, the calling one included. 0 and 1 both process the
+	/// batches sequentially.
+	pub fn new(threads: usize) -> Self {
+		Self {
+			threads: threads.max(1),
+			cache: Arc::new(Mutex::new(LruMap::new(ByLength::new(SENDER_CACHE_SIZE)))),
+		}
+	}
+
+	/// Recover the sender of a transaction, `None` if its signature is invalid.
+	pub fn sender(&self, transaction: &EthereumTransaction) -> Option<H160> {
+		self.senders(std::slice::from_ref(transaction))
+			.pop()
+			.flatten()
+	}
+
+	/// Recover the senders of the given transactions, `None` for the invalid signatures.
+	pub fn senders(&self, transactions: &[EthereumTransaction]) -> Vec<Option<H160>> {
+		let hashes = transactions
+			.iter()
+			.map(EthereumTransaction::hash)
+			.collect::<Vec<_>>();
+		let mut senders = {
+			let mut cache = self.cache();
+			hashes
+				.iter()
+				.map(|hash| cache.get(hash).copied())
+				.collect::<Vec<_>>()
+		};
+
+		let missing = (0..senders.len())
+			.filter(|index| senders[*index].is_none())
+			.collect::<Vec<_>>();
+		if missing.is_empty() {
+			return senders;
+		}
+		let recovered = self.map(&missing, |index| transaction_sender(&transactions[*index]));
+
+		let mut cache = self.cache();
+		for (index, sender) in missing.into_iter().zip(recovered) {
+			if let Some(sender) = sender {
+				cache.insert(hashes[index], sender);
+			}
+			senders[index] = sender;
+		}
+		senders
+	}
+
+	fn cache(&self) -> MutexGuard<'_, SenderCache> {
+		// A panic cannot leave the cache inconsistent, it only holds recovered senders.
+		self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+
+	/// Apply `f` to every item, keeping their order.
+	fn map<T, R, F>(&self, items: &[T], f: F) -> Vec<R>
+	where
+		T: Sync,
+		R: Send,
+		F: Fn(&T) -> R + Sync,
+	{
+		let threads = self.threads.min(items.len() / MIN_ITEMS_PER_THREAD).max(1);
+		if threads == 1 {
+			return items.iter().map(&f).collect();
+		}
+
+		let chunk_size = items.len().div_ceil(threads);
+		let f = &f;
+		std::thread::scope(|scope| {
+			let mut chunks = items.chunks(chunk_size);
+			let first = chunks.next().unwrap_or_default();
+			let handles = chunks
+				.map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<_>>()))
+				.collect::<Vec<_>>();
+
+			let mut results = first.iter().map(f).collect::<Vec<_>>();
+			for handle in handles {
+				results.extend(handle.join().expect("transaction fan-out thread panicked"));
+			}
+			results
+		})
+	}
+}
+
+impl std::fmt::Debug for SenderRecovery {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("SenderRecovery")
+			.field("threads", &self.threads)
+			.finish_non_exhaustive()
+	}
+}
+
+impl Default for SenderRecovery {
+	fn default() -> Self {
+		Self::new(1)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Instant;
+
+	use ethereum_types::U256;
+
+	use super::*;
+	use crate::signer::public_key_address;
+
+	fn secret(seed: u8) -> libsecp256k1::SecretKey {
+		libsecp256k1::SecretKey::parse(&[seed.max(1); 32]).expect("key is valid")
+	}
+
+	/// Legacy transactions signed by `secret(index % 8)`, `nonce` making them unique.
+	fn transactions(count: usize, nonce: u64) -> Vec<EthereumTransaction> {
+		(0..count)
+			.map(|index| {
+				let message = ethereum::LegacyTransactionMessage {
+					nonce: U256::from(nonce),
+					gas_price: U256::from(1_000_000_000),
+					gas_limit: U256::from(21_000),
+					action: ethereum::TransactionAction::Call(H160::repeat_byte(0x35)),
+					value: U256::from(index),
+					input: vec![],
+					chain_id: Some(42),
+				};
+				let signing_message = libsecp256k1::Message::parse_slice(&message.hash()[..])
+					.expect("message is a hash");
+				let (signature, recid) =
+					libsecp256k1::sign(&signing_message, &secret(index as u8 % 8));
+				let rs = signature.serialize();
+				EthereumTransaction::Legacy(ethereum::LegacyTransaction {
+					nonce: message.nonce,
+					gas_price: message.gas_price,
+					gas_limit: message.gas_limit,
+					action: message.action,
+					value: message.value,
+					input: message.input,
+					signature: ethereum::TransactionSignature::new(
+						2 * 42 + 35 + recid.serialize() as u64,
+						H256::from_slice(&rs[0..32]),
+						H256::from_slice(&rs[32..64]),
+					)
+					.expect("signature is valid"),
+				})
+			})
+			.collect()
+	}
+
+	fn sender(index: usize) -> Option<H160> {
+		Some(public_key_address(
+			&libsecp256k1::PublicKey::from_secret_key(&secret(index as u8 % 8)),
+		))
+	}
+
+	#[test]
+	fn map_keeps_the_order() {
+		let items = (0..1_000).collect::<Vec<u32>>();
+		for threads in [0, 1, 3, 8, 1_000] {
+			assert_eq!(
+				SenderRecovery::new(threads).map(&items, |item| item * 2),
+				items.iter().map(|item| item * 2).collect::<Vec<_>>(),
+			);
+		}
+		assert!(SenderRecovery::new(4)
+			.map(&[] as &[u32], |item| *item)
+			.is_empty());
+	}
+
+	#[test]
+	fn senders_are_recovered_in_order() {
+		let transactions = transactions(100, 1);
+		let senders = SenderRecovery::new(4).senders(&transactions);
+		assert_eq!(senders, (0..100).map(sender).collect::<Vec<_>>());
+	}
+
+	#[test]
+	fn cached_and_recovered_senders_are_merged_in_order() {
+		let recovery = SenderRecovery::new(4);
+		let transactions = transactions(100, 2);
+
+		// Every other transaction is cached by a first batch.
+		let even = transactions.iter().step_by(2).cloned().collect::<Vec<_>>();
+		recovery.senders(&even);
+		assert_eq!(recovery.cache().len(), 50);
+
+		assert_eq!(
+			recovery.senders(&transactions),
+			(0..100).map(sender).collect::<Vec<_>>()
+		);
+		assert_eq!(recovery.cache().len(), 100);
+		assert_eq!(recovery.sender(&transactions[1]), sender(1));
+		// Clones share the cache.
+		assert_eq!(recovery.clone().cache().len(), 100);
+		assert_eq!(SenderRecovery::new(4).cache().len(), 0);
+	}
+
+	// Run with `cargo test --release -p fc-rpc sender_recovery_latency -- --ignored --nocapture`.
+	#[test]
+	#[ignore = "benchmark"]
+	fn sender_recovery_latency() {
+		const BLOCK_SIZE: usize = 500;
+		const ROUNDS: u64 = 10;
+
+		let mut report = String::new();
+		for threads in [1, 2, 4, 8] {
+			let recovery = SenderRecovery::new(threads);
+			let mut recovered = std::time::Duration::ZERO;
+			let mut cached = std::time::Duration::ZERO;
+			for round in 0..ROUNDS {
+				// Fresh transactions every round, so that none of them is cached at first.
+				let transactions = transactions(BLOCK_SIZE, threads as u64 * ROUNDS + round);
+				let start = Instant::now();
+				let senders = recovery.senders(&transactions);
+				recovered += start.elapsed();
+				let start = Instant::now();
+				assert_eq!(recovery.senders(&transactions), senders);
+				cached += start.elapsed();
+				assert!(senders.iter().all(Option::is_some));
+			}
+			assert!(cached < recovered);
+			report += &format!(
+				"\n{threads} threads: {:?} recovered, {:?} cached",
+				recovered / ROUNDS as u32,
+				cached / ROUNDS as u32,
+			);
+		}
+		eprintln!("Sender recovery of {BLOCK_SIZE} transactions, per block:{report}");
+	}
+}
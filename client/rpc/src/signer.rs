@@ -36,6 +36,24 @@ pub trait EthSigner: Send + Sync {
 		message: TransactionMessage,
 		address: &H160,
 	) -> Result<EthereumTransaction, ErrorObjectOwned>;
+	/// Sign a message hash using the given account, returning the `r || s || v` signature with `v`
+	/// being 27 or 28.
+	fn sign_hash(&self, _hash: H256, _address: &H160) -> Result<[u8; 65], ErrorObjectOwned> {
+		Err(internal_err("signer does not sign messages"))
+	}
+}
+
+/// Sign `hash` with the signer of `address`, which must be one of the dev accounts.
+pub(crate) fn sign_hash(
+	signers: &[Box<dyn EthSigner>],
+	hash: H256,
+	address: H160,
+) -> Result<[u8; 65], ErrorObjectOwned> {
+	let signer = signers
+		.iter()
+		.find(|signer| signer.accounts().contains(&address))
+		.ok_or_else(|| internal_err(format!("{address:?} is not a dev account")))?;
+	signer.sign_hash(hash, &address)
 }
 
 pub struct EthDevSigner {
@@ -60,7 +78,7 @@ fn secret_key_address(secret: &libsecp256k1::SecretKey) -> H160 {
 	public_key_address(&public)
 }
 
-fn public_key_address(public: &libsecp256k1::PublicKey) -> H160 {
+pub(crate) fn public_key_address(public: &libsecp256k1::PublicKey) -> H160 {
 	let mut res = [0u8; 64];
 	res.copy_from_slice(&public.serialize()[1..65]);
 	H160::from(H256::from(keccak_256(&res)))
@@ -160,4 +178,19 @@ impl EthSigner for EthDevSigner {
 
 		transaction.ok_or_else(|| internal_err("signer not available"))
 	}
+
+	fn sign_hash(&self, hash: H256, address: &H160) -> Result<[u8; 65], ErrorObjectOwned> {
+		let secret = self
+			.keys
+			.iter()
+			.find(|secret| &secret_key_address(secret) == address)
+			.ok_or_else(|| internal_err("signer not available"))?;
+		let signing_message = libsecp256k1::Message::parse(&hash.0);
+		let (signature, recid) = libsecp256k1::sign(&signing_message, secret);
+
+		let mut signature_bytes = [0u8; 65];
+		signature_bytes[..64].copy_from_slice(&signature.serialize());
+		signature_bytes[64] = 27 + recid.serialize();
+		Ok(signature_bytes)
+	}
 }
@@ -0,0 +1,406 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Throttling of the raw transaction submissions, before their costly validation.
+
+use std::{
+	sync::{Arc, Mutex},
+	time::Instant,
+};
+
+use ethereum::TransactionV2 as EthereumTransaction;
+use ethereum_types::{H160, H256};
+use futures::future::{self, Either};
+use jsonrpsee::{
+	core::RpcResult, server::middleware::rpc::RpcServiceT, types::Request, MethodResponse,
+};
+use schnellru::{ByLength, LruMap};
+
+use crate::{err, internal_err, transaction_sender};
+
+/// Error code of a throttled submission.
+pub const RATE_LIMITED_ERROR_CODE: i32 = -32005;
+
+/// Maximum number of senders whose token bucket is kept.
+const MAX_TRACKED_SENDERS: u32 = 10_000;
+
+/// Limits of the raw transaction submissions.
+#[derive(Clone, Copy, Debug)]
+pub struct SubmissionLimits {
+	/// Transactions per second a sender can submit, 0 disables the limit.
+	pub sender_rate: u32,
+	/// Transactions a sender can submit at once.
+	pub sender_burst: u32,
+	/// Number of submitted transaction hashes remembered to reject duplicates, 0 disables it.
+	pub known_transactions: u32,
+	/// Transactions per second a connection can submit, 0 disables the limit.
+	pub connection_rate: u32,
+	/// Transactions a connection can submit at once.
+	pub connection_burst: u32,
+}
+
+struct TokenBucket {
+	tokens: f64,
+	updated: Instant,
+}
+
+impl TokenBucket {
+	fn full(burst: u32, now: Instant) -> Self {
+		Self {
+			tokens: f64::from(burst.max(1)),
+			updated: now,
+		}
+	}
+
+	/// Refill the bucket at `rate` tokens per second up to `burst`, then take a token if there
+	/// is one.
+	fn take(&mut self, rate: u32, burst: u32, now: Instant) -> bool {
+		let refill = now.duration_since(self.updated).as_secs_f64() * f64::from(rate);
+		self.tokens = (self.tokens + refill).min(f64::from(burst.max(1)));
+		self.updated = now;
+
+		if self.tokens < 1.0 {
+			return false;
+		}
+		self.tokens -= 1.0;
+		true
+	}
+}
+
+fn rate_limited() -> jsonrpsee::types::error::ErrorObjectOwned {
+	err(RATE_LIMITED_ERROR_CODE, "request rate limit exceeded", None)
+}
+
+/// Rejects the duplicate submissions and rate-limits the submissions of each sender.
+pub struct SubmissionLimiter {
+	limits: SubmissionLimits,
+	senders: Mutex<LruMap<H160, TokenBucket, ByLength>>,
+	known: Mutex<LruMap<H256, (), ByLength>>,
+	metrics: Option<SubmissionLimiterMetrics>,
+}
+
+impl SubmissionLimiter {
+	pub fn new(
+		limits: SubmissionLimits,
+		prometheus_registry: Option<prometheus_endpoint::Registry>,
+	) -> Self {
+		let metrics = match prometheus_registry {
+			Some(registry) => match SubmissionLimiterMetrics::register(&registry) {
+				Ok(metrics) => Some(metrics),
+				Err(e) => {
+					log::error!(target: "rpc", "Failed to register metrics: {:?}", e);
+					None
+				}
+			},
+			None => None,
+		};
+
+		Self {
+			limits,
+			senders: Mutex::new(LruMap::new(ByLength::new(MAX_TRACKED_SENDERS))),
+			known: Mutex::new(LruMap::new(ByLength::new(limits.known_transactions))),
+			metrics,
+		}
+	}
+
+	/// Reject the transaction if it was submitted recently.
+	pub fn check_known(&self, transaction_hash: H256) -> RpcResult<()> {
+		if self.limits.known_transactions == 0 {
+			return Ok(());
+		}
+		if self
+			.known
+			.lock()
+			.expect("poisoned mutex")
+			.peek(&transaction_hash)
+			.is_some()
+		{
+			if let Some(metrics) = &self.metrics {
+				metrics.duplicates.inc();
+			}
+			return Err(internal_err("already known"));
+		}
+		Ok(())
+	}
+
+	/// Take a token of the bucket of the transaction sender, rejecting the transaction if the
+	/// bucket is empty.
+	///
+	/// Transactions with an invalid signature are left to the runtime validation.
+	pub fn check_sender(&self, transaction: &EthereumTransaction) -> RpcResult<()> {
+		if self.limits.sender_rate == 0 {
+			return Ok(());
+		}
+		let Some(sender) = transaction_sender(transaction) else {
+			return Ok(());
+		};
+
+		let now = Instant::now();
+		let mut senders = self.senders.lock().expect("poisoned mutex");
+		let bucket = senders
+			.get_or_insert(sender, || TokenBucket::full(self.limits.sender_burst, now))
+			.expect("ByLength limiter accepts any entry; qed");
+
+		if !bucket.take(self.limits.sender_rate, self.limits.sender_burst, now) {
+			if let Some(metrics) = &self.metrics {
+				metrics.throttled.inc();
+			}
+			return Err(rate_limited());
+		}
+		Ok(())
+	}
+
+	/// Layer limiting the submissions of each connection, to be added to the RPC middleware of
+	/// the server.
+	pub fn connection_layer(&self) -> ConnectionLimitLayer {
+		ConnectionLimitLayer {
+			rate: self.limits.connection_rate,
+			burst: self.limits.connection_burst,
+			throttled: self
+				.metrics
+				.as_ref()
+				.map(|metrics| metrics.throttled.clone()),
+		}
+	}
+
+	/// Remember the hash of a transaction accepted by the pool.
+	pub fn submitted(&self, transaction_hash: H256) {
+		if self.limits.known_transactions > 0 {
+			self.known
+				.lock()
+				.expect("poisoned mutex")
+				.insert(transaction_hash, ());
+		}
+	}
+}
+
+/// Layer of [`ConnectionLimit`], which the server builds once per connection.
+#[derive(Clone)]
+pub struct ConnectionLimitLayer {
+	rate: u32,
+	burst: u32,
+	throttled: Option<prometheus::IntCounter>,
+}
+
+impl<S> tower::Layer<S> for ConnectionLimitLayer {
+	type Service = ConnectionLimit<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		ConnectionLimit {
+			service,
+			rate: self.rate,
+			burst: self.burst,
+			bucket: Arc::new(Mutex::new(TokenBucket::full(self.burst, Instant::now()))),
+			throttled: self.throttled.clone(),
+		}
+	}
+}
+
+/// Rate-limits the `eth_sendRawTransaction` requests of a connection, with a token bucket of
+/// its own.
+///
+/// Requests go straight to the inner service when the limit is disabled.
+#[derive(Clone)]
+pub struct ConnectionLimit<S> {
+	service: S,
+	rate: u32,
+	burst: u32,
+	bucket: Arc<Mutex<TokenBucket>>,
+	throttled: Option<prometheus::IntCounter>,
+}
+
+impl<'a, S> RpcServiceT<'a> for ConnectionLimit<S>
+where
+	S: RpcServiceT<'a>,
+{
+	type Future = Either<S::Future, future::Ready<MethodResponse>>;
+
+	fn call(&self, request: Request<'a>) -> Self::Future {
+		if self.rate == 0 || request.method_name() != "eth_sendRawTransaction" {
+			return Either::Left(self.service.call(request));
+		}
+
+		let taken =
+			self.bucket
+				.lock()
+				.expect("poisoned mutex")
+				.take(self.rate, self.burst, Instant::now());
+		if taken {
+			return Either::Left(self.service.call(request));
+		}
+		if let Some(throttled) = &self.throttled {
+			throttled.inc();
+		}
+		Either::Right(future::ready(MethodResponse::error(
+			request.id,
+			rate_limited(),
+		)))
+	}
+}
+
+struct SubmissionLimiterMetrics {
+	throttled: prometheus::IntCounter,
+	duplicates: prometheus::IntCounter,
+}
+
+impl SubmissionLimiterMetrics {
+	fn register(
+		registry: &prometheus_endpoint::Registry,
+	) -> Result<Self, prometheus_endpoint::PrometheusError> {
+		Ok(Self {
+			throttled: prometheus_endpoint::register(
+				prometheus::IntCounter::new(
+					"frontier_eth_submissions_throttled",
+					"Raw transactions rejected by the rate limit of their sender or connection.",
+				)?,
+				registry,
+			)?,
+			duplicates: prometheus_endpoint::register(
+				prometheus::IntCounter::new(
+					"frontier_eth_submissions_duplicate",
+					"Raw transactions rejected as already submitted.",
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn limiter(sender_rate: u32, sender_burst: u32) -> SubmissionLimiter {
+		SubmissionLimiter::new(
+			SubmissionLimits {
+				sender_rate,
+				sender_burst,
+				known_transactions: 16,
+				connection_rate: sender_rate,
+				connection_burst: sender_burst,
+			},
+			None,
+		)
+	}
+
+	/// Answers every request with an error of code 0.
+	#[derive(Clone)]
+	struct Answer;
+
+	impl<'a> RpcServiceT<'a> for Answer {
+		type Future = future::Ready<MethodResponse>;
+
+		fn call(&self, request: Request<'a>) -> Self::Future {
+			future::ready(MethodResponse::error(request.id, err(0, "answered", None)))
+		}
+	}
+
+	fn call<S: for<'a> RpcServiceT<'a>>(service: &S, method: &str) -> Option<i32> {
+		let request = Request::new(method.into(), None, jsonrpsee::types::Id::Number(1));
+		futures::executor::block_on(service.call(request)).as_error_code()
+	}
+
+	fn transaction(secret: u8) -> EthereumTransaction {
+		let secret = libsecp256k1::SecretKey::parse(&[secret; 32]).unwrap();
+		let message = ethereum::LegacyTransactionMessage {
+			nonce: Default::default(),
+			gas_price: Default::default(),
+			gas_limit: 21_000.into(),
+			action: ethereum::TransactionAction::Create,
+			value: Default::default(),
+			input: Vec::new(),
+			chain_id: None,
+		};
+		let signing_message = libsecp256k1::Message::parse_slice(&message.hash()[..]).unwrap();
+		let (signature, recid) = libsecp256k1::sign(&signing_message, &secret);
+		let rs = signature.serialize();
+		let signature = ethereum::TransactionSignature::new(
+			27 + recid.serialize() as u64,
+			H256::from_slice(&rs[0..32]),
+			H256::from_slice(&rs[32..64]),
+		)
+		.unwrap();
+		EthereumTransaction::Legacy(ethereum::LegacyTransaction {
+			nonce: message.nonce,
+			gas_price: message.gas_price,
+			gas_limit: message.gas_limit,
+			action: message.action,
+			value: message.value,
+			input: message.input,
+			signature,
+		})
+	}
+
+	#[test]
+	fn rejects_known_transactions() {
+		let limiter = limiter(0, 0);
+		let hash = H256::repeat_byte(0x01);
+		assert!(limiter.check_known(hash).is_ok());
+		limiter.submitted(hash);
+		assert_eq!(
+			limiter.check_known(hash).unwrap_err().message(),
+			"already known"
+		);
+		assert!(limiter.check_known(H256::repeat_byte(0x02)).is_ok());
+	}
+
+	#[test]
+	fn throttles_each_sender() {
+		let limiter = limiter(1, 2);
+		let (alice, bob) = (transaction(0x01), transaction(0x02));
+		assert!(limiter.check_sender(&alice).is_ok());
+		assert!(limiter.check_sender(&alice).is_ok());
+		assert_eq!(
+			limiter.check_sender(&alice).unwrap_err().code(),
+			RATE_LIMITED_ERROR_CODE
+		);
+		assert!(limiter.check_sender(&bob).is_ok());
+	}
+
+	#[test]
+	fn zero_rate_disables_throttling() {
+		let limiter = limiter(0, 0);
+		let alice = transaction(0x01);
+		for _ in 0..10 {
+			assert!(limiter.check_sender(&alice).is_ok());
+		}
+	}
+
+	#[test]
+	fn throttles_each_connection() {
+		let layer = limiter(1, 2).connection_layer();
+		let (first, second) = (
+			tower::Layer::layer(&layer, Answer),
+			tower::Layer::layer(&layer, Answer),
+		);
+		assert_eq!(call(&first, "eth_sendRawTransaction"), Some(0));
+		assert_eq!(call(&first, "eth_sendRawTransaction"), Some(0));
+		assert_eq!(
+			call(&first, "eth_sendRawTransaction"),
+			Some(RATE_LIMITED_ERROR_CODE)
+		);
+		// Other methods and other connections are not limited.
+		assert_eq!(call(&first, "eth_blockNumber"), Some(0));
+		assert_eq!(call(&second, "eth_sendRawTransaction"), Some(0));
+
+		let unlimited = tower::Layer::layer(&limiter(0, 0).connection_layer(), Answer);
+		for _ in 0..10 {
+			assert_eq!(call(&unlimited, "eth_sendRawTransaction"), Some(0));
+		}
+	}
+}
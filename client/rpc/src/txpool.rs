@@ -19,7 +19,7 @@
 use std::{marker::PhantomData, sync::Arc};
 
 use ethereum::TransactionV2 as EthereumTransaction;
-use ethereum_types::{H160, H256, U256};
+use ethereum_types::U256;
 use jsonrpsee::core::RpcResult;
 use serde::Serialize;
 // substrate
@@ -27,7 +27,6 @@ use sc_transaction_pool::{ChainApi, Pool};
 use sc_transaction_pool_api::InPoolTransaction;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
-use sp_core::hashing::keccak_256;
 use sp_runtime::traits::Block as BlockT;
 // Frontier
 use fc_rpc_core::{
@@ -36,7 +35,7 @@ use fc_rpc_core::{
 };
 use fp_rpc::EthereumRuntimeRPCApi;
 
-use crate::{internal_err, public_key};
+use crate::{internal_err, SenderRecovery};
 
 struct TxPoolTransactions {
 	ready: Vec<EthereumTransaction>,
@@ -46,6 +45,7 @@ struct TxPoolTransactions {
 pub struct TxPool<B, C, A: ChainApi> {
 	client: Arc<C>,
 	graph: Arc<Pool<A>>,
+	sender_recovery: SenderRecovery,
 	_marker: PhantomData<B>,
 }
 
@@ -54,6 +54,7 @@ impl<B, C, A: ChainApi> Clone for TxPool<B, C, A> {
 		Self {
 			client: self.client.clone(),
 			graph: self.graph.clone(),
+			sender_recovery: self.sender_recovery.clone(),
 			_marker: PhantomData,
 		}
 	}
@@ -72,28 +73,23 @@ where
 		T: BuildFrom + Serialize,
 	{
 		let txns = self.collect_txpool_transactions()?;
-		let pending = Self::build_txn_map::<'_, T>(txns.ready.iter());
-		let queued = Self::build_txn_map::<'_, T>(txns.future.iter());
+		let pending = self.build_txn_map::<T>(&txns.ready);
+		let queued = self.build_txn_map::<T>(&txns.future);
 		Ok(TxPoolResult { pending, queued })
 	}
 
-	fn build_txn_map<'a, T>(
-		txns: impl Iterator<Item = &'a EthereumTransaction>,
-	) -> TransactionMap<T>
+	fn build_txn_map<T>(&self, txns: &[EthereumTransaction]) -> TransactionMap<T>
 	where
 		T: BuildFrom + Serialize,
 	{
 		let mut result = TransactionMap::<T>::new();
-		for txn in txns {
+		for (txn, from) in txns.iter().zip(self.sender_recovery.senders(txns)) {
 			let nonce = match txn {
 				EthereumTransaction::Legacy(t) => t.nonce,
 				EthereumTransaction::EIP2930(t) => t.nonce,
 				EthereumTransaction::EIP1559(t) => t.nonce,
 			};
-			let from = match public_key(txn) {
-				Ok(pk) => H160::from(H256::from(keccak_256(&pk))),
-				Err(_) => H160::default(),
-			};
+			let from = from.unwrap_or_default();
 			result
 				.entry(from)
 				.or_default()
@@ -136,10 +132,11 @@ where
 }
 
 impl<B, C, A: ChainApi> TxPool<B, C, A> {
-	pub fn new(client: Arc<C>, graph: Arc<Pool<A>>) -> Self {
+	pub fn new(client: Arc<C>, graph: Arc<Pool<A>>, sender_recovery: SenderRecovery) -> Self {
 		Self {
 			client,
 			graph,
+			sender_recovery,
 			_marker: PhantomData,
 		}
 	}
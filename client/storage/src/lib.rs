@@ -29,7 +29,7 @@ use sc_client_api::{backend::Backend, StorageProvider};
 use sp_api::ProvideRuntimeApi;
 use sp_runtime::{traits::Block as BlockT, Permill};
 // Frontier
-use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
+use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus, Withdrawal};
 use fp_storage::EthereumStorageSchema;
 
 pub use self::overrides::*;
@@ -165,4 +165,19 @@ where
 			None => self.fallback.is_eip1559(at),
 		}
 	}
+
+	fn current_withdrawals(&self, at: B::Hash) -> Option<Vec<Withdrawal>> {
+		match self.querier.storage_schema(at) {
+			Some(EthereumStorageSchema::V1) => {
+				SchemaV1StorageOverrideRef::new(&self.querier).current_withdrawals(at)
+			}
+			Some(EthereumStorageSchema::V2) => {
+				SchemaV2StorageOverrideRef::new(&self.querier).current_withdrawals(at)
+			}
+			Some(EthereumStorageSchema::V3) => {
+				SchemaV3StorageOverrideRef::new(&self.querier).current_withdrawals(at)
+			}
+			None => self.fallback.current_withdrawals(at),
+		}
+	}
 }
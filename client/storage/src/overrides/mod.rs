@@ -26,7 +26,7 @@ use sp_io::hashing::{blake2_128, twox_128};
 use sp_runtime::{traits::Block as BlockT, Permill};
 use sp_storage::StorageKey;
 // Frontier
-use fp_rpc::TransactionStatus;
+use fp_rpc::{TransactionStatus, Withdrawal};
 use fp_storage::{constants::*, EthereumStorageSchema, PALLET_ETHEREUM_SCHEMA};
 
 mod runtime_api;
@@ -68,6 +68,8 @@ pub trait StorageOverride<Block: BlockT>: Send + Sync {
 	fn elasticity(&self, at: Block::Hash) -> Option<Permill>;
 	/// Return `true` if the request block is post-eip1559.
 	fn is_eip1559(&self, at: Block::Hash) -> bool;
+	/// Return the withdrawals of the given block, if it processed any.
+	fn current_withdrawals(&self, at: Block::Hash) -> Option<Vec<Withdrawal>>;
 }
 
 fn storage_prefix_build(module: &[u8], storage: &[u8]) -> Vec<u8> {
@@ -148,6 +150,11 @@ where
 		self.query::<Vec<TransactionStatus>>(at, &StorageKey(key))
 	}
 
+	pub fn current_withdrawals(&self, at: B::Hash) -> Option<Vec<Withdrawal>> {
+		let key = storage_prefix_build(PALLET_ETHEREUM, ETHEREUM_CURRENT_WITHDRAWALS);
+		self.query::<Vec<Withdrawal>>(at, &StorageKey(key))
+	}
+
 	pub fn elasticity(&self, at: B::Hash) -> Option<Permill> {
 		let key = storage_prefix_build(PALLET_BASE_FEE, BASE_FEE_ELASTICITY);
 		self.query::<Permill>(at, &StorageKey(key))
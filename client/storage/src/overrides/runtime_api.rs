@@ -23,7 +23,7 @@ use ethereum_types::{Address, H256, U256};
 use sp_api::{ApiExt, ApiRef, ProvideRuntimeApi};
 use sp_runtime::{traits::Block as BlockT, Permill};
 // Frontier
-use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
+use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus, Withdrawal};
 
 use crate::overrides::StorageOverride;
 
@@ -146,4 +146,9 @@ where
 			false
 		}
 	}
+
+	fn current_withdrawals(&self, _block_hash: B::Hash) -> Option<Vec<Withdrawal>> {
+		// The runtime API does not expose the withdrawals.
+		None
+	}
 }
@@ -23,7 +23,7 @@ use ethereum_types::{Address, H256, U256};
 use sc_client_api::backend::{Backend, StorageProvider};
 use sp_runtime::{traits::Block as BlockT, Permill};
 // Frontier
-use fp_rpc::TransactionStatus;
+use fp_rpc::{TransactionStatus, Withdrawal};
 
 use crate::overrides::{StorageOverride, StorageQuerier};
 
@@ -76,6 +76,10 @@ pub mod v1 {
 		fn is_eip1559(&self, at: B::Hash) -> bool {
 			SchemaStorageOverrideRef::new(&self.querier).is_eip1559(at)
 		}
+
+		fn current_withdrawals(&self, at: B::Hash) -> Option<Vec<Withdrawal>> {
+			SchemaStorageOverrideRef::new(&self.querier).current_withdrawals(at)
+		}
 	}
 
 	/// A storage override reference for runtimes that use schema v1.
@@ -138,6 +142,10 @@ pub mod v1 {
 		fn is_eip1559(&self, _at: B::Hash) -> bool {
 			false
 		}
+
+		fn current_withdrawals(&self, _at: B::Hash) -> Option<Vec<Withdrawal>> {
+			None
+		}
 	}
 }
 
@@ -190,6 +198,10 @@ pub mod v2 {
 		fn is_eip1559(&self, at: B::Hash) -> bool {
 			SchemaStorageOverrideRef::new(&self.querier).is_eip1559(at)
 		}
+
+		fn current_withdrawals(&self, at: B::Hash) -> Option<Vec<Withdrawal>> {
+			SchemaStorageOverrideRef::new(&self.querier).current_withdrawals(at)
+		}
 	}
 
 	/// A storage override reference for runtimes that use schema v2.
@@ -250,6 +262,10 @@ pub mod v2 {
 		fn is_eip1559(&self, _at: B::Hash) -> bool {
 			true
 		}
+
+		fn current_withdrawals(&self, _at: B::Hash) -> Option<Vec<Withdrawal>> {
+			None
+		}
 	}
 }
 
@@ -302,6 +318,10 @@ pub mod v3 {
 		fn is_eip1559(&self, at: B::Hash) -> bool {
 			SchemaStorageOverrideRef::new(&self.querier).is_eip1559(at)
 		}
+
+		fn current_withdrawals(&self, at: B::Hash) -> Option<Vec<Withdrawal>> {
+			SchemaStorageOverrideRef::new(&self.querier).current_withdrawals(at)
+		}
 	}
 
 	/// A storage override for runtimes that use schema v3.
@@ -348,5 +368,9 @@ pub mod v3 {
 		fn is_eip1559(&self, _at: B::Hash) -> bool {
 			true
 		}
+
+		fn current_withdrawals(&self, at: B::Hash) -> Option<Vec<Withdrawal>> {
+			self.querier.current_withdrawals(at)
+		}
 	}
 }
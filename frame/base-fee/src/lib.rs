@@ -50,6 +50,13 @@ pub mod pallet {
 		type Threshold: BaseFeeThreshold;
 		type DefaultBaseFeePerGas: Get<U256>;
 		type DefaultElasticity: Get<Permill>;
+		/// Origin allowed to override the base fee with `set_base_fee_per_gas`.
+		type SetBaseFeeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Lowest base fee, bounding both `set_base_fee_per_gas` and the adjustment of the base fee.
+		type MinBaseFee: Get<U256>;
+		/// Highest base fee, bounding both `set_base_fee_per_gas` and the adjustment of the base
+		/// fee.
+		type MaxBaseFee: Get<U256>;
 	}
 
 	#[pallet::genesis_config]
@@ -112,6 +119,12 @@ pub mod pallet {
 		NewElasticity { elasticity: Permill },
 	}
 
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The base fee is out of the `[MinBaseFee, MaxBaseFee]` bounds.
+		BaseFeeOutOfBounds,
+	}
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(_: BlockNumberFor<T>) -> Weight {
@@ -162,7 +175,7 @@ pub mod pallet {
 						let increase = scaled_basefee
 							.checked_div(U256::from(1_000_000))
 							.unwrap_or_else(U256::zero);
-						*bf = bf.saturating_add(increase);
+						*bf = Self::clamp_base_fee(bf.saturating_add(increase));
 					} else {
 						Self::deposit_event(Event::BaseFeeOverflow);
 					}
@@ -186,9 +199,9 @@ pub mod pallet {
 							.checked_div(U256::from(1_000_000))
 							.unwrap_or(default_base_fee);
 						if bf.saturating_sub(decrease) >= lowest_base_fee {
-							*bf = bf.saturating_sub(decrease);
+							*bf = Self::clamp_base_fee(bf.saturating_sub(decrease));
 						} else {
-							*bf = lowest_base_fee;
+							*bf = Self::clamp_base_fee(lowest_base_fee);
 						}
 					} else {
 						Self::deposit_event(Event::BaseFeeOverflow);
@@ -200,10 +213,15 @@ pub mod pallet {
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
+		/// Override the base fee, which keeps adjusting from the new value on the next blocks.
 		#[pallet::call_index(0)]
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
 		pub fn set_base_fee_per_gas(origin: OriginFor<T>, fee: U256) -> DispatchResult {
-			ensure_root(origin)?;
+			T::SetBaseFeeOrigin::ensure_origin(origin)?;
+			ensure!(
+				fee >= T::MinBaseFee::get() && fee <= T::MaxBaseFee::get(),
+				Error::<T>::BaseFeeOutOfBounds
+			);
 			let _ = Self::set_base_fee_per_gas_inner(fee);
 			Self::deposit_event(Event::NewBaseFeePerGas { fee });
 			Ok(())
@@ -227,6 +245,11 @@ impl<T: Config> fp_evm::FeeCalculator for Pallet<T> {
 }
 
 impl<T: Config> Pallet<T> {
+	/// Bring `base_fee` within the `[MinBaseFee, MaxBaseFee]` bounds.
+	fn clamp_base_fee(base_fee: U256) -> U256 {
+		base_fee.max(T::MinBaseFee::get()).min(T::MaxBaseFee::get())
+	}
+
 	pub fn set_base_fee_per_gas_inner(value: U256) -> Weight {
 		<BaseFeePerGas<T>>::put(value);
 		T::DbWeight::get().writes(1)
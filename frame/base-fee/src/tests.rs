@@ -16,7 +16,7 @@
 // limitations under the License.
 
 use frame_support::{
-	assert_ok, derive_impl,
+	assert_noop, assert_ok, derive_impl,
 	dispatch::DispatchClass,
 	parameter_types,
 	traits::{ConstU32, OnFinalize},
@@ -68,6 +68,8 @@ impl frame_system::Config for Test {
 parameter_types! {
 	pub DefaultBaseFeePerGas: U256 = U256::from(100_000_000_000_u128);
 	pub DefaultElasticity: Permill = Permill::from_parts(125_000);
+	pub MinBaseFee: U256 = U256::from(1_000_000);
+	pub MaxBaseFee: U256 = U256::from(1_000_000_000_000_u128);
 }
 
 pub struct BaseFeeThreshold;
@@ -88,6 +90,9 @@ impl Config for Test {
 	type Threshold = BaseFeeThreshold;
 	type DefaultBaseFeePerGas = DefaultBaseFeePerGas;
 	type DefaultElasticity = DefaultElasticity;
+	type SetBaseFeeOrigin = frame_system::EnsureRoot<u64>;
+	type MinBaseFee = MinBaseFee;
+	type MaxBaseFee = MaxBaseFee;
 }
 
 frame_support::construct_runtime!(
@@ -202,14 +207,7 @@ fn should_handle_consecutive_full_blocks() {
 			BaseFee::on_finalize(System::block_number());
 			System::set_block_number(System::block_number() + 1);
 		}
-		assert_eq!(
-			BaseFeePerGas::<Test>::get(),
-			// Max value allowed in the algorithm before overflowing U256.
-			U256::from_dec_str(
-				"930583037201699994746877284806656508753618758732556029383742480470471799"
-			)
-			.unwrap()
-		);
+		assert_eq!(BaseFeePerGas::<Test>::get(), MaxBaseFee::get());
 	});
 	let zero_elasticity = Permill::zero();
 	new_test_ext(Some(base_fee), Some(zero_elasticity)).execute_with(|| {
@@ -285,9 +283,40 @@ fn set_base_fee_per_gas_dispatchable() {
 		assert_eq!(BaseFeePerGas::<Test>::get(), U256::from(1000000000));
 		assert_ok!(BaseFee::set_base_fee_per_gas(
 			RuntimeOrigin::root(),
-			U256::from(1)
+			U256::from(2_000_000)
+		));
+		assert_eq!(BaseFeePerGas::<Test>::get(), U256::from(2_000_000));
+
+		// The base fee keeps adjusting from the new value.
+		System::register_extra_weight_unchecked(
+			Weight::from_parts(1000000000000, 0),
+			DispatchClass::Normal,
+		);
+		BaseFee::on_finalize(System::block_number());
+		assert_eq!(BaseFeePerGas::<Test>::get(), U256::from(2_250_000));
+	});
+}
+
+#[test]
+fn set_base_fee_per_gas_checks_origin_and_bounds() {
+	let base_fee = U256::from(1_000_000_000);
+	new_test_ext(Some(base_fee), None).execute_with(|| {
+		assert_noop!(
+			BaseFee::set_base_fee_per_gas(RuntimeOrigin::signed(1), U256::from(2_000_000)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			BaseFee::set_base_fee_per_gas(RuntimeOrigin::root(), MinBaseFee::get() - 1),
+			Error::<Test>::BaseFeeOutOfBounds
+		);
+		assert_noop!(
+			BaseFee::set_base_fee_per_gas(RuntimeOrigin::root(), MaxBaseFee::get() + 1),
+			Error::<Test>::BaseFeeOutOfBounds
+		);
+		assert_ok!(BaseFee::set_base_fee_per_gas(
+			RuntimeOrigin::root(),
+			MaxBaseFee::get()
 		));
-		assert_eq!(BaseFeePerGas::<Test>::get(), U256::from(1));
 	});
 }
 
@@ -303,3 +332,64 @@ fn set_elasticity_dispatchable() {
 		assert_eq!(Elasticity::<Test>::get(), Permill::from_parts(1_000));
 	});
 }
+
+mod adjustment_invariants {
+	use super::*;
+	use proptest::prelude::*;
+
+	/// Block fullness in parts per million, biased towards empty and full blocks.
+	fn fullness() -> impl Strategy<Value = u32> {
+		prop_oneof![Just(0), Just(1_000_000), 0..=1_000_000u32]
+	}
+
+	/// Initial base fee, biased towards `MaxBaseFee` to exercise the cap.
+	fn base_fee() -> impl Strategy<Value = u128> {
+		let lowest = get_lowest_base_fee().low_u128();
+		let max = MaxBaseFee::get().low_u128();
+		prop_oneof![Just(max), (max - max / 8)..=max, lowest..=max]
+	}
+
+	proptest! {
+		#![proptest_config(ProptestConfig::with_cases(64))]
+
+		#[test]
+		fn base_fee_adjustment_is_bounded(
+			base_fee in base_fee(),
+			elasticity in 0..=DefaultElasticity::get().deconstruct(),
+			blocks in prop::collection::vec(fullness(), 1000),
+		) {
+			let elasticity = Permill::from_parts(elasticity);
+			new_test_ext(Some(base_fee.into()), Some(elasticity)).execute_with(|| {
+				let max_block = <Test as frame_system::Config>::BlockWeights::get().max_block;
+				let target = BaseFeeThreshold::ideal().deconstruct();
+				for fullness in blocks {
+					let before = BaseFeePerGas::<Test>::get();
+					System::set_block_consumed_resources(
+						Weight::from_parts(Permill::from_parts(fullness) * max_block.ref_time(), 0),
+						0,
+					);
+					BaseFee::on_finalize(System::block_number());
+					System::set_block_number(System::block_number() + 1);
+					let after = BaseFeePerGas::<Test>::get();
+
+					// At most the elasticity, 12.5% by default, of change per block.
+					let max_change =
+						before.saturating_mul(elasticity.deconstruct().into()) / 1_000_000;
+					prop_assert!(after <= before + max_change);
+					prop_assert!(after >= before.saturating_sub(max_change));
+					// Towards the target fullness.
+					if fullness > target {
+						prop_assert!(after >= before);
+					} else if fullness < target {
+						prop_assert!(after <= before);
+					}
+					// Never below the lowest base fee, and so `MinBaseFee`.
+					prop_assert!(after >= get_lowest_base_fee());
+					prop_assert!(after >= MinBaseFee::get());
+					prop_assert!(after <= MaxBaseFee::get());
+				}
+				Ok(())
+			})?;
+		}
+	}
+}
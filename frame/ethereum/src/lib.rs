@@ -66,7 +66,7 @@ use fp_ethereum::ValidatedTransaction as ValidatedTransactionT;
 use fp_evm::{
 	CallOrCreateInfo, CheckEvmTransaction, CheckEvmTransactionConfig, TransactionValidationError,
 };
-pub use fp_rpc::TransactionStatus;
+pub use fp_rpc::{TransactionStatus, Withdrawal};
 use fp_storage::{EthereumStorageSchema, PALLET_ETHEREUM_SCHEMA};
 use pallet_evm::{BlockHashMapping, FeeCalculator, GasWeightMapping, Runner};
 
@@ -170,6 +170,23 @@ where
 	}
 }
 
+/// Processing of the withdrawals of a block, EIP-4895.
+pub trait ProcessWithdrawals {
+	/// Credit the withdrawals of the block being initialized, returning them along with the
+	/// weight consumed.
+	///
+	/// The amounts of the withdrawals are in Gwei, as in EIP-4895, and are credited in wei: each
+	/// Gwei is 10^9 wei of the EVM balance of the account.
+	fn process_withdrawals() -> (Vec<Withdrawal>, Weight);
+}
+
+/// No withdrawals, for chains without withdrawals to EVM accounts.
+impl ProcessWithdrawals for () {
+	fn process_withdrawals() -> (Vec<Withdrawal>, Weight) {
+		(Vec::new(), Weight::zero())
+	}
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Default)]
 pub enum PostLogContent {
 	#[default]
@@ -202,6 +219,8 @@ pub mod pallet {
 		type PostLogContent: Get<PostLogContent>;
 		/// The maximum length of the extra data in the Executed event.
 		type ExtraDataLength: Get<u32>;
+		/// How the withdrawals of a block are processed.
+		type WithdrawalProcessor: ProcessWithdrawals;
 	}
 
 	#[pallet::hooks]
@@ -228,11 +247,23 @@ pub mod pallet {
 				));
 			}
 			Pending::<T>::kill();
+			let _ = PendingTransactionHashes::<T>::clear(u32::MAX, None);
 		}
 
 		fn on_initialize(_: BlockNumberFor<T>) -> Weight {
 			let mut weight = T::SystemWeightInfo::kill_storage(1);
 
+			// Withdrawals are processed before the transactions of the block.
+			let (withdrawals, withdrawals_weight) = T::WithdrawalProcessor::process_withdrawals();
+			weight = weight
+				.saturating_add(withdrawals_weight)
+				.saturating_add(T::DbWeight::get().writes(1));
+			if withdrawals.is_empty() {
+				CurrentWithdrawals::<T>::kill();
+			} else {
+				CurrentWithdrawals::<T>::put(withdrawals);
+			}
+
 			// If the digest contain an existing ethereum block(encoded as PreLog), If contains,
 			// execute the imported block firstly and disable transact dispatch function.
 			if let Ok(log) = fp_consensus::find_pre_log(&frame_system::Pallet::<T>::digest()) {
@@ -283,6 +314,7 @@ pub mod pallet {
 				let transaction_data: TransactionData = transaction.into();
 				transaction_data.gas_limit.unique_saturated_into()
 			}, without_base_extrinsic_weight)
+			.saturating_add(Pallet::<T>::transaction_hash_weight())
 		})]
 		pub fn transact(
 			origin: OriginFor<T>,
@@ -325,6 +357,10 @@ pub mod pallet {
 	pub type Pending<T: Config> =
 		StorageValue<_, Vec<(Transaction, TransactionStatus, Receipt)>, ValueQuery>;
 
+	/// Hashes of the current building block's transactions, to reject duplicates.
+	#[pallet::storage]
+	pub type PendingTransactionHashes<T: Config> = StorageMap<_, Identity, H256, ()>;
+
 	/// The current Ethereum block.
 	#[pallet::storage]
 	pub type CurrentBlock<T: Config> = StorageValue<_, ethereum::BlockV2>;
@@ -337,6 +373,10 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type CurrentTransactionStatuses<T: Config> = StorageValue<_, Vec<TransactionStatus>>;
 
+	/// The withdrawals processed in the current block, if any.
+	#[pallet::storage]
+	pub type CurrentWithdrawals<T: Config> = StorageValue<_, Vec<Withdrawal>>;
+
 	// Mapping for block number and hashes.
 	#[pallet::storage]
 	pub type BlockHash<T: Config> = StorageMap<_, Twox64Concat, U256, H256, ValueQuery>;
@@ -514,6 +554,7 @@ impl<T: Config> Pallet<T> {
 		.and_then(|v| v.with_base_fee())
 		.and_then(|v| v.with_balance_for(&who))
 		.map_err(|e| e.0)?;
+		Self::check_gas_price_floor(&transaction_data, base_fee)?;
 
 		// EIP-3607: https://eips.ethereum.org/EIPS/eip-3607
 		// Do not allow transactions for which `tx.sender` has any code deployed.
@@ -679,6 +720,7 @@ impl<T: Config> Pallet<T> {
 		};
 
 		Pending::<T>::append((transaction, status, receipt));
+		PendingTransactionHashes::<T>::insert(transaction_hash, ());
 
 		Self::deposit_event(Event::Executed {
 			from: source,
@@ -703,7 +745,7 @@ impl<T: Config> Pallet<T> {
 							*gas_to_weight.proof_size_mut() = proof_size_usage;
 						}
 					}
-					Some(gas_to_weight)
+					Some(gas_to_weight.saturating_add(Self::transaction_hash_weight()))
 				},
 				pays_fee: Pays::No,
 			},
@@ -873,7 +915,7 @@ impl<T: Config> Pallet<T> {
 				chain_id: T::ChainId::get(),
 				is_transactional: true,
 			},
-			transaction_data.into(),
+			transaction_data.clone().into(),
 			weight_limit,
 			proof_size_base_cost,
 		)
@@ -883,6 +925,50 @@ impl<T: Config> Pallet<T> {
 		.and_then(|v| v.with_balance_for(&who))
 		.map_err(|e| TransactionValidityError::Invalid(e.0))?;
 
+		Self::check_gas_price_floor(&transaction_data, base_fee)?;
+		Self::check_no_duplicate_hash(transaction.hash())?;
+
+		Ok(())
+	}
+
+	/// Reject a transaction whose hash is already one of the block, as the mapping of the
+	/// second one would overwrite the first one's. The nonce check normally rules this out.
+	fn check_no_duplicate_hash(transaction_hash: H256) -> Result<(), InvalidTransaction> {
+		if PendingTransactionHashes::<T>::contains_key(transaction_hash) {
+			return Err(InvalidTransaction::BadProof);
+		}
+		Ok(())
+	}
+
+	/// Weight of tracking the hash of a transaction of the block:
+	///	- read: `PendingTransactionHashes` in `check_no_duplicate_hash`
+	///	- write: `PendingTransactionHashes` after the transaction is applied
+	///	- write: `PendingTransactionHashes` cleared in `on_finalize`
+	fn transaction_hash_weight() -> Weight {
+		T::DbWeight::get().reads_writes(1, 2)
+	}
+
+	/// Reject the transactions paying less than [`pallet_evm::Config::GasPriceFloor`] per gas,
+	/// the effective gas price of EIP-1559 transactions being the one at `base_fee`.
+	fn check_gas_price_floor(
+		transaction_data: &TransactionData,
+		base_fee: U256,
+	) -> Result<(), InvalidTransaction> {
+		let effective_gas_price =
+			match (transaction_data.gas_price, transaction_data.max_fee_per_gas) {
+				(Some(gas_price), _) => gas_price,
+				(None, Some(max_fee_per_gas)) => max_fee_per_gas.min(
+					base_fee.saturating_add(
+						transaction_data
+							.max_priority_fee_per_gas
+							.unwrap_or_default(),
+					),
+				),
+				(None, None) => U256::zero(),
+			};
+		if effective_gas_price < T::GasPriceFloor::get() {
+			return Err(InvalidTransaction::Payment);
+		}
 		Ok(())
 	}
 
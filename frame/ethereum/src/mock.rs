@@ -22,7 +22,7 @@ use rlp::RlpStream;
 // Substrate
 use frame_support::{
 	derive_impl, parameter_types,
-	traits::{ConstU32, FindAuthor},
+	traits::{ConstU32, Currency, FindAuthor},
 	weights::Weight,
 	ConsensusEngineId, PalletId,
 };
@@ -156,6 +156,8 @@ impl AddressMapping<AccountId32> for HashedAddressMapping {
 
 parameter_types! {
 	pub SuicideQuickClearLimit: u32 = 0;
+	pub static GasPriceFloor: U256 = U256::zero();
+	pub static Withdrawals: Vec<Withdrawal> = Vec::new();
 }
 
 impl pallet_evm::Config for Test {
@@ -172,13 +174,17 @@ impl pallet_evm::Config for Test {
 	type PrecompilesValue = ();
 	type ChainId = ChainId;
 	type BlockGasLimit = BlockGasLimit;
+	type GasPriceFloor = GasPriceFloor;
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
 	type OnChargeTransaction = ();
 	type OnCreate = ();
+	type OnAfterEvmExecution = ();
+	type GasLimitOverrides = ();
 	type FindAuthor = FindAuthorTruncated;
 	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
 	type Timestamp = Timestamp;
+	type EvmConfigSchedule = ();
 	type WeightInfo = ();
 }
 
@@ -186,11 +192,30 @@ parameter_types! {
 	pub const PostBlockAndTxnHashes: PostLogContent = PostLogContent::BlockAndTxnHashes;
 }
 
+/// Wei in a Gwei, the unit of the withdrawal amounts.
+pub const GWEI: u64 = 1_000_000_000;
+
+/// Credits the `Withdrawals` amounts, converted from Gwei to wei.
+pub struct MockWithdrawalProcessor;
+impl ProcessWithdrawals for MockWithdrawalProcessor {
+	fn process_withdrawals() -> (Vec<Withdrawal>, Weight) {
+		let withdrawals = Withdrawals::get();
+		for withdrawal in &withdrawals {
+			let _ = <Balances as Currency<_>>::deposit_creating(
+				&HashedAddressMapping::into_account_id(withdrawal.address),
+				withdrawal.amount.saturating_mul(GWEI),
+			);
+		}
+		(withdrawals, Weight::zero())
+	}
+}
+
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type StateRoot = IntermediateStateRoot<Self>;
 	type PostLogContent = PostBlockAndTxnHashes;
 	type ExtraDataLength = ConstU32<30>;
+	type WithdrawalProcessor = MockWithdrawalProcessor;
 }
 
 impl fp_self_contained::SelfContainedCall for RuntimeCall {
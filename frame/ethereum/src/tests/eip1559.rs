@@ -117,6 +117,39 @@ fn transaction_with_gas_limit_greater_than_max_extrinsic_should_fail_pre_dispatc
 	});
 }
 
+#[test]
+fn transaction_below_gas_price_floor_should_not_work() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		// At a base fee of 1, the effective gas price is 2 of at most 3.
+		let mut transaction = eip1559_erc20_creation_unsigned_transaction();
+		transaction.max_fee_per_gas = U256::from(3);
+		let call = crate::Call::<Test>::transact {
+			transaction: transaction.sign(&alice.private_key, None),
+		};
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<_, _, SignedExtra, _> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Ethereum(call.clone()),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+
+		GasPriceFloor::set(&U256::from(2));
+		assert_ok!(call
+			.validate_self_contained(&source, &dispatch_info, 0)
+			.unwrap());
+
+		GasPriceFloor::set(&U256::from(3));
+		assert_err!(
+			call.validate_self_contained(&source, &dispatch_info, 0)
+				.unwrap(),
+			InvalidTransaction::Payment
+		);
+	});
+}
+
 #[test]
 fn transaction_should_increment_nonce() {
 	let (pairs, mut ext) = new_test_ext(1);
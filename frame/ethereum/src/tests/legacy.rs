@@ -22,6 +22,7 @@ use evm::{ExitReason, ExitRevert, ExitSucceed};
 use fp_ethereum::{TransactionData, ValidatedTransaction};
 use frame_support::{
 	dispatch::{DispatchClass, GetDispatchInfo},
+	traits::Hooks,
 	weights::Weight,
 };
 use pallet_evm::AddressMapping;
@@ -85,6 +86,41 @@ fn transaction_without_enough_gas_should_not_work() {
 	});
 }
 
+#[test]
+fn transaction_below_gas_price_floor_should_not_work() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		let call = crate::Call::<Test>::transact {
+			transaction: legacy_erc20_creation_transaction(alice),
+		};
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<_, _, SignedExtra, _> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Ethereum(call.clone()),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+
+		// The transaction pays exactly the floor.
+		GasPriceFloor::set(&U256::from(1));
+		assert_ok!(call
+			.validate_self_contained(&source, &dispatch_info, 0)
+			.unwrap());
+
+		GasPriceFloor::set(&U256::from(2));
+		assert_err!(
+			call.validate_self_contained(&source, &dispatch_info, 0)
+				.unwrap(),
+			InvalidTransaction::Payment
+		);
+		assert_err!(
+			extrinsic.apply::<Test>(&dispatch_info, 0),
+			TransactionValidityError::Invalid(InvalidTransaction::Payment)
+		);
+	});
+}
+
 #[test]
 fn transaction_with_to_low_nonce_should_not_work() {
 	let (pairs, mut ext) = new_test_ext(1);
@@ -168,6 +204,38 @@ fn transaction_with_to_hight_nonce_should_fail_in_block() {
 	});
 }
 
+#[test]
+fn transaction_with_duplicate_hash_should_fail_in_block() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+	let substrate_alice =
+		<Test as pallet_evm::Config>::AddressMapping::into_account_id(alice.address);
+
+	ext.execute_with(|| {
+		let call = crate::Call::<Test>::transact {
+			transaction: legacy_erc20_creation_transaction(alice),
+		};
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<_, _, SignedExtra, _> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Ethereum(call),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+		assert_ok!(extrinsic.clone().apply::<Test>(&dispatch_info, 0));
+
+		// Bypass the nonce check, which would otherwise reject the same transaction.
+		frame_system::Account::<Test>::mutate(&substrate_alice, |account| account.nonce = 0);
+		assert_err!(
+			extrinsic.apply::<Test>(&dispatch_info, 0),
+			TransactionValidityError::Invalid(InvalidTransaction::BadProof)
+		);
+
+		// The hashes are only tracked for the block being built.
+		Ethereum::on_finalize(1);
+		assert_eq!(crate::PendingTransactionHashes::<Test>::iter().count(), 0);
+	});
+}
+
 #[test]
 fn transaction_with_invalid_chain_id_should_fail_in_block() {
 	let (pairs, mut ext) = new_test_ext(1);
@@ -32,6 +32,8 @@ use fp_self_contained::CheckedExtrinsic;
 mod eip1559;
 mod eip2930;
 mod legacy;
+mod roots;
+mod withdrawals;
 
 // This ERC-20 contract mints the maximum amount of tokens to the contract creator.
 // pragma solidity ^0.5.0;`
@@ -0,0 +1,346 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Block header trie root tests.
+//!
+//! The expected roots were computed with an independent Merkle-Patricia trie implementation
+//! and can be cross-checked against any Ethereum client.
+
+use super::*;
+use ethereum_types::{Bloom, BloomInput};
+use frame_support::traits::Hooks;
+
+use crate::{CurrentBlock, Pending, Receipt, TransactionStatus};
+
+/// Root of the empty trie, `keccak256(rlp(""))`.
+const EMPTY_TRIE_ROOT: &str = "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421";
+
+fn transfer_transaction(from: &AccountInfo, to: H160, nonce: u64) -> Transaction {
+	LegacyUnsignedTransaction {
+		nonce: U256::from(nonce),
+		gas_price: U256::from(1),
+		gas_limit: U256::from(21_000),
+		action: ethereum::TransactionAction::Call(to),
+		value: U256::from(1),
+		input: Vec::new(),
+	}
+	.sign(&from.private_key)
+}
+
+fn receipt_data(
+	status_code: u8,
+	used_gas: u64,
+	logs: Vec<ethereum::Log>,
+) -> ethereum::EIP658ReceiptData {
+	let mut logs_bloom = Bloom::default();
+	for log in &logs {
+		logs_bloom.accrue(BloomInput::Raw(&log.address[..]));
+		for topic in &log.topics {
+			logs_bloom.accrue(BloomInput::Raw(&topic[..]));
+		}
+	}
+	ethereum::EIP658ReceiptData {
+		status_code,
+		used_gas: U256::from(used_gas),
+		logs_bloom,
+		logs,
+	}
+}
+
+/// Finalizes the current block with the given pending entries and returns the stored block.
+fn finalize_block_with(
+	pending: Vec<(Transaction, TransactionStatus, Receipt)>,
+) -> ethereum::BlockV2 {
+	Pending::<Test>::put(pending);
+	Ethereum::on_finalize(System::block_number());
+	CurrentBlock::<Test>::get().expect("block is stored on finalize")
+}
+
+#[test]
+fn receipts_root_of_empty_block_is_empty_trie_root() {
+	let (_, mut ext) = new_test_ext(0);
+
+	ext.execute_with(|| {
+		let block = finalize_block_with(vec![]);
+		assert_eq!(
+			block.header.receipts_root,
+			H256::from_str(EMPTY_TRIE_ROOT).unwrap()
+		);
+	});
+}
+
+#[test]
+fn receipts_root_of_single_legacy_receipt_works() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		let block = finalize_block_with(vec![(
+			transfer_transaction(alice, bob.address, 0),
+			TransactionStatus::default(),
+			Receipt::Legacy(receipt_data(1, 21_000, vec![])),
+		)]);
+		// A single successful plain transfer, as found in many mainnet blocks.
+		assert_eq!(
+			block.header.receipts_root,
+			H256::from_str("056b23fbba480696b65fe5a59b8f2148a1299103c4f57df839233af2cf4ca2d2")
+				.unwrap()
+		);
+	});
+}
+
+#[test]
+fn receipts_root_of_single_typed_receipt_is_enveloped() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		let log = ethereum::Log {
+			address: H160::repeat_byte(0x11),
+			topics: vec![H256::repeat_byte(0x22)],
+			data: vec![0x01, 0x02],
+		};
+		let block = finalize_block_with(vec![(
+			transfer_transaction(alice, bob.address, 0),
+			TransactionStatus::default(),
+			Receipt::EIP2930(receipt_data(1, 50_000, vec![log])),
+		)]);
+		// The trie leaf must be `0x01 || rlp(receipt)`. The bare RLP payload would yield
+		// `0x07ce151853e06f9221bd6eac195e65c3e4e2bb0113d3f2e6e76c39cd5ae8ed4d` instead.
+		assert_eq!(
+			block.header.receipts_root,
+			H256::from_str("bc44f5008954880457dc5288e8c34ccdf1bd11c173535be885447355752668cb")
+				.unwrap()
+		);
+	});
+}
+
+#[test]
+fn receipts_root_of_mixed_receipts_works() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		let log = ethereum::Log {
+			address: H160::repeat_byte(0x11),
+			topics: vec![H256::repeat_byte(0x22)],
+			data: vec![0x01, 0x02],
+		};
+		let block = finalize_block_with(vec![
+			(
+				transfer_transaction(alice, bob.address, 0),
+				TransactionStatus::default(),
+				Receipt::Legacy(receipt_data(1, 21_000, vec![])),
+			),
+			(
+				transfer_transaction(alice, bob.address, 1),
+				TransactionStatus::default(),
+				Receipt::EIP2930(receipt_data(1, 50_000, vec![log])),
+			),
+			(
+				transfer_transaction(alice, bob.address, 2),
+				TransactionStatus::default(),
+				Receipt::EIP1559(receipt_data(0, 71_000, vec![])),
+			),
+		]);
+		assert_eq!(
+			block.header.receipts_root,
+			H256::from_str("b7cb21f472c4b992cd92a7b4f92e01d4e023d58a607efce7c96eedb1dcb3c974")
+				.unwrap()
+		);
+	});
+}
+
+#[test]
+fn receipts_root_of_executed_transfer_works() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		assert_ok!(Ethereum::transact(
+			RawOrigin::EthereumTransaction(alice.address).into(),
+			transfer_transaction(alice, bob.address, 0),
+		));
+		Ethereum::on_finalize(System::block_number());
+
+		let block = CurrentBlock::<Test>::get().expect("block is stored on finalize");
+		assert_eq!(
+			block.header.receipts_root,
+			H256::from_str("056b23fbba480696b65fe5a59b8f2148a1299103c4f57df839233af2cf4ca2d2")
+				.unwrap()
+		);
+	});
+}
+
+/// The signed example transaction from the EIP-155 specification.
+fn eip155_example_transaction() -> Transaction {
+	Transaction::Legacy(ethereum::LegacyTransaction {
+		nonce: U256::from(9),
+		gas_price: U256::from(20_000_000_000u64),
+		gas_limit: U256::from(21_000),
+		action: ethereum::TransactionAction::Call(H160::repeat_byte(0x35)),
+		value: U256::from(1_000_000_000_000_000_000u64),
+		input: Vec::new(),
+		signature: ethereum::TransactionSignature::new(
+			37,
+			H256::from_str("28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276")
+				.unwrap(),
+			H256::from_str("67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83")
+				.unwrap(),
+		)
+		.unwrap(),
+	})
+}
+
+fn eip2930_fixed_transaction() -> Transaction {
+	Transaction::EIP2930(ethereum::EIP2930Transaction {
+		chain_id: 42,
+		nonce: U256::from(1),
+		gas_price: U256::from(1_000_000_000),
+		gas_limit: U256::from(30_000),
+		action: ethereum::TransactionAction::Call(H160::repeat_byte(0x35)),
+		value: U256::from(1),
+		input: Vec::new(),
+		access_list: vec![ethereum::AccessListItem {
+			address: H160::repeat_byte(0x11),
+			storage_keys: vec![H256::zero()],
+		}],
+		odd_y_parity: false,
+		r: H256::repeat_byte(0x01),
+		s: H256::repeat_byte(0x02),
+	})
+}
+
+fn eip1559_fixed_transaction() -> Transaction {
+	Transaction::EIP1559(ethereum::EIP1559Transaction {
+		chain_id: 42,
+		nonce: U256::from(2),
+		max_priority_fee_per_gas: U256::from(1),
+		max_fee_per_gas: U256::from(1_000_000_000),
+		gas_limit: U256::from(53_000),
+		action: ethereum::TransactionAction::Create,
+		value: U256::zero(),
+		input: vec![0x60, 0x00],
+		access_list: vec![],
+		odd_y_parity: true,
+		r: H256::repeat_byte(0x03),
+		s: H256::repeat_byte(0x04),
+	})
+}
+
+fn pending_entry(transaction: Transaction) -> (Transaction, TransactionStatus, Receipt) {
+	(
+		transaction,
+		TransactionStatus::default(),
+		Receipt::Legacy(receipt_data(1, 21_000, vec![])),
+	)
+}
+
+#[test]
+fn transaction_hashes_follow_eip2718_envelopes() {
+	assert_eq!(
+		eip155_example_transaction().hash(),
+		H256::from_str("33469b22e9f636356c4160a87eb19df52b7412e8eac32a4a55ffe88ea8350788").unwrap()
+	);
+	assert_eq!(
+		eip2930_fixed_transaction().hash(),
+		H256::from_str("4b7aa14166c3ec434f4c3dcecc93ef5db16948e3ead916fcdcb3afc7bc77f052").unwrap()
+	);
+	assert_eq!(
+		eip1559_fixed_transaction().hash(),
+		H256::from_str("819f71904a1ac72b14935b821dcf7b8e1be1bde6d67f572c08ca345b61cc8fcc").unwrap()
+	);
+}
+
+#[test]
+fn transactions_root_of_empty_block_is_empty_trie_root() {
+	let (_, mut ext) = new_test_ext(0);
+
+	ext.execute_with(|| {
+		let block = finalize_block_with(vec![]);
+		assert_eq!(
+			block.header.transactions_root,
+			H256::from_str(EMPTY_TRIE_ROOT).unwrap()
+		);
+	});
+}
+
+#[test]
+fn transactions_root_of_legacy_transaction_is_bare_rlp() {
+	let (_, mut ext) = new_test_ext(0);
+
+	ext.execute_with(|| {
+		let block = finalize_block_with(vec![pending_entry(eip155_example_transaction())]);
+		assert_eq!(
+			block.header.transactions_root,
+			H256::from_str("36cf58bec935fe50593ac7443cb728dd37dedac603d60fddfae59fd3bdbfcd7f")
+				.unwrap()
+		);
+	});
+}
+
+#[test]
+fn transactions_root_of_eip2930_transaction_is_enveloped() {
+	let (_, mut ext) = new_test_ext(0);
+
+	ext.execute_with(|| {
+		let block = finalize_block_with(vec![pending_entry(eip2930_fixed_transaction())]);
+		// The trie leaf must be `0x01 || rlp(transaction)`. The bare RLP payload would yield
+		// `0xd4029953865fc0ed655109dae9c744bf1f09824526aa422dad2f68419ace0807` instead.
+		assert_eq!(
+			block.header.transactions_root,
+			H256::from_str("402823da1a9c678682327bcf58ed7e6c37dd260ec1fecd2fb0e311d57bbdda71")
+				.unwrap()
+		);
+	});
+}
+
+#[test]
+fn transactions_root_of_eip1559_transaction_is_enveloped() {
+	let (_, mut ext) = new_test_ext(0);
+
+	ext.execute_with(|| {
+		let block = finalize_block_with(vec![pending_entry(eip1559_fixed_transaction())]);
+		assert_eq!(
+			block.header.transactions_root,
+			H256::from_str("9575bdd5b5ad203dd7055a52fa667aebf5b40405d0b452765cd27126dd871d85")
+				.unwrap()
+		);
+	});
+}
+
+#[test]
+fn transactions_root_of_mixed_transactions_works() {
+	let (_, mut ext) = new_test_ext(0);
+
+	ext.execute_with(|| {
+		let block = finalize_block_with(vec![
+			pending_entry(eip155_example_transaction()),
+			pending_entry(eip2930_fixed_transaction()),
+			pending_entry(eip1559_fixed_transaction()),
+		]);
+		assert_eq!(
+			block.header.transactions_root,
+			H256::from_str("6166041b0aaa2578cb67271b2c37772fd38df5f77b6d37e59ade2cb5d0d8cc15")
+				.unwrap()
+		);
+	});
+}
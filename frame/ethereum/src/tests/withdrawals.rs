@@ -0,0 +1,59 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! EIP-4895 withdrawal processing tests.
+
+use super::*;
+use frame_support::traits::Hooks;
+use pallet_evm::AddressMapping;
+
+use crate::{CurrentWithdrawals, Withdrawal};
+
+#[test]
+fn withdrawals_are_credited_on_initialize() {
+	let (pairs, mut ext) = new_test_ext_with_initial_balance(1, 1_000);
+	let alice = &pairs[0];
+	let substrate_alice =
+		<Test as pallet_evm::Config>::AddressMapping::into_account_id(alice.address);
+
+	ext.execute_with(|| {
+		let withdrawals = vec![
+			Withdrawal {
+				index: 0,
+				validator_index: 7,
+				address: alice.address,
+				amount: 100,
+			},
+			Withdrawal {
+				index: 1,
+				validator_index: 8,
+				address: alice.address,
+				amount: 20,
+			},
+		];
+		Withdrawals::set(withdrawals.clone());
+		Ethereum::on_initialize(1);
+		assert_eq!(Balances::free_balance(&substrate_alice), 1_000 + 120 * GWEI);
+		assert_eq!(CurrentWithdrawals::<Test>::get(), Some(withdrawals));
+
+		// Blocks without withdrawals do not keep the previous ones.
+		Withdrawals::set(Vec::new());
+		Ethereum::on_initialize(2);
+		assert_eq!(Balances::free_balance(&substrate_alice), 1_000 + 120 * GWEI);
+		assert_eq!(CurrentWithdrawals::<Test>::get(), None);
+	});
+}
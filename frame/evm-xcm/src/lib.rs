@@ -0,0 +1,195 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! EVM calls of XCM `Transact` instructions.
+//!
+//! The `transact` call is to be dispatched by a `Transact` with the `Xcm` origin kind: the caller
+//! is the address of the origin location of the message. Its result is reported to the sender
+//! by the `ReportTransactStatus` instruction, a failed call being an error, and the weight of
+//! the used gas is refunded.
+//!
+//! The call is paid by the weight bought by the message, e.g. with `BuyExecution` and the fee
+//! asset, so the EVM charges no gas fee to the caller.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use alloc::vec::Vec;
+use scale_codec::Encode;
+// Substrate
+use frame_support::dispatch::{DispatchErrorWithPostInfo, Pays, PostDispatchInfo};
+use sp_core::{hashing::blake2_256, H160, U256};
+use sp_runtime::traits::UniqueSaturatedInto;
+// XCM
+use xcm::latest::{Junction, Location};
+// Frontier
+use fp_evm::ExitReason;
+use pallet_evm::{GasWeightMapping, Runner};
+
+pub use self::pallet::*;
+
+/// Address calling the EVM for the origin location of an XCM message.
+pub trait LocationToH160 {
+	fn convert_location(location: &Location) -> Option<H160>;
+}
+
+/// Address hashed from the location, so that no remote location can call as a local account.
+pub struct HashedLocationToH160;
+
+impl LocationToH160 for HashedLocationToH160 {
+	fn convert_location(location: &Location) -> Option<H160> {
+		let hash = blake2_256(&(b"xcm-evm:", location).encode());
+		Some(H160::from_slice(&hash[0..20]))
+	}
+}
+
+/// Address of the `AccountKey20` junction ending the location.
+///
+/// Only to be used for locations controlled by this chain, as a remote chain would otherwise be
+/// able to call as any local account.
+pub struct AccountKey20ToH160;
+
+impl LocationToH160 for AccountKey20ToH160 {
+	fn convert_location(location: &Location) -> Option<H160> {
+		match location.last() {
+			Some(Junction::AccountKey20 { key, .. }) => Some(H160::from(*key)),
+			_ => None,
+		}
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_evm::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Origin of the `Transact` instructions, e.g. `pallet_xcm::EnsureXcm<Everything>`.
+		type XcmOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Location>;
+
+		/// Address calling the EVM for an origin location.
+		type LocationToH160: LocationToH160;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The EVM call of an XCM message succeeded.
+		Executed {
+			location: Location,
+			source: H160,
+			target: H160,
+			used_gas: U256,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The origin location has no address.
+		UnmappedLocation,
+		/// The EVM call reverted or failed.
+		CallFailed,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Call `target` from the address of the origin location.
+		#[pallet::call_index(0)]
+		#[pallet::weight({
+			let without_base_extrinsic_weight = true;
+			<T as pallet_evm::Config>::GasWeightMapping::gas_to_weight(
+				*gas_limit,
+				without_base_extrinsic_weight,
+			)
+		})]
+		pub fn transact(
+			origin: OriginFor<T>,
+			target: H160,
+			input: Vec<u8>,
+			value: U256,
+			gas_limit: u64,
+		) -> DispatchResultWithPostInfo {
+			let location = T::XcmOrigin::ensure_origin(origin)?;
+			let source = T::LocationToH160::convert_location(&location)
+				.ok_or(Error::<T>::UnmappedLocation)?;
+
+			let is_transactional = false;
+			let validate = true;
+			let info = <T as pallet_evm::Config>::Runner::call(
+				source,
+				target,
+				input,
+				value,
+				gas_limit,
+				None,
+				None,
+				None,
+				Vec::new(),
+				is_transactional,
+				validate,
+				None,
+				None,
+				<T as pallet_evm::Config>::config(),
+			)
+			.map_err(|e| DispatchErrorWithPostInfo {
+				post_info: PostDispatchInfo {
+					actual_weight: Some(e.weight),
+					pays_fee: Pays::Yes,
+				},
+				error: e.error.into(),
+			})?;
+
+			let post_info = PostDispatchInfo {
+				actual_weight: Some(<T as pallet_evm::Config>::GasWeightMapping::gas_to_weight(
+					info.used_gas.standard.unique_saturated_into(),
+					true,
+				)),
+				pays_fee: Pays::Yes,
+			};
+			match info.exit_reason {
+				ExitReason::Succeed(_) => {
+					Self::deposit_event(Event::Executed {
+						location,
+						source,
+						target,
+						used_gas: info.used_gas.standard,
+					});
+					Ok(post_info)
+				}
+				_ => Err(DispatchErrorWithPostInfo {
+					post_info,
+					error: Error::<T>::CallFailed.into(),
+				}),
+			}
+		}
+	}
+}
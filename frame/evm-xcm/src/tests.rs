@@ -0,0 +1,138 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::{assert_noop, assert_ok, traits::Currency};
+use sp_runtime::DispatchError;
+
+use super::*;
+use crate::mock::*;
+
+// Stores the call value at slot 0.
+const STORE_VALUE: &[u8] = &[0x34, 0x60, 0x00, 0x55, 0x00];
+// PUSH1 0, PUSH1 0, REVERT
+const REVERT: &[u8] = &[0x60, 0x00, 0x60, 0x00, 0xfd];
+
+fn deploy(code: &[u8]) -> H160 {
+	let address = H160::repeat_byte(0xcc);
+	pallet_evm::Pallet::<Test>::create_account(address, code.to_vec());
+	address
+}
+
+fn caller(account: H160) -> H160 {
+	HashedLocationToH160::convert_location(&sibling(account)).unwrap()
+}
+
+#[test]
+fn transact_calls_from_the_origin_location() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let account = H160::from_low_u64_be(1000);
+		let source = caller(account);
+		let _ = Balances::deposit_creating(&source, 1_000);
+		let target = deploy(STORE_VALUE);
+
+		assert_ok!(EvmXcm::transact(
+			RuntimeOrigin::signed(account),
+			target,
+			Vec::new(),
+			U256::from(100),
+			1_000_000,
+		));
+
+		assert_eq!(Balances::free_balance(source), 900);
+		assert_eq!(
+			pallet_evm::AccountStorages::<Test>::get(target, H256::zero()),
+			H256::from_low_u64_be(100)
+		);
+		let used_gas = match System::events().last().map(|record| &record.event) {
+			Some(RuntimeEvent::EvmXcm(Event::Executed {
+				location,
+				source: event_source,
+				target: event_target,
+				used_gas,
+			})) => {
+				assert_eq!(location, &sibling(account));
+				assert_eq!((*event_source, *event_target), (source, target));
+				*used_gas
+			}
+			event => panic!("Unexpected event {event:?}"),
+		};
+		assert!(used_gas > U256::zero());
+	});
+}
+
+#[test]
+fn transact_fails_with_the_evm_call() {
+	new_test_ext().execute_with(|| {
+		let target = deploy(REVERT);
+
+		let error = EvmXcm::transact(
+			RuntimeOrigin::signed(H160::from_low_u64_be(1000)),
+			target,
+			Vec::new(),
+			U256::zero(),
+			1_000_000,
+		)
+		.unwrap_err();
+
+		assert_eq!(error.error, Error::<Test>::CallFailed.into());
+		// Only the used gas is paid.
+		assert!(
+			error.post_info.actual_weight.unwrap()
+				< <Test as pallet_evm::Config>::GasWeightMapping::gas_to_weight(1_000_000, true)
+		);
+	});
+}
+
+#[test]
+fn transact_requires_an_xcm_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EvmXcm::transact(
+				RuntimeOrigin::root(),
+				H160::repeat_byte(0xcc),
+				Vec::new(),
+				U256::zero(),
+				1_000_000,
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn hashed_location_address_differs_from_the_account_key() {
+	let key = [0xaa; 20];
+	let location = Location::new(1, [Junction::AccountKey20 { network: None, key }]);
+
+	assert_eq!(
+		AccountKey20ToH160::convert_location(&location),
+		Some(H160::from(key))
+	);
+	assert_ne!(
+		HashedLocationToH160::convert_location(&location),
+		Some(H160::from(key))
+	);
+	assert_ne!(
+		HashedLocationToH160::convert_location(&location),
+		HashedLocationToH160::convert_location(&Location::parent())
+	);
+	assert_eq!(
+		AccountKey20ToH160::convert_location(&Location::parent()),
+		None
+	);
+}
@@ -115,11 +115,28 @@ impl Precompile for Blake2F {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use pallet_evm_test_vector_support::test_precompile_test_vectors;
+	use pallet_evm_test_vector_support::{precompile_gas_used, test_precompile_test_vectors};
+	use proptest::prelude::*;
 
 	#[test]
 	fn process_consensus_tests() -> Result<(), String> {
 		test_precompile_test_vectors::<Blake2F>("../testdata/blake2F.json")?;
 		Ok(())
 	}
+
+	proptest! {
+		#[test]
+		fn cost_scales_with_rounds(rounds in 0u32..=100_000, f in 0u8..=1) {
+			let mut input = rounds.to_be_bytes().to_vec();
+			input.resize(212, 0);
+			input.push(f);
+			prop_assert_eq!(precompile_gas_used::<Blake2F>(input), rounds as u64);
+		}
+
+		#[test]
+		fn malformed_input_costs_nothing(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+			prop_assume!(input.len() != 213);
+			prop_assert_eq!(precompile_gas_used::<Blake2F>(input), 0);
+		}
+	}
 }
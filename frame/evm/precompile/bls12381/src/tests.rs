@@ -17,8 +17,9 @@
 
 use super::*;
 use pallet_evm_test_vector_support::{
-	test_precompile_failure_test_vectors, test_precompile_test_vectors,
+	precompile_gas_used, test_precompile_failure_test_vectors, test_precompile_test_vectors,
 };
+use proptest::prelude::*;
 
 #[test]
 fn process_consensus_tests() -> Result<(), String> {
@@ -53,3 +54,55 @@ fn process_consensus_failure_tests() -> Result<(), String> {
 	test_precompile_failure_test_vectors::<Bls12381MapG2>("../testdata/fail-bls12381MapG2.json")?;
 	Ok(())
 }
+
+fn multiexp_cost(pairs: usize, mul_cost: u64) -> u64 {
+	if pairs == 0 {
+		return 0;
+	}
+	let discount =
+		BLS12381_MULTIEXP_DISCOUNT_TABLE[pairs.min(BLS12381_MULTIEXP_DISCOUNT_TABLE.len()) - 1];
+	pairs as u64 * mul_cost * discount as u64 / 1_000
+}
+
+proptest! {
+	#[test]
+	fn constant_costs(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+		prop_assert_eq!(precompile_gas_used::<Bls12381G1Add>(input.clone()), 600);
+		prop_assert_eq!(precompile_gas_used::<Bls12381G1Mul>(input.clone()), 12_000);
+		prop_assert_eq!(precompile_gas_used::<Bls12381G2Add>(input.clone()), 4_500);
+		prop_assert_eq!(precompile_gas_used::<Bls12381G2Mul>(input.clone()), 55_000);
+		prop_assert_eq!(precompile_gas_used::<Bls12381MapG1>(input.clone()), 5_500);
+		prop_assert_eq!(precompile_gas_used::<Bls12381MapG2>(input), 110_000);
+	}
+
+	// Random points are almost always rejected once the cost is recorded, which keeps large
+	// inputs cheap to check.
+	#[test]
+	fn g1_multiexp_cost_scales_with_pairs(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+		let pairs = input.len() / 160;
+		prop_assert_eq!(precompile_gas_used::<Bls12381G1MultiExp>(input), multiexp_cost(pairs, 12_000));
+	}
+
+	#[test]
+	fn g2_multiexp_cost_scales_with_pairs(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+		let pairs = input.len() / 288;
+		prop_assert_eq!(precompile_gas_used::<Bls12381G2MultiExp>(input), multiexp_cost(pairs, 55_000));
+	}
+
+	#[test]
+	fn pairing_cost_scales_with_pairs(
+		input in (1usize..=170).prop_flat_map(|pairs| prop::collection::vec(any::<u8>(), pairs * 384))
+	) {
+		let pairs = input.len() as u64 / 384;
+		prop_assert_eq!(precompile_gas_used::<Bls12381Pairing>(input), 115_000 + 23_000 * pairs);
+	}
+
+	// Inputs of no whole number of pairs are rejected before any cost is recorded.
+	#[test]
+	fn pairing_of_partial_pairs_costs_nothing(
+		input in prop::collection::vec(any::<u8>(), 0..=65_536)
+			.prop_filter("whole pairs", |input| input.len() % 384 != 0)
+	) {
+		prop_assert_eq!(precompile_gas_used::<Bls12381Pairing>(input), 0);
+	}
+}
@@ -295,7 +295,8 @@ impl Precompile for Bn128Pairing {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use pallet_evm_test_vector_support::test_precompile_test_vectors;
+	use pallet_evm_test_vector_support::{precompile_gas_used, test_precompile_test_vectors};
+	use proptest::prelude::*;
 
 	#[test]
 	fn process_consensus_tests_for_add() -> Result<(), String> {
@@ -314,4 +315,26 @@ mod tests {
 		test_precompile_test_vectors::<Bn128Pairing>("../testdata/common_bnpair.json")?;
 		Ok(())
 	}
+
+	proptest! {
+		#[test]
+		fn add_cost_is_constant(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+			prop_assert_eq!(precompile_gas_used::<Bn128Add>(input), 150);
+		}
+
+		#[test]
+		fn mul_cost_is_constant(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+			prop_assert_eq!(precompile_gas_used::<Bn128Mul>(input), 6_000);
+		}
+
+		// Random points are almost always rejected once the cost is recorded, which keeps large
+		// pairing counts cheap to check.
+		#[test]
+		fn pairing_cost_scales_with_pairs(
+			input in (0usize..=341).prop_flat_map(|pairs| prop::collection::vec(any::<u8>(), pairs * 192))
+		) {
+			let pairs = input.len() as u64 / 192;
+			prop_assert_eq!(precompile_gas_used::<Bn128Pairing>(input), 45_000 + 34_000 * pairs);
+		}
+	}
 }
@@ -111,6 +111,8 @@ impl LinearCostPrecompile for Curve25519ScalarMul {
 mod tests {
 	use super::*;
 	use curve25519_dalek::constants;
+	use pallet_evm_test_vector_support::precompile_gas_used;
+	use proptest::prelude::*;
 
 	#[test]
 	fn test_sum() -> Result<(), PrecompileFailure> {
@@ -265,4 +267,22 @@ mod tests {
 			}
 		}
 	}
+
+	fn linear_cost(base: u64, word: u64, len: usize) -> u64 {
+		base + word * (len as u64).div_ceil(32)
+	}
+
+	proptest! {
+		#[test]
+		fn add_cost_scales_with_input(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+			let len = input.len();
+			prop_assert_eq!(precompile_gas_used::<Curve25519Add>(input), linear_cost(60, 12, len));
+		}
+
+		#[test]
+		fn scalar_mul_cost_scales_with_input(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+			let len = input.len();
+			prop_assert_eq!(precompile_gas_used::<Curve25519ScalarMul>(input), linear_cost(60, 12, len));
+		}
+	}
 }
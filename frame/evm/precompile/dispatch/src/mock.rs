@@ -153,13 +153,17 @@ impl pallet_evm::Config for Test {
 	type PrecompilesValue = ();
 	type ChainId = ();
 	type BlockGasLimit = BlockGasLimit;
+	type GasPriceFloor = ();
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
 	type OnChargeTransaction = ();
 	type OnCreate = ();
+	type OnAfterEvmExecution = ();
+	type GasLimitOverrides = ();
 	type FindAuthor = FindAuthorTruncated;
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
 	type GasLimitPovSizeRatio = ();
 	type Timestamp = Timestamp;
+	type EvmConfigSchedule = ();
 	type WeightInfo = ();
 }
 
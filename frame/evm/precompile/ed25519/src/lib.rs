@@ -66,6 +66,8 @@ impl LinearCostPrecompile for Ed25519Verify {
 mod tests {
 	use super::*;
 	use ed25519_dalek::{Signer, SigningKey};
+	use pallet_evm_test_vector_support::precompile_gas_used;
+	use proptest::prelude::*;
 
 	#[test]
 	fn test_empty_input() -> Result<(), PrecompileFailure> {
@@ -152,4 +154,12 @@ mod tests {
 
 		Ok(())
 	}
+
+	proptest! {
+		#[test]
+		fn verify_cost_scales_with_input(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+			let words = (input.len() as u64).div_ceil(32);
+			prop_assert_eq!(precompile_gas_used::<Ed25519Verify>(input), 15 + 3 * words);
+		}
+	}
 }
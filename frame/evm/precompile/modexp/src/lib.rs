@@ -247,7 +247,10 @@ mod tests {
 	use super::*;
 	extern crate hex;
 	use fp_evm::Context;
-	use pallet_evm_test_vector_support::{test_precompile_test_vectors, MockHandle};
+	use pallet_evm_test_vector_support::{
+		precompile_gas_used, test_precompile_test_vectors, MockHandle,
+	};
+	use proptest::prelude::*;
 
 	#[test]
 	fn process_consensus_tests() -> Result<(), String> {
@@ -521,4 +524,77 @@ mod tests {
 
 		assert_eq!(handle.gas_used, 7104 * 20); // gas used when ran in geth (x20)
 	}
+
+	fn bit_length(bytes: &[u8]) -> u64 {
+		match bytes.iter().position(|byte| *byte != 0) {
+			Some(first) => {
+				(bytes.len() - first - 1) as u64 * 8 + 8 - bytes[first].leading_zeros() as u64
+			}
+			None => 0,
+		}
+	}
+
+	// EIP-2565, the cost being 20 times higher for an even modulus.
+	fn eip_2565_cost(base_len: usize, exponent: &[u8], modulus: &[u8]) -> u64 {
+		let words = (base_len.max(modulus.len()) as u64).div_ceil(8);
+		let iterations = if exponent.len() <= 32 {
+			bit_length(exponent).saturating_sub(1)
+		} else {
+			8 * (exponent.len() as u64 - 32) + bit_length(&exponent[..32]) - 1
+		};
+		let cost = (words * words * iterations.max(1) / 3).max(200);
+		if modulus.last().map_or(true, |byte| byte % 2 == 0) {
+			cost * 20
+		} else {
+			cost
+		}
+	}
+
+	fn modexp_input(base: &[u8], exponent: &[u8], modulus: &[u8]) -> Vec<u8> {
+		let mut input = Vec::new();
+		for len in [base.len(), exponent.len(), modulus.len()] {
+			input.extend_from_slice(&[0; 24]);
+			input.extend_from_slice(&(len as u64).to_be_bytes());
+		}
+		input.extend_from_slice(base);
+		input.extend_from_slice(exponent);
+		input.extend_from_slice(modulus);
+		input
+	}
+
+	proptest! {
+		#[test]
+		fn cost_follows_eip_2565(
+			base in prop::collection::vec(any::<u8>(), 0..=64),
+			exponent in prop::collection::vec(any::<u8>(), 0..=64),
+			modulus in prop::collection::vec(any::<u8>(), 1..=64),
+		) {
+			let input = modexp_input(&base, &exponent, &modulus);
+			let cost = eip_2565_cost(base.len(), &exponent, &modulus);
+			prop_assert_eq!(precompile_gas_used::<Modexp>(input), cost);
+		}
+
+		// A modulus of zero or one skips the exponentiation, which keeps the longest inputs cheap
+		// to check.
+		#[test]
+		fn cost_follows_eip_2565_up_to_the_maximum_lengths(
+			base in prop::collection::vec(any::<u8>(), 0..=1024),
+			exponent in prop::collection::vec(any::<u8>(), 0..=1024),
+			(mod_len, mod_value) in (1usize..=1024, 0u8..=1),
+		) {
+			let mut modulus = vec![0; mod_len];
+			modulus[mod_len - 1] = mod_value;
+			let input = modexp_input(&base, &exponent, &modulus);
+			let cost = eip_2565_cost(base.len(), &exponent, &modulus);
+			prop_assert_eq!(precompile_gas_used::<Modexp>(input), cost);
+		}
+
+		#[test]
+		fn empty_modulus_costs_nothing(
+			base in prop::collection::vec(any::<u8>(), 0..=1024),
+			exponent in prop::collection::vec(any::<u8>(), 0..=1024),
+		) {
+			prop_assert_eq!(precompile_gas_used::<Modexp>(modexp_input(&base, &exponent, &[])), 0);
+		}
+	}
 }
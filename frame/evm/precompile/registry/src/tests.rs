@@ -0,0 +1,63 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::mock::{new_test_ext, PCall, Precompiles, PrecompilesValue, Runtime};
+use precompile_utils::testing::*;
+use sp_core::H160;
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+const REGISTRY: u64 = 0x900;
+
+#[test]
+fn selectors() {
+	assert!(PCall::is_precompile_selectors().contains(&0x446b450e));
+}
+
+#[test]
+fn registry_is_a_precompile() {
+	new_test_ext().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				H160::from_low_u64_be(REGISTRY),
+				PCall::is_precompile {
+					address: H160::from_low_u64_be(REGISTRY).into(),
+				},
+			)
+			.expect_no_logs()
+			.execute_returns(true);
+	})
+}
+
+#[test]
+fn accounts_are_not_precompiles() {
+	new_test_ext().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				H160::from_low_u64_be(REGISTRY),
+				PCall::is_precompile {
+					address: Bob.into(),
+				},
+			)
+			.expect_no_logs()
+			.execute_returns(false);
+	})
+}
@@ -56,9 +56,30 @@ impl LinearCostPrecompile for Sha3FIPS512 {
 	}
 }
 
+/// Keccak-256, as the `SHA3` opcode which it costs as much as.
+///
+/// Not a standard precompile, it must be set at a custom address.
+pub struct Keccak256;
+
+impl LinearCostPrecompile for Keccak256 {
+	const BASE: u64 = 30;
+	const WORD: u64 = 6;
+
+	fn execute(input: &[u8], _: u64) -> Result<(ExitSucceed, Vec<u8>), PrecompileFailure> {
+		use tiny_keccak::Hasher;
+		let mut output = [0; 32];
+		let mut keccak = tiny_keccak::Keccak::v256();
+		keccak.update(input);
+		keccak.finalize(&mut output);
+		Ok((ExitSucceed::Returned, output.to_vec()))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use pallet_evm_test_vector_support::precompile_gas_used;
+	use proptest::prelude::*;
 
 	#[test]
 	fn test_empty_input() -> Result<(), PrecompileFailure> {
@@ -145,4 +166,70 @@ mod tests {
 			}
 		}
 	}
+
+	#[test]
+	fn test_empty_input_keccak_256() -> Result<(), PrecompileFailure> {
+		let input: [u8; 0] = [];
+		let expected = b"\
+			\xc5\xd2\x46\x01\x86\xf7\x23\x3c\x92\x7e\x7d\xb2\xdc\xc7\x03\xc0\
+			\xe5\x00\xb6\x53\xca\x82\x27\x3b\x7b\xfa\xd8\x04\x5d\x85\xa4\x70\
+		";
+
+		let cost: u64 = 1;
+
+		match Keccak256::execute(&input, cost) {
+			Ok((_, out)) => {
+				assert_eq!(out, expected);
+				Ok(())
+			}
+			Err(e) => {
+				panic!("Test not expected to fail: {:?}", e);
+			}
+		}
+	}
+
+	#[test]
+	fn hello_keccak_256() -> Result<(), PrecompileFailure> {
+		let input = b"hello";
+		let expected = b"\
+			\x1c\x8a\xff\x95\x06\x85\xc2\xed\x4b\xc3\x17\x4f\x34\x72\x28\x7b\
+			\x56\xd9\x51\x7b\x9c\x94\x81\x27\x31\x9a\x09\xa7\xa3\x6d\xea\xc8\
+		";
+
+		let cost: u64 = 1;
+
+		match Keccak256::execute(input, cost) {
+			Ok((_, out)) => {
+				assert_eq!(out, expected);
+				Ok(())
+			}
+			Err(e) => {
+				panic!("Test not expected to fail: {:?}", e);
+			}
+		}
+	}
+
+	fn linear_cost(base: u64, word: u64, len: usize) -> u64 {
+		base + word * (len as u64).div_ceil(32)
+	}
+
+	proptest! {
+		#[test]
+		fn sha3_256_cost_scales_with_input(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+			let len = input.len();
+			prop_assert_eq!(precompile_gas_used::<Sha3FIPS256>(input), linear_cost(60, 12, len));
+		}
+
+		#[test]
+		fn sha3_512_cost_scales_with_input(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+			let len = input.len();
+			prop_assert_eq!(precompile_gas_used::<Sha3FIPS512>(input), linear_cost(60, 12, len));
+		}
+
+		#[test]
+		fn keccak_256_cost_scales_with_input(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+			let len = input.len();
+			prop_assert_eq!(precompile_gas_used::<Keccak256>(input), linear_cost(30, 6, len));
+		}
+	}
 }
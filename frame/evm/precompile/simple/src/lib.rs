@@ -134,10 +134,215 @@ impl LinearCostPrecompile for ECRecoverPublicKey {
 	}
 }
 
+/// Half of the secp256k1 curve order, the upper bound of non-malleable `s` values (EIP-2).
+const SECP256K1_HALF_ORDER: [u8; 32] = [
+	0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+	0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// The EIP-712 signature recovery precompile.
+///
+/// Takes the ABI-encoded `(bytes32 domainSeparator, bytes32 hashStruct, bytes signature)` tuple,
+/// the signature being the 65 bytes `r || s || v`, and returns the address that signed the
+/// `keccak256("\x19\x01" || domainSeparator || hashStruct)` digest.
+///
+/// Like ecrecover, an invalid signature returns empty output. Unlike the ECRecover precompile,
+/// which accepts any `s` below the curve order, malleable signatures with `s` in the upper half
+/// are considered invalid (EIP-2), as done by Solidity libraries such as OpenZeppelin's `ECDSA`.
+/// A signed message thus only ever has a single valid signature.
+pub struct EIP712Recover;
+
+impl LinearCostPrecompile for EIP712Recover {
+	const BASE: u64 = 3030;
+	const WORD: u64 = 6;
+
+	fn execute(input: &[u8], _: u64) -> Result<(ExitSucceed, Vec<u8>), PrecompileFailure> {
+		let invalid_input = || PrecompileFailure::Error {
+			exit_status: ExitError::Other("Invalid EIP-712 recover input".into()),
+		};
+		let word = |offset: usize| -> Result<&[u8], PrecompileFailure> {
+			input
+				.get(offset..offset.checked_add(32).ok_or_else(invalid_input)?)
+				.ok_or_else(invalid_input)
+		};
+		let small_int = |offset: usize| -> Result<usize, PrecompileFailure> {
+			let value = word(offset)?;
+			if value[..24] != [0u8; 24] {
+				return Err(invalid_input());
+			}
+			let mut bytes = [0u8; 8];
+			bytes.copy_from_slice(&value[24..]);
+			usize::try_from(u64::from_be_bytes(bytes)).map_err(|_| invalid_input())
+		};
+
+		let mut message = [0u8; 66];
+		message[0..2].copy_from_slice(&[0x19, 0x01]);
+		message[2..34].copy_from_slice(word(0)?);
+		message[34..66].copy_from_slice(word(32)?);
+
+		let signature_offset = small_int(64)?;
+		if small_int(signature_offset)? != 65 {
+			return Err(invalid_input());
+		}
+		let signature_start = signature_offset.checked_add(32).ok_or_else(invalid_input)?;
+		let signature = input
+			.get(signature_start..signature_start + 65)
+			.ok_or_else(invalid_input)?;
+
+		let v = signature[64];
+		if ![27, 28].contains(&v) || signature[32..64] > SECP256K1_HALF_ORDER[..] {
+			return Ok((ExitSucceed::Returned, [0u8; 0].to_vec()));
+		}
+		let mut sig = [0u8; 65];
+		sig[0..64].copy_from_slice(&signature[0..64]);
+		sig[64] = v - 27;
+
+		let digest = sp_io::hashing::keccak_256(&message);
+		let result = match sp_io::crypto::secp256k1_ecdsa_recover(&sig, &digest) {
+			Ok(pubkey) => {
+				let mut address = sp_io::hashing::keccak_256(&pubkey);
+				address[0..12].copy_from_slice(&[0u8; 12]);
+				address.to_vec()
+			}
+			Err(_) => [0u8; 0].to_vec(),
+		};
+
+		Ok((ExitSucceed::Returned, result))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use pallet_evm_test_vector_support::test_precompile_test_vectors;
+	use pallet_evm_test_vector_support::{precompile_gas_used, test_precompile_test_vectors};
+	use proptest::prelude::*;
+	use sp_core::{ecdsa, Pair};
+
+	/// ABI encoding of `(domainSeparator, hashStruct, signature)`.
+	fn eip712_input(
+		domain_separator: [u8; 32],
+		hash_struct: [u8; 32],
+		signature: &[u8],
+	) -> Vec<u8> {
+		let mut input = Vec::new();
+		input.extend(domain_separator);
+		input.extend(hash_struct);
+		let mut offset = [0u8; 32];
+		offset[31] = 0x60;
+		input.extend(offset);
+		let mut length = [0u8; 32];
+		length[31] = signature.len() as u8;
+		input.extend(length);
+		input.extend(signature);
+		input.resize(input.len() + (32 - signature.len() % 32) % 32, 0);
+		input
+	}
+
+	/// Signs the EIP-712 digest, returning the `r || s || v` signature with `v` in `{27, 28}`.
+	fn sign(pair: &ecdsa::Pair, domain_separator: [u8; 32], hash_struct: [u8; 32]) -> [u8; 65] {
+		let mut message = vec![0x19, 0x01];
+		message.extend(domain_separator);
+		message.extend(hash_struct);
+		let mut signature = [0u8; 65];
+		signature.copy_from_slice(
+			pair.sign_prehashed(&sp_io::hashing::keccak_256(&message))
+				.as_ref(),
+		);
+		signature[64] += 27;
+		signature
+	}
+
+	/// `n - s`, the malleable counterpart of `s`.
+	fn flip_s(s: &[u8]) -> [u8; 32] {
+		let mut order = [0u8; 32];
+		let mut carry = 0u16;
+		// n = 2 * half_order + 1
+		for i in (0..32).rev() {
+			let doubled = (SECP256K1_HALF_ORDER[i] as u16) * 2 + carry + (i == 31) as u16;
+			order[i] = doubled as u8;
+			carry = doubled >> 8;
+		}
+		let mut result = [0u8; 32];
+		let mut borrow = 0i16;
+		for i in (0..32).rev() {
+			let mut diff = order[i] as i16 - s[i] as i16 - borrow;
+			borrow = (diff < 0) as i16;
+			if diff < 0 {
+				diff += 256;
+			}
+			result[i] = diff as u8;
+		}
+		result
+	}
+
+	fn ecrecover(digest: [u8; 32], signature: &[u8; 65]) -> Vec<u8> {
+		let mut input = [0u8; 128];
+		input[0..32].copy_from_slice(&digest);
+		input[63] = signature[64];
+		input[64..128].copy_from_slice(&signature[0..64]);
+		ECRecover::execute(&input, 0).unwrap().1
+	}
+
+	#[test]
+	fn eip712_recover_matches_ecrecover() {
+		for seed in 0..64u8 {
+			let pair = ecdsa::Pair::from_seed(&[seed.wrapping_add(1); 32]);
+			let domain_separator = sp_io::hashing::keccak_256(&[seed, 1]);
+			let hash_struct = sp_io::hashing::keccak_256(&[seed, 2]);
+			let signature = sign(&pair, domain_separator, hash_struct);
+
+			let mut message = vec![0x19, 0x01];
+			message.extend(domain_separator);
+			message.extend(hash_struct);
+			let expected = ecrecover(sp_io::hashing::keccak_256(&message), &signature);
+
+			let (_, output) =
+				EIP712Recover::execute(&eip712_input(domain_separator, hash_struct, &signature), 0)
+					.unwrap();
+			assert_eq!(output.len(), 32);
+			assert_eq!(output, expected);
+		}
+	}
+
+	#[test]
+	fn eip712_recover_rejects_malleable_signatures() {
+		let pair = ecdsa::Pair::from_seed(&[7u8; 32]);
+		let domain_separator = [1u8; 32];
+		let hash_struct = [2u8; 32];
+		let signature = sign(&pair, domain_separator, hash_struct);
+
+		let mut malleable = signature;
+		malleable[32..64].copy_from_slice(&flip_s(&signature[32..64]));
+		malleable[64] = if signature[64] == 27 { 28 } else { 27 };
+
+		let (_, output) =
+			EIP712Recover::execute(&eip712_input(domain_separator, hash_struct, &malleable), 0)
+				.unwrap();
+		assert!(output.is_empty());
+
+		// ECRecover recovers the same signer from the malleable signature.
+		let mut message = vec![0x19, 0x01];
+		message.extend(domain_separator);
+		message.extend(hash_struct);
+		let digest = sp_io::hashing::keccak_256(&message);
+		assert_eq!(ecrecover(digest, &malleable), ecrecover(digest, &signature));
+
+		let mut invalid_v = signature;
+		invalid_v[64] = 1;
+		let (_, output) =
+			EIP712Recover::execute(&eip712_input(domain_separator, hash_struct, &invalid_v), 0)
+				.unwrap();
+		assert!(output.is_empty());
+	}
+
+	#[test]
+	fn eip712_recover_rejects_malformed_input() {
+		let signature = [0u8; 64];
+		assert!(
+			EIP712Recover::execute(&eip712_input([1u8; 32], [2u8; 32], &signature), 0).is_err()
+		);
+		assert!(EIP712Recover::execute(&[0u8; 64], 0).is_err());
+	}
 
 	// TODO: this fails on the test "InvalidHighV-bits-1" where it is expected to return ""
 	#[test]
@@ -157,4 +362,44 @@ mod tests {
 		test_precompile_test_vectors::<Ripemd160>("../testdata/common_ripemd.json")?;
 		Ok(())
 	}
+
+	fn linear_cost(base: u64, word: u64, len: usize) -> u64 {
+		base + word * (len as u64).div_ceil(32)
+	}
+
+	proptest! {
+		#[test]
+		fn identity_cost_scales_with_input(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+			let len = input.len();
+			prop_assert_eq!(precompile_gas_used::<Identity>(input), linear_cost(15, 3, len));
+		}
+
+		#[test]
+		fn ecrecover_cost_is_constant(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+			prop_assert_eq!(precompile_gas_used::<ECRecover>(input), 3000);
+		}
+
+		#[test]
+		fn ripemd160_cost_scales_with_input(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+			let len = input.len();
+			prop_assert_eq!(precompile_gas_used::<Ripemd160>(input), linear_cost(600, 120, len));
+		}
+
+		#[test]
+		fn sha256_cost_scales_with_input(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+			let len = input.len();
+			prop_assert_eq!(precompile_gas_used::<Sha256>(input), linear_cost(60, 12, len));
+		}
+
+		#[test]
+		fn ecrecover_public_key_cost_is_constant(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+			prop_assert_eq!(precompile_gas_used::<ECRecoverPublicKey>(input), 3000);
+		}
+
+		#[test]
+		fn eip712_recover_cost_scales_with_input(input in prop::collection::vec(any::<u8>(), 0..=65_536)) {
+			let len = input.len();
+			prop_assert_eq!(precompile_gas_used::<EIP712Recover>(input), linear_cost(3030, 6, len));
+		}
+	}
 }
@@ -138,11 +138,15 @@ impl pallet_evm::Config for Runtime {
 	type ChainId = ();
 	type OnChargeTransaction = ();
 	type BlockGasLimit = BlockGasLimit;
+	type GasPriceFloor = ();
 	type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
 	type FindAuthor = ();
 	type OnCreate = ();
+	type OnAfterEvmExecution = ();
+	type GasLimitOverrides = ();
 	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
 	type Timestamp = Timestamp;
+	type EvmConfigSchedule = ();
 	type WeightInfo = ();
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
 }
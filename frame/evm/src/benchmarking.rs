@@ -34,6 +34,34 @@ benchmarks! {
 		assert!(result.is_err());
 		assert_eq!(result.unwrap_err(), sp_runtime::DispatchError::BadOrigin);
 	}
+
+	set_precompile_activation {
+		let address = H160::from_low_u64_be(1);
+	}: _(RawOrigin::Root, address, false, 0u32.into())
+	verify {
+		assert!(PrecompileActivations::<T>::contains_key(address));
+	}
+
+	remove_precompile_activation {
+		let address = H160::from_low_u64_be(1);
+		PrecompileActivations::<T>::insert(address, PrecompileActivation {
+			enabled: false,
+			active_from: 0u32.into(),
+		});
+	}: _(RawOrigin::Root, address)
+	verify {
+		assert!(!PrecompileActivations::<T>::contains_key(address));
+	}
+
+	precompile_activation_lookup {
+		let address = H160::from_low_u64_be(1);
+		PrecompileActivations::<T>::insert(address, PrecompileActivation {
+			enabled: true,
+			active_from: 0u32.into(),
+		});
+	}: {
+		assert!(Pallet::<T>::is_precompile_active(address));
+	}
 }
 
 // impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::mock::Test);
@@ -96,7 +96,7 @@ use frame_system::RawOrigin;
 use sp_core::{H160, H256, U256};
 use sp_runtime::{
 	traits::{BadOrigin, NumberFor, Saturating, UniqueSaturatedInto, Zero},
-	AccountId32, DispatchErrorWithPostInfo,
+	AccountId32, ConsensusEngineId, DispatchErrorWithPostInfo,
 };
 // Frontier
 use fp_account::AccountId20;
@@ -156,6 +156,9 @@ pub mod pallet {
 		type ChainId: Get<u64>;
 		/// The block gas limit. Can be a simple constant, or an adjustment algorithm in another pallet.
 		type BlockGasLimit: Get<U256>;
+		/// Minimum gas price paid by the transactions of `pallet_ethereum`, rejecting the ones
+		/// below it even without EIP-1559. Set to `()` for no floor.
+		type GasPriceFloor: Get<U256>;
 		/// EVM execution runner.
 		type Runner: Runner<Self>;
 
@@ -167,6 +170,13 @@ pub mod pallet {
 		/// Called on create calls, used to record owner
 		type OnCreate: OnCreate<Self>;
 
+		/// Called after each EVM call or create.
+		type OnAfterEvmExecution: OnAfterEvmExecution;
+
+		/// Gas limits of the calls of some contracts by other pallets, see
+		/// [`Pallet::call_with_value`].
+		type GasLimitOverrides: GasLimitOverride;
+
 		/// Find author for the current block.
 		type FindAuthor: FindAuthor<H160>;
 
@@ -182,9 +192,21 @@ pub mod pallet {
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 
-		/// EVM config used in the module.
+		/// EVM config of each block, for forks activated at a block height.
+		type EvmConfigSchedule: EvmConfigSchedule<BlockNumberFor<Self>>;
+
+		/// EVM config used in the module, the one of the current block in
+		/// [`Config::EvmConfigSchedule`].
 		fn config() -> &'static EvmConfig {
-			&SHANGHAI_CONFIG
+			Self::EvmConfigSchedule::config_at(frame_system::Pallet::<Self>::block_number())
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			Self::do_try_state()
 		}
 	}
 
@@ -461,6 +483,48 @@ pub mod pallet {
 				pays_fee: Pays::No,
 			})
 		}
+
+		/// Set the activation of the precompile at `address`. The precompile is only executed
+		/// once `enabled` and from block `active_from` on, calls to it before behave like calls
+		/// to an empty account.
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_precompile_activation())]
+		pub fn set_precompile_activation(
+			origin: OriginFor<T>,
+			address: H160,
+			enabled: bool,
+			active_from: BlockNumberFor<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			PrecompileActivations::<T>::insert(
+				address,
+				PrecompileActivation {
+					enabled,
+					active_from,
+				},
+			);
+			Pallet::<T>::deposit_event(Event::<T>::PrecompileActivationSet {
+				address,
+				enabled,
+				active_from,
+			});
+
+			Ok(())
+		}
+
+		/// Remove the activation entry of the precompile at `address`, which makes it active
+		/// again.
+		#[pallet::call_index(5)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::remove_precompile_activation())]
+		pub fn remove_precompile_activation(origin: OriginFor<T>, address: H160) -> DispatchResult {
+			ensure_root(origin)?;
+
+			PrecompileActivations::<T>::remove(address);
+			Pallet::<T>::deposit_event(Event::<T>::PrecompileActivationRemoved { address });
+
+			Ok(())
+		}
 	}
 
 	#[pallet::event]
@@ -476,6 +540,14 @@ pub mod pallet {
 		Executed { address: H160 },
 		/// A contract has been executed with errors. States are reverted with only gas fees applied.
 		ExecutedFailed { address: H160 },
+		/// The activation of a precompile has been set.
+		PrecompileActivationSet {
+			address: H160,
+			enabled: bool,
+			active_from: BlockNumberFor<T>,
+		},
+		/// The activation entry of a precompile has been removed.
+		PrecompileActivationRemoved { address: H160 },
 	}
 
 	#[pallet::error]
@@ -558,6 +630,19 @@ pub mod pallet {
 					&account_id,
 					account.balance.unique_saturated_into(),
 				);
+				let native_balance =
+					U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(
+						T::Currency::free_balance(&account_id),
+					));
+				if native_balance != account.balance {
+					log::warn!(
+						target: "evm",
+						"Genesis account {:?} has a native balance of {} instead of its EVM balance of {}",
+						address,
+						native_balance,
+						account.balance,
+					);
+				}
 
 				Pallet::<T>::create_account(*address, account.code.clone());
 
@@ -581,6 +666,11 @@ pub mod pallet {
 
 	#[pallet::storage]
 	pub type Suicided<T: Config> = StorageMap<_, Blake2_128Concat, H160, (), OptionQuery>;
+
+	/// Activation of the precompiles, precompiles without an entry are always active.
+	#[pallet::storage]
+	pub type PrecompileActivations<T: Config> =
+		StorageMap<_, Blake2_128Concat, H160, PrecompileActivation<BlockNumberFor<T>>, OptionQuery>;
 }
 
 /// Type alias for currency balance.
@@ -607,6 +697,144 @@ pub struct CodeMetadata {
 	pub hash: H256,
 }
 
+/// EVM account defined at genesis, see [`build_evm_genesis`].
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct EvmGenesisAccount {
+	pub address: H160,
+	/// Balance, credited to the native account of `address`.
+	pub balance: U256,
+	pub nonce: U256,
+	pub code: Vec<u8>,
+	pub storage: BTreeMap<H256, H256>,
+}
+
+/// Build the `accounts` of the pallet [`GenesisConfig`] from a list of accounts.
+///
+/// The balance of each account is credited to its native account when the genesis is built, it
+/// should not be endowed by the balances pallet as well. An address listed more than once keeps
+/// its last definition.
+pub fn build_evm_genesis(accounts: Vec<EvmGenesisAccount>) -> BTreeMap<H160, GenesisAccount> {
+	let mut genesis = BTreeMap::new();
+	for account in accounts {
+		let previous = genesis.insert(
+			account.address,
+			GenesisAccount {
+				nonce: account.nonce,
+				balance: account.balance,
+				storage: account.storage,
+				code: account.code,
+			},
+		);
+		if previous.is_some() {
+			log::warn!(
+				target: "evm",
+				"Genesis account {:?} is defined more than once, keeping the last definition",
+				account.address,
+			);
+		}
+	}
+	genesis
+}
+
+/// Activation of a precompile, see [`ActivatedPrecompiles`].
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	Eq,
+	PartialEq,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen
+)]
+pub struct PrecompileActivation<BlockNumber> {
+	/// Whether the precompile is enabled.
+	pub enabled: bool,
+	/// First block the precompile is active at.
+	pub active_from: BlockNumber,
+}
+
+/// Precompile set only exposing the precompiles of `P` active according to the on-chain
+/// [`PrecompileActivations`]. Inactive precompiles are not precompiles as far as the EVM is
+/// concerned, so calling them behaves like calling an empty account.
+///
+/// The activation of each precompile is read once and cached for the lifetime of the set, the
+/// gas of [`WeightInfo::precompile_activation_lookup`] being charged on the first lookup. Only
+/// the addresses of `P` are looked up, which bounds the cache.
+pub struct ActivatedPrecompiles<T, P> {
+	precompiles: P,
+	activations: core::cell::RefCell<BTreeMap<H160, bool>>,
+	_marker: core::marker::PhantomData<T>,
+}
+
+impl<T, P> ActivatedPrecompiles<T, P> {
+	pub fn new(precompiles: P) -> Self {
+		Self {
+			precompiles,
+			activations: Default::default(),
+			_marker: core::marker::PhantomData,
+		}
+	}
+}
+
+impl<T: Config, P: PrecompileSet> ActivatedPrecompiles<T, P> {
+	/// Returns whether the precompile at `address` is active, and the gas cost of the lookup.
+	fn lookup(&self, address: H160) -> (bool, u64) {
+		if let Some(active) = self.activations.borrow().get(&address) {
+			return (*active, 0);
+		}
+		let active = Pallet::<T>::is_precompile_active(address);
+		self.activations.borrow_mut().insert(address, active);
+		let cost = T::GasWeightMapping::weight_to_gas(
+			<T as Config>::WeightInfo::precompile_activation_lookup(),
+		);
+		(active, cost)
+	}
+}
+
+impl<T: Config, P: PrecompileSet> PrecompileSet for ActivatedPrecompiles<T, P> {
+	fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
+		let address = handle.code_address();
+		if let IsPrecompileResult::Answer {
+			is_precompile: true,
+			..
+		} = self
+			.precompiles
+			.is_precompile(address, handle.remaining_gas())
+		{
+			let (active, cost) = self.lookup(address);
+			if let Err(exit_status) = handle.record_cost(cost) {
+				return Some(Err(PrecompileFailure::Error { exit_status }));
+			}
+			if !active {
+				return None;
+			}
+		}
+		self.precompiles.execute(handle)
+	}
+
+	fn is_precompile(&self, address: H160, remaining_gas: u64) -> IsPrecompileResult {
+		match self.precompiles.is_precompile(address, remaining_gas) {
+			IsPrecompileResult::Answer {
+				is_precompile: true,
+				extra_cost,
+			} => {
+				let (active, cost) = self.lookup(address);
+				let extra_cost = extra_cost.saturating_add(cost);
+				if extra_cost > remaining_gas {
+					return IsPrecompileResult::OutOfGas;
+				}
+				IsPrecompileResult::Answer {
+					is_precompile: active,
+					extra_cost,
+				}
+			}
+			result => result,
+		}
+	}
+}
+
 impl CodeMetadata {
 	fn from_code(code: &[u8]) -> Self {
 		let size = code.len() as u64;
@@ -747,6 +975,47 @@ impl<H: Hasher<Out = H256>> AddressMapping<AccountId32> for HashedAddressMapping
 	}
 }
 
+/// Trait to be implemented for the reverse of an evm address mapping.
+pub trait AccountIdMapping<A> {
+	fn into_address(account_id: A) -> H160;
+}
+
+/// Identity account id mapping, the reverse of [`IdentityAddressMapping`].
+pub struct IdentityAccountIdMapping;
+
+impl<T: Into<H160>> AccountIdMapping<T> for IdentityAccountIdMapping {
+	fn into_address(account_id: T) -> H160 {
+		account_id.into()
+	}
+}
+
+/// Truncated account id mapping, the address being the first 20 bytes of the `AccountId32` like
+/// in [`EnsureAddressTruncated`].
+pub struct TruncatedAccountIdMapping;
+
+impl AccountIdMapping<AccountId32> for TruncatedAccountIdMapping {
+	fn into_address(account_id: AccountId32) -> H160 {
+		H160::from_slice(&AsRef::<[u8; 32]>::as_ref(&account_id)[0..20])
+	}
+}
+
+/// Address of the block author account `A` found by `F`, e.g. with
+/// `pallet_session::FindAccountFromAuthorIndex`, mapped by `M`.
+pub struct FindAuthorAddress<F, M, A>(core::marker::PhantomData<(F, M, A)>);
+
+impl<F, M, A> FindAuthor<H160> for FindAuthorAddress<F, M, A>
+where
+	F: FindAuthor<A>,
+	M: AccountIdMapping<A>,
+{
+	fn find_author<'a, I>(digests: I) -> Option<H160>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		F::find_author(digests).map(M::into_address)
+	}
+}
+
 /// A trait for getting a block hash by number.
 pub trait BlockHashMapping {
 	fn block_hash(number: u32) -> H256;
@@ -792,9 +1061,120 @@ impl<T: Config> GasWeightMapping for FixedGasWeightMapping<T> {
 	}
 }
 
+/// A trait for getting the EVM config of a block.
+///
+/// This is called with the number of the block being executed, so the runtime APIs replaying a
+/// block also use the config the block was executed with.
+pub trait EvmConfigSchedule<BlockNumber> {
+	fn config_at(number: BlockNumber) -> &'static EvmConfig;
+}
+
+/// Shanghai config for every block.
+impl<BlockNumber> EvmConfigSchedule<BlockNumber> for () {
+	fn config_at(_number: BlockNumber) -> &'static EvmConfig {
+		&SHANGHAI_CONFIG
+	}
+}
+
 static SHANGHAI_CONFIG: EvmConfig = EvmConfig::shanghai();
 
 impl<T: Config> Pallet<T> {
+	/// Whether the precompile at `address` is active at the current block, according to
+	/// [`PrecompileActivations`].
+	pub fn is_precompile_active(address: H160) -> bool {
+		PrecompileActivations::<T>::get(address).map_or(true, |activation| {
+			activation.enabled
+				&& frame_system::Pallet::<T>::block_number() >= activation.active_from
+		})
+	}
+
+	/// Whether `address` is a precompile of [`Config::PrecompilesValue`] at the current block.
+	pub fn is_precompile(address: H160) -> bool {
+		matches!(
+			T::PrecompilesValue::get().is_precompile(address, u64::MAX),
+			IsPrecompileResult::Answer {
+				is_precompile: true,
+				..
+			}
+		)
+	}
+
+	/// Call `target` from `source`, transferring `value` along with the call, for other pallets to
+	/// call into the EVM.
+	///
+	/// The origin is checked with [`Config::CallOrigin`], as for the `call` extrinsic, but the call
+	/// is not a transaction and no fee is charged. The value transfer is reverted along with the
+	/// call if it does not succeed.
+	///
+	/// The gas limit of [`Config::GasLimitOverrides`] for `target`, if any, replaces `gas_limit`.
+	pub fn call_with_value(
+		origin: T::RuntimeOrigin,
+		source: H160,
+		target: H160,
+		input: Vec<u8>,
+		value: U256,
+		gas_limit: u64,
+	) -> Result<CallInfo, DispatchErrorWithPostInfo> {
+		T::CallOrigin::ensure_address_origin(&source, origin)?;
+		let gas_limit = T::GasLimitOverrides::gas_limit_override(&target).unwrap_or(gas_limit);
+
+		let is_transactional = false;
+		let validate = true;
+		let mut info = T::Runner::call(
+			source,
+			target,
+			input,
+			value,
+			gas_limit,
+			None,
+			None,
+			None,
+			Vec::new(),
+			is_transactional,
+			validate,
+			None,
+			None,
+			T::config(),
+		)
+		.map_err(|e| DispatchErrorWithPostInfo {
+			post_info: PostDispatchInfo {
+				actual_weight: Some(e.weight),
+				pays_fee: Pays::Yes,
+			},
+			error: e.error.into(),
+		})?;
+
+		// The runner only runs the hook for transactions, but this call is applied as well.
+		let weight = T::OnAfterEvmExecution::on_after_evm_execution(
+			source,
+			Some(target),
+			value,
+			info.exit_reason.clone(),
+			info.used_gas.standard.unique_saturated_into(),
+		);
+		let gas = U256::from(T::GasWeightMapping::weight_to_gas(weight));
+		info.used_gas.standard = info.used_gas.standard.saturating_add(gas);
+		info.used_gas.effective = info.used_gas.effective.saturating_add(gas);
+		if let Some(proof_size_usage) = info
+			.weight_info
+			.as_mut()
+			.and_then(|weight_info| weight_info.proof_size_usage.as_mut())
+		{
+			*proof_size_usage = proof_size_usage.saturating_add(weight.proof_size());
+		}
+
+		match info.exit_reason {
+			ExitReason::Succeed(_) => {
+				Pallet::<T>::deposit_event(Event::<T>::Executed { address: target });
+			}
+			_ => {
+				Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed { address: target });
+			}
+		};
+
+		Ok(info)
+	}
+
 	/// Check whether an account is empty.
 	pub fn is_account_empty(address: &H160) -> bool {
 		let (account, _) = Self::account_basic(address);
@@ -927,6 +1307,31 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+#[cfg(any(feature = "try-runtime", test))]
+impl<T: Config> Pallet<T> {
+	/// Check that the accounts with code agree with their native accounts: the native account is
+	/// kept alive by a sufficient reference and the code metadata, if cached, describes the code.
+	pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+		for (address, code) in AccountCodes::<T>::iter() {
+			let account_id = T::AddressMapping::into_account_id(address);
+
+			frame_support::ensure!(
+				code.is_empty() || frame_system::Pallet::<T>::sufficients(&account_id) > 0,
+				"Account with code has no sufficient reference"
+			);
+
+			if let Some(metadata) = AccountCodesMetadata::<T>::get(address) {
+				frame_support::ensure!(
+					metadata == CodeMetadata::from_code(&code),
+					"Code metadata does not match the code"
+				);
+			}
+		}
+
+		Ok(())
+	}
+}
+
 /// Handle withdrawing, refunding and depositing of transaction fees.
 /// Similar to `OnChargeTransaction` of `pallet_transaction_payment`
 pub trait OnChargeEVMTransaction<T: Config> {
@@ -1175,3 +1580,69 @@ impl<T> OnCreate<T> for Tuple {
 		)*)
 	}
 }
+
+/// Gas limits of the calls of some contracts, like system contracts called from the runtime whose
+/// weight is budgeted separately.
+///
+/// Only [`Pallet::call_with_value`] applies them, the calls of transactions never do.
+pub trait GasLimitOverride {
+	/// The gas limit of the calls of `target`, `None` to keep the one of the caller.
+	fn gas_limit_override(target: &H160) -> Option<u64>;
+}
+
+impl GasLimitOverride for () {
+	fn gas_limit_override(_target: &H160) -> Option<u64> {
+		None
+	}
+}
+
+/// Handler of the end of an EVM execution.
+pub trait OnAfterEvmExecution {
+	/// Called when a call of `target`, or a create if `None`, by `caller` completed, for the
+	/// executions which are applied: not for the ones of `eth_call` or `eth_estimateGas`.
+	///
+	/// Returns the weight consumed, which is charged to the execution as gas on top of
+	/// `gas_used`.
+	fn on_after_evm_execution(
+		caller: H160,
+		target: Option<H160>,
+		value: U256,
+		exit_reason: ExitReason,
+		gas_used: u64,
+	) -> Weight;
+}
+
+impl OnAfterEvmExecution for () {
+	fn on_after_evm_execution(
+		_caller: H160,
+		_target: Option<H160>,
+		_value: U256,
+		_exit_reason: ExitReason,
+		_gas_used: u64,
+	) -> Weight {
+		Weight::zero()
+	}
+}
+
+#[impl_for_tuples(1, 12)]
+impl OnAfterEvmExecution for Tuple {
+	fn on_after_evm_execution(
+		caller: H160,
+		target: Option<H160>,
+		value: U256,
+		exit_reason: ExitReason,
+		gas_used: u64,
+	) -> Weight {
+		let mut weight = Weight::zero();
+		for_tuples!(#(
+			weight = weight.saturating_add(Tuple::on_after_evm_execution(
+				caller,
+				target,
+				value,
+				exit_reason.clone(),
+				gas_used,
+			));
+		)*);
+		weight
+	}
+}
@@ -31,8 +31,9 @@ use sp_runtime::{
 };
 
 use crate::{
-	EnsureAddressNever, EnsureAddressRoot, FeeCalculator, IdentityAddressMapping,
-	IsPrecompileResult, Precompile, PrecompileHandle, PrecompileResult, PrecompileSet,
+	ActivatedPrecompiles, EnsureAddressNever, EnsureAddressRoot, EvmConfig, EvmConfigSchedule,
+	ExitReason, FeeCalculator, IdentityAddressMapping, IsPrecompileResult, Precompile,
+	PrecompileHandle, PrecompileResult, PrecompileSet,
 };
 
 frame_support::construct_runtime! {
@@ -131,9 +132,76 @@ parameter_types! {
 	pub BlockGasLimit: U256 = U256::from(BLOCK_GAS_LIMIT);
 	pub const GasLimitPovSizeRatio: u64 = BLOCK_GAS_LIMIT.saturating_div(MAX_POV_SIZE);
 	pub WeightPerGas: Weight = Weight::from_parts(20_000, 0);
-	pub MockPrecompiles: MockPrecompileSet = MockPrecompileSet;
+	pub MockPrecompiles: ActivatedPrecompiles<Test, MockPrecompileSet> =
+		ActivatedPrecompiles::new(MockPrecompileSet);
 	pub SuicideQuickClearLimit: u32 = 0;
+	/// London config before this block, Shanghai config from it.
+	pub storage ShanghaiActivation: u64 = 0;
 }
+
+static LONDON_CONFIG: EvmConfig = EvmConfig::london();
+static SHANGHAI_CONFIG: EvmConfig = EvmConfig::shanghai();
+
+pub struct MockEvmConfigSchedule;
+impl EvmConfigSchedule<u64> for MockEvmConfigSchedule {
+	fn config_at(number: u64) -> &'static EvmConfig {
+		if number < ShanghaiActivation::get() {
+			&LONDON_CONFIG
+		} else {
+			&SHANGHAI_CONFIG
+		}
+	}
+}
+
+/// An execution seen by [`MockOnAfterEvmExecution`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct EvmExecution {
+	pub caller: H160,
+	pub target: Option<H160>,
+	pub value: U256,
+	pub exit_reason: ExitReason,
+	pub gas_used: u64,
+}
+
+std::thread_local! {
+	pub static EVM_EXECUTIONS: core::cell::RefCell<Vec<EvmExecution>> = Default::default();
+	/// Weight returned by [`MockOnAfterEvmExecution`].
+	pub static EVM_EXECUTION_WEIGHT: core::cell::Cell<Weight> = core::cell::Cell::new(Weight::zero());
+}
+
+pub struct MockOnAfterEvmExecution;
+impl crate::OnAfterEvmExecution for MockOnAfterEvmExecution {
+	fn on_after_evm_execution(
+		caller: H160,
+		target: Option<H160>,
+		value: U256,
+		exit_reason: ExitReason,
+		gas_used: u64,
+	) -> Weight {
+		EVM_EXECUTIONS.with(|executions| {
+			executions.borrow_mut().push(EvmExecution {
+				caller,
+				target,
+				value,
+				exit_reason,
+				gas_used,
+			})
+		});
+		EVM_EXECUTION_WEIGHT.with(|weight| weight.get())
+	}
+}
+
+/// Contract called with [`SYSTEM_CONTRACT_GAS_LIMIT`] by [`MockGasLimitOverrides`].
+pub const SYSTEM_CONTRACT: H160 = H160([0xd7; 20]);
+pub const SYSTEM_CONTRACT_GAS_LIMIT: u64 = 1_000_000;
+
+pub struct MockGasLimitOverrides;
+impl crate::GasLimitOverride for MockGasLimitOverrides {
+	fn gas_limit_override(target: &H160) -> Option<u64> {
+		(*target == SYSTEM_CONTRACT).then_some(SYSTEM_CONTRACT_GAS_LIMIT)
+	}
+}
+
 impl crate::Config for Test {
 	type FeeCalculator = FixedGasPrice;
 	type GasWeightMapping = crate::FixedGasWeightMapping<Self>;
@@ -147,23 +215,33 @@ impl crate::Config for Test {
 	type Currency = Balances;
 
 	type RuntimeEvent = RuntimeEvent;
-	type PrecompilesType = MockPrecompileSet;
+	type PrecompilesType = ActivatedPrecompiles<Self, MockPrecompileSet>;
 	type PrecompilesValue = MockPrecompiles;
 	type ChainId = ();
 	type BlockGasLimit = BlockGasLimit;
+	type GasPriceFloor = ();
 	type Runner = crate::runner::stack::Runner<Self>;
 	type OnChargeTransaction = ();
 	type OnCreate = ();
+	type OnAfterEvmExecution = MockOnAfterEvmExecution;
+	type GasLimitOverrides = MockGasLimitOverrides;
 	type FindAuthor = FindAuthorTruncated;
 	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
 	type Timestamp = Timestamp;
+	type EvmConfigSchedule = MockEvmConfigSchedule;
 	type WeightInfo = ();
 }
 
-/// Example PrecompileSet with only Identity precompile.
+/// Example PrecompileSet with the Identity precompile, along with the recovery precompiles used by
+/// the EIP-712 tests.
 pub struct MockPrecompileSet;
 
+/// Address of the ECRecover precompile, address 1 being the Identity precompile here.
+pub const ECRECOVER_ADDRESS: u64 = 0x0101;
+/// Address of the EIP712Recover precompile, as in the template runtime.
+pub const EIP712_RECOVER_ADDRESS: u64 = 1026;
+
 impl PrecompileSet for MockPrecompileSet {
 	/// Tries to execute a precompile in the precompile set.
 	/// If the provided address is not a precompile, returns None.
@@ -173,6 +251,12 @@ impl PrecompileSet for MockPrecompileSet {
 		if address == H160::from_low_u64_be(1) {
 			return Some(pallet_evm_precompile_simple::Identity::execute(handle));
 		}
+		if address == H160::from_low_u64_be(ECRECOVER_ADDRESS) {
+			return Some(pallet_evm_precompile_simple::ECRecover::execute(handle));
+		}
+		if address == H160::from_low_u64_be(EIP712_RECOVER_ADDRESS) {
+			return Some(pallet_evm_precompile_simple::EIP712Recover::execute(handle));
+		}
 
 		None
 	}
@@ -182,7 +266,9 @@ impl PrecompileSet for MockPrecompileSet {
 	/// `execute` already performs a check internally.
 	fn is_precompile(&self, address: H160, _gas: u64) -> IsPrecompileResult {
 		IsPrecompileResult::Answer {
-			is_precompile: address == H160::from_low_u64_be(1),
+			is_precompile: [1, ECRECOVER_ADDRESS, EIP712_RECOVER_ADDRESS]
+				.into_iter()
+				.any(|precompile| address == H160::from_low_u64_be(precompile)),
 			extra_cost: 0,
 		}
 	}
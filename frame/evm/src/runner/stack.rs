@@ -48,8 +48,8 @@ use fp_evm::{
 
 use crate::{
 	runner::Runner as RunnerT, AccountCodes, AccountCodesMetadata, AccountStorages, AddressMapping,
-	BalanceOf, BlockHashMapping, Config, Error, Event, FeeCalculator, OnChargeEVMTransaction,
-	OnCreate, Pallet, RunnerError,
+	BalanceOf, BlockHashMapping, Config, Error, Event, FeeCalculator, GasWeightMapping,
+	OnAfterEvmExecution, OnChargeEVMTransaction, OnCreate, Pallet, RunnerError,
 };
 
 #[cfg(feature = "forbid-evm-reentrancy")]
@@ -68,6 +68,7 @@ where
 	/// Execute an already validated EVM operation.
 	fn execute<'config, 'precompiles, F, R>(
 		source: H160,
+		target: Option<H160>,
 		value: U256,
 		gas_limit: u64,
 		max_fee_per_gas: Option<U256>,
@@ -102,6 +103,7 @@ where
 
 		let res = Self::execute_inner(
 			source,
+			target,
 			value,
 			gas_limit,
 			max_fee_per_gas,
@@ -127,6 +129,7 @@ where
 	// Execute an already validated EVM operation.
 	fn execute_inner<'config, 'precompiles, F, R>(
 		source: H160,
+		target: Option<H160>,
 		value: U256,
 		mut gas_limit: u64,
 		max_fee_per_gas: Option<U256>,
@@ -246,7 +249,24 @@ where
 		let (reason, retv) = f(&mut executor);
 
 		// Post execution.
-		let used_gas = executor.used_gas();
+		let mut used_gas = executor.used_gas();
+		// The executions of `eth_call` and `eth_estimateGas` are not applied, and do not run the
+		// hook. Its weight is charged as gas.
+		if is_transactional {
+			let weight = T::OnAfterEvmExecution::on_after_evm_execution(
+				source,
+				target,
+				value,
+				reason.clone(),
+				used_gas,
+			);
+			if let (Some(weight_info), _) = executor.state_mut().info_mut() {
+				if let Some(proof_size_usage) = weight_info.proof_size_usage.as_mut() {
+					*proof_size_usage = proof_size_usage.saturating_add(weight.proof_size());
+				}
+			}
+			used_gas = used_gas.saturating_add(T::GasWeightMapping::weight_to_gas(weight));
+		}
 		let effective_gas = match executor.state().weight_info() {
 			Some(weight_info) => U256::from(core::cmp::max(
 				used_gas,
@@ -440,6 +460,7 @@ where
 		let precompiles = T::PrecompilesValue::get();
 		Self::execute(
 			source,
+			Some(target),
 			value,
 			gas_limit,
 			max_fee_per_gas,
@@ -488,6 +509,7 @@ where
 		let precompiles = T::PrecompilesValue::get();
 		Self::execute(
 			source,
+			None,
 			value,
 			gas_limit,
 			max_fee_per_gas,
@@ -544,6 +566,7 @@ where
 		let code_hash = H256::from(sp_io::hashing::keccak_256(&init));
 		Self::execute(
 			source,
+			None,
 			value,
 			gas_limit,
 			max_fee_per_gas,
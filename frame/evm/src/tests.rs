@@ -21,7 +21,7 @@ use super::*;
 use crate::mock::*;
 
 use frame_support::{
-	assert_ok,
+	assert_noop, assert_ok,
 	traits::{LockIdentifier, LockableCurrency, WithdrawReasons},
 };
 use sp_runtime::BuildStorage;
@@ -686,6 +686,49 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	t.into()
 }
 
+#[test]
+fn build_evm_genesis_works() {
+	let address = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+	let mut storage = BTreeMap::new();
+	storage.insert(H256::from_low_u64_be(1), H256::from_low_u64_be(2));
+	let accounts = crate::build_evm_genesis(vec![
+		EvmGenesisAccount {
+			address,
+			balance: U256::from(1),
+			..Default::default()
+		},
+		EvmGenesisAccount {
+			address,
+			balance: U256::from(1000000),
+			nonce: U256::from(2),
+			code: vec![0x00],
+			storage,
+		},
+	]);
+	assert_eq!(accounts.len(), 1);
+
+	let mut t = frame_system::GenesisConfig::<Test>::default()
+		.build_storage()
+		.unwrap();
+	crate::GenesisConfig::<Test> {
+		accounts,
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		let (account, _) = EVM::account_basic(&address);
+		assert_eq!(account.balance, U256::from(1000000));
+		assert_eq!(account.nonce, U256::from(2));
+		assert_eq!(AccountCodes::<Test>::get(address), vec![0x00]);
+		assert_eq!(
+			AccountStorages::<Test>::get(address, H256::from_low_u64_be(1)),
+			H256::from_low_u64_be(2)
+		);
+	});
+}
+
 #[test]
 fn fail_call_return_ok() {
 	new_test_ext().execute_with(|| {
@@ -1260,3 +1303,864 @@ fn metadata_empty_dont_code_gets_cached() {
 		assert!(<AccountCodesMetadata<Test>>::get(address).is_none());
 	});
 }
+
+#[test]
+fn sstore_clears_refund_is_capped_by_hardfork() {
+	new_test_ext().execute_with(|| {
+		// Clears five storage slots: `PUSH1 0 PUSH1 slot SSTORE` for each slot, then `STOP`.
+		let contract_address = H160::repeat_byte(0xbb);
+		let mut code = Vec::new();
+		for slot in 0..5u8 {
+			code.extend([0x60, 0x00, 0x60, slot, 0x55]);
+			AccountStorages::<Test>::insert(
+				contract_address,
+				H256::from_low_u64_be(slot as u64),
+				H256::from_low_u64_be(1),
+			);
+		}
+		code.push(0x00);
+		AccountCodes::<Test>::insert(contract_address, code);
+
+		let call = |config: &evm::Config| {
+			<Test as Config>::Runner::call(
+				H160::default(),
+				contract_address,
+				Vec::new(),
+				U256::zero(),
+				1_000_000,
+				Some(FixedGasPrice::min_gas_price().0),
+				None,
+				None,
+				Vec::new(),
+				true, // transactional
+				true, // must be validated
+				None,
+				None,
+				config,
+			)
+			.expect("call succeeds")
+		};
+		// 21_000 intrinsic gas, plus 5 * (2 * PUSH1 + cold SSTORE resetting a slot).
+		let gas_before_refund = 21_000 + 5 * (2 * 3 + 5_000);
+
+		// Pre-London, up to half of the gas used is refunded.
+		let result = call(&evm::Config::berlin());
+		assert_eq!(
+			result.exit_reason,
+			ExitReason::Succeed(ExitSucceed::Stopped)
+		);
+		assert_eq!(
+			result.used_gas.standard,
+			U256::from(gas_before_refund - gas_before_refund / 2)
+		);
+
+		// EIP-3529, London onwards only refunds up to a fifth of the gas used.
+		let result = call(<Test as Config>::config());
+		assert_eq!(
+			result.exit_reason,
+			ExitReason::Succeed(ExitSucceed::Stopped)
+		);
+		assert_eq!(
+			result.used_gas.standard,
+			U256::from(gas_before_refund - gas_before_refund / 5)
+		);
+	});
+}
+
+#[test]
+fn precompile_activation_is_set_by_root() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let address = H160::from_low_u64_be(1);
+
+		assert_noop!(
+			EVM::set_precompile_activation(
+				RuntimeOrigin::signed(H160::default()),
+				address,
+				true,
+				10
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(EVM::set_precompile_activation(
+			RuntimeOrigin::root(),
+			address,
+			true,
+			10
+		));
+		System::assert_last_event(RuntimeEvent::EVM(crate::Event::PrecompileActivationSet {
+			address,
+			enabled: true,
+			active_from: 10,
+		}));
+		assert!(!EVM::is_precompile_active(address));
+
+		assert_ok!(EVM::remove_precompile_activation(
+			RuntimeOrigin::root(),
+			address
+		));
+		System::assert_last_event(RuntimeEvent::EVM(
+			crate::Event::PrecompileActivationRemoved { address },
+		));
+		assert!(EVM::is_precompile_active(address));
+	});
+}
+
+#[test]
+fn inactive_precompile_behaves_like_empty_account() {
+	new_test_ext().execute_with(|| {
+		let identity = H160::from_low_u64_be(1);
+		let call = || {
+			<Test as Config>::Runner::call(
+				H160::default(),
+				identity,
+				vec![1, 2, 3],
+				U256::zero(),
+				1_000_000,
+				Some(FixedGasPrice::min_gas_price().0),
+				None,
+				None,
+				Vec::new(),
+				true, // transactional
+				true, // must be validated
+				None,
+				None,
+				<Test as Config>::config(),
+			)
+			.expect("call succeeds")
+		};
+
+		assert_ok!(EVM::set_precompile_activation(
+			RuntimeOrigin::root(),
+			identity,
+			true,
+			10
+		));
+		System::set_block_number(9);
+		let result = call();
+		assert_eq!(
+			result.exit_reason,
+			ExitReason::Succeed(ExitSucceed::Stopped)
+		);
+		assert!(result.value.is_empty());
+
+		System::set_block_number(10);
+		let result = call();
+		assert_eq!(
+			result.exit_reason,
+			ExitReason::Succeed(ExitSucceed::Returned)
+		);
+		assert_eq!(result.value, vec![1, 2, 3]);
+
+		assert_ok!(EVM::set_precompile_activation(
+			RuntimeOrigin::root(),
+			identity,
+			false,
+			10
+		));
+		let result = call();
+		assert_eq!(
+			result.exit_reason,
+			ExitReason::Succeed(ExitSucceed::Stopped)
+		);
+		assert!(result.value.is_empty());
+	});
+}
+
+#[test]
+fn designated_invalid_consumes_all_gas() {
+	new_test_ext().execute_with(|| {
+		// EIP-141, `INVALID` aborts the execution and consumes all the remaining gas.
+		let contract_address = H160::repeat_byte(0xcc);
+		AccountCodes::<Test>::insert(contract_address, vec![0xfe]);
+
+		let result = <Test as Config>::Runner::call(
+			H160::default(),
+			contract_address,
+			Vec::new(),
+			U256::zero(),
+			1_000_000,
+			Some(FixedGasPrice::min_gas_price().0),
+			None,
+			None,
+			Vec::new(),
+			true, // transactional
+			true, // must be validated
+			None,
+			None,
+			<Test as Config>::config(),
+		)
+		.expect("call succeeds");
+
+		assert_eq!(
+			result.exit_reason,
+			ExitReason::Error(ExitError::DesignatedInvalid)
+		);
+		assert_eq!(result.used_gas.standard, U256::from(1_000_000));
+	});
+}
+
+#[test]
+fn static_context_propagates_through_calls() {
+	new_test_ext().execute_with(|| {
+		let caller = H160::repeat_byte(0xa1);
+		let proxy = H160::repeat_byte(0xb2);
+		let writer = H160::repeat_byte(0xc3);
+
+		// Stores 1 at slot 0.
+		AccountCodes::<Test>::insert(writer, vec![0x60, 0x01, 0x60, 0x00, 0x55, 0x00]);
+		// `CALL`s the writer and returns whether the call succeeded.
+		let mut code = vec![
+			0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73,
+		];
+		code.extend(writer.as_bytes());
+		code.extend([0x5a, 0xf1, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3]);
+		AccountCodes::<Test>::insert(proxy, code);
+		// `STATICCALL`s the proxy and returns its output, followed by whether the static call
+		// succeeded.
+		let mut code = vec![0x60, 0x20, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73];
+		code.extend(proxy.as_bytes());
+		code.extend([0x5a, 0xfa, 0x60, 0x20, 0x52, 0x60, 0x40, 0x60, 0x00, 0xf3]);
+		AccountCodes::<Test>::insert(caller, code);
+
+		let call = |target: H160| {
+			<Test as Config>::Runner::call(
+				H160::default(),
+				target,
+				Vec::new(),
+				U256::zero(),
+				1_000_000,
+				Some(FixedGasPrice::min_gas_price().0),
+				None,
+				None,
+				Vec::new(),
+				true, // transactional
+				true, // must be validated
+				None,
+				None,
+				<Test as Config>::config(),
+			)
+			.expect("call succeeds")
+		};
+
+		// The inner `CALL` is static as well, so the write fails but the static call succeeds.
+		let result = call(caller);
+		assert_eq!(
+			result.exit_reason,
+			ExitReason::Succeed(ExitSucceed::Returned)
+		);
+		assert_eq!(U256::from_big_endian(&result.value[..32]), U256::zero());
+		assert_eq!(U256::from_big_endian(&result.value[32..]), U256::one());
+		assert_eq!(
+			AccountStorages::<Test>::get(writer, H256::zero()),
+			H256::zero()
+		);
+
+		// Outside of a static context the same call writes.
+		let result = call(proxy);
+		assert_eq!(U256::from_big_endian(&result.value), U256::one());
+		assert_eq!(
+			AccountStorages::<Test>::get(writer, H256::zero()),
+			H256::from_low_u64_be(1)
+		);
+	});
+}
+
+#[test]
+fn return_data_is_kept_after_reverted_call() {
+	new_test_ext().execute_with(|| {
+		let caller = H160::repeat_byte(0xa1);
+		let reverter = H160::repeat_byte(0xb2);
+
+		// Reverts with `0xdeadbeef`.
+		AccountCodes::<Test>::insert(
+			reverter,
+			vec![
+				0x63, 0xde, 0xad, 0xbe, 0xef, 0x60, 0x00, 0x52, 0x60, 0x04, 0x60, 0x1c, 0xfd,
+			],
+		);
+		// `CALL`s the reverter, then returns `RETURNDATASIZE` followed by the return data
+		// copied with `RETURNDATACOPY`.
+		let mut code = vec![
+			0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73,
+		];
+		code.extend(reverter.as_bytes());
+		code.extend([
+			0x5a, 0xf1, 0x50, 0x3d, 0x60, 0x00, 0x52, 0x3d, 0x60, 0x00, 0x60, 0x20, 0x3e, 0x60,
+			0x40, 0x60, 0x00, 0xf3,
+		]);
+		AccountCodes::<Test>::insert(caller, code);
+
+		let result = <Test as Config>::Runner::call(
+			H160::default(),
+			caller,
+			Vec::new(),
+			U256::zero(),
+			1_000_000,
+			Some(FixedGasPrice::min_gas_price().0),
+			None,
+			None,
+			Vec::new(),
+			true, // transactional
+			true, // must be validated
+			None,
+			None,
+			<Test as Config>::config(),
+		)
+		.expect("call succeeds");
+
+		assert_eq!(
+			result.exit_reason,
+			ExitReason::Succeed(ExitSucceed::Returned)
+		);
+		assert_eq!(U256::from_big_endian(&result.value[..32]), U256::from(4));
+		assert_eq!(&result.value[32..36], &[0xde, 0xad, 0xbe, 0xef]);
+	});
+}
+
+#[test]
+fn call_with_value_transfers_value_along_with_the_call() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let target = H160::repeat_byte(0xd4);
+		// STOP
+		AccountCodes::<Test>::insert(target, vec![0x00]);
+
+		let info = EVM::call_with_value(
+			RuntimeOrigin::root(),
+			H160::default(),
+			target,
+			Vec::new(),
+			U256::from(1000),
+			1_000_000,
+		)
+		.expect("call succeeds");
+
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert_eq!(EVM::account_basic(&target).0.balance, U256::from(1000));
+		System::assert_last_event(RuntimeEvent::EVM(crate::Event::Executed {
+			address: target,
+		}));
+	});
+}
+
+#[test]
+fn call_with_value_reverts_the_transfer_of_a_failed_call() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let target = H160::repeat_byte(0xd5);
+		// PUSH1 0, PUSH1 0, REVERT
+		AccountCodes::<Test>::insert(target, vec![0x60, 0x00, 0x60, 0x00, 0xfd]);
+
+		let info = EVM::call_with_value(
+			RuntimeOrigin::root(),
+			H160::default(),
+			target,
+			Vec::new(),
+			U256::from(1000),
+			1_000_000,
+		)
+		.expect("call is executed");
+
+		assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+		assert_eq!(EVM::account_basic(&target).0.balance, U256::zero());
+		System::assert_last_event(RuntimeEvent::EVM(crate::Event::ExecutedFailed {
+			address: target,
+		}));
+	});
+}
+
+#[test]
+fn call_with_value_applies_the_gas_limit_overrides() {
+	new_test_ext().execute_with(|| {
+		// JUMPDEST, PUSH1 0, JUMP
+		let infinite_loop = vec![0x5b, 0x60, 0x00, 0x56];
+		let other_contract = H160::repeat_byte(0xd8);
+		AccountCodes::<Test>::insert(SYSTEM_CONTRACT, infinite_loop.clone());
+		AccountCodes::<Test>::insert(other_contract, infinite_loop);
+
+		let used_gas = |target| {
+			EVM::call_with_value(
+				RuntimeOrigin::root(),
+				H160::default(),
+				target,
+				Vec::new(),
+				U256::zero(),
+				50_000,
+			)
+			.expect("call is executed")
+			.used_gas
+			.standard
+		};
+
+		assert_eq!(
+			used_gas(SYSTEM_CONTRACT),
+			U256::from(SYSTEM_CONTRACT_GAS_LIMIT)
+		);
+		assert_eq!(used_gas(other_contract), U256::from(50_000));
+	});
+}
+
+#[test]
+fn call_with_value_checks_the_origin() {
+	new_test_ext().execute_with(|| {
+		let result = EVM::call_with_value(
+			RuntimeOrigin::signed(H160::repeat_byte(0x01)),
+			H160::default(),
+			H160::repeat_byte(0xd4),
+			Vec::new(),
+			U256::from(1000),
+			1_000_000,
+		);
+
+		assert!(result.is_err());
+	});
+}
+
+#[test]
+fn evm_config_follows_the_fork_schedule() {
+	new_test_ext().execute_with(|| {
+		let contract_address = H160::repeat_byte(0xd6);
+		// PUSH0, PUSH0, RETURN
+		AccountCodes::<Test>::insert(contract_address, vec![0x5f, 0x5f, 0xf3]);
+		ShanghaiActivation::set(&10);
+
+		let call = || {
+			<Test as Config>::Runner::call(
+				H160::default(),
+				contract_address,
+				Vec::new(),
+				U256::zero(),
+				1_000_000,
+				Some(FixedGasPrice::min_gas_price().0),
+				None,
+				None,
+				Vec::new(),
+				true, // transactional
+				true, // must be validated
+				None,
+				None,
+				<Test as Config>::config(),
+			)
+			.expect("call succeeds")
+		};
+
+		// PUSH0 is not an opcode before Shanghai.
+		System::set_block_number(9);
+		assert_eq!(
+			call().exit_reason,
+			ExitReason::Error(ExitError::InvalidCode(evm::Opcode(0x5f)))
+		);
+
+		System::set_block_number(10);
+		assert_eq!(
+			call().exit_reason,
+			ExitReason::Succeed(ExitSucceed::Returned)
+		);
+	});
+}
+
+#[test]
+fn basefee_opcode_returns_the_block_base_fee() {
+	new_test_ext().execute_with(|| {
+		let contract_address = H160::repeat_byte(0xd9);
+		// BASEFEE, PUSH1 0, MSTORE, PUSH1 32, PUSH1 0, RETURN
+		AccountCodes::<Test>::insert(
+			contract_address,
+			vec![0x48, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3],
+		);
+
+		let (base_fee, _) = FixedGasPrice::min_gas_price();
+		let result = <Test as Config>::Runner::call(
+			H160::default(),
+			contract_address,
+			Vec::new(),
+			U256::zero(),
+			1_000_000,
+			Some(base_fee),
+			None,
+			None,
+			Vec::new(),
+			true, // transactional
+			true, // must be validated
+			None,
+			None,
+			<Test as Config>::config(),
+		)
+		.expect("call succeeds");
+
+		assert_eq!(
+			result.exit_reason,
+			ExitReason::Succeed(ExitSucceed::Returned)
+		);
+		assert_eq!(U256::from_big_endian(&result.value), base_fee);
+		assert!(!base_fee.is_zero());
+	});
+}
+
+#[test]
+fn is_precompile_follows_the_precompile_set() {
+	new_test_ext().execute_with(|| {
+		let identity = H160::from_low_u64_be(1);
+		assert!(EVM::is_precompile(identity));
+		assert!(!EVM::is_precompile(H160::from_low_u64_be(2)));
+
+		assert_ok!(EVM::set_precompile_activation(
+			RuntimeOrigin::root(),
+			identity,
+			false,
+			0
+		));
+		assert!(!EVM::is_precompile(identity));
+	});
+}
+
+#[test]
+fn try_state_accepts_accounts_created_through_the_pallet() {
+	new_test_ext().execute_with(|| {
+		EVM::create_account(H160::repeat_byte(0xaa), vec![0x00]);
+		assert_ok!(EVM::do_try_state());
+	});
+}
+
+#[test]
+fn try_state_detects_code_written_around_the_pallet() {
+	new_test_ext().execute_with(|| {
+		// Without the sufficient reference of `create_account`.
+		AccountCodes::<Test>::insert(H160::repeat_byte(0xaa), vec![0x00]);
+		assert_eq!(
+			EVM::do_try_state(),
+			Err("Account with code has no sufficient reference".into())
+		);
+	});
+
+	new_test_ext().execute_with(|| {
+		let address = H160::from_str("1000000000000000000000000000000000000001").unwrap();
+		AccountCodes::<Test>::insert(address, vec![0x60, 0x00]);
+		assert_eq!(
+			EVM::do_try_state(),
+			Err("Code metadata does not match the code".into())
+		);
+	});
+}
+
+#[test]
+fn find_author_address_maps_the_author_account() {
+	struct FindAuthorAccount;
+	impl FindAuthor<AccountId32> for FindAuthorAccount {
+		fn find_author<'a, I>(_digests: I) -> Option<AccountId32>
+		where
+			I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+		{
+			let mut account = [0xee; 32];
+			account[..20].copy_from_slice(&[0xaa; 20]);
+			Some(account.into())
+		}
+	}
+
+	assert_eq!(
+		FindAuthorAddress::<FindAuthorAccount, TruncatedAccountIdMapping, AccountId32>::find_author(
+			Vec::new()
+		),
+		Some(H160::repeat_byte(0xaa))
+	);
+	assert_eq!(
+		FindAuthorAddress::<(), IdentityAccountIdMapping, H160>::find_author(Vec::new()),
+		None
+	);
+}
+
+#[test]
+fn on_after_evm_execution_sees_calls_and_creates() {
+	new_test_ext().execute_with(|| {
+		EVM_EXECUTIONS.with(|executions| executions.borrow_mut().clear());
+		let target = H160::repeat_byte(0xd4);
+		// STOP
+		AccountCodes::<Test>::insert(target, vec![0x00]);
+
+		let info = EVM::call_with_value(
+			RuntimeOrigin::root(),
+			H160::default(),
+			target,
+			Vec::new(),
+			U256::from(1000),
+			1_000_000,
+		)
+		.expect("call succeeds");
+		// PUSH1 0, PUSH1 0, RETURN
+		<Test as Config>::Runner::create(
+			H160::default(),
+			vec![0x60, 0x00, 0x60, 0x00, 0xf3],
+			U256::zero(),
+			1_000_000,
+			Some(FixedGasPrice::min_gas_price().0),
+			None,
+			None,
+			Vec::new(),
+			true,
+			true,
+			None,
+			None,
+			<Test as Config>::config(),
+		)
+		.expect("create succeeds");
+
+		let executions = EVM_EXECUTIONS.with(|executions| executions.take());
+		assert_eq!(executions.len(), 2);
+		assert_eq!(
+			executions[0],
+			EvmExecution {
+				caller: H160::default(),
+				target: Some(target),
+				value: U256::from(1000),
+				exit_reason: ExitReason::Succeed(ExitSucceed::Stopped),
+				gas_used: info.used_gas.standard.as_u64(),
+			}
+		);
+		assert_eq!(executions[1].target, None);
+		assert_eq!(
+			executions[1].exit_reason,
+			ExitReason::Succeed(ExitSucceed::Returned)
+		);
+	});
+}
+
+#[test]
+fn on_after_evm_execution_is_not_run_by_non_transactional_calls() {
+	new_test_ext().execute_with(|| {
+		EVM_EXECUTIONS.with(|executions| executions.borrow_mut().clear());
+		let target = H160::repeat_byte(0xd4);
+		// STOP
+		AccountCodes::<Test>::insert(target, vec![0x00]);
+
+		// Like the calls of `eth_call`.
+		<Test as Config>::Runner::call(
+			H160::default(),
+			target,
+			Vec::new(),
+			U256::zero(),
+			1_000_000,
+			None,
+			None,
+			None,
+			Vec::new(),
+			false,
+			true,
+			None,
+			None,
+			<Test as Config>::config(),
+		)
+		.expect("call succeeds");
+
+		assert_eq!(EVM_EXECUTIONS.with(|executions| executions.take()), vec![]);
+	});
+}
+
+#[test]
+fn on_after_evm_execution_weight_is_charged_as_gas() {
+	new_test_ext().execute_with(|| {
+		EVM_EXECUTIONS.with(|executions| executions.borrow_mut().clear());
+		EVM_EXECUTION_WEIGHT.with(|weight| weight.set(WeightPerGas::get().saturating_mul(1_000)));
+		let target = H160::repeat_byte(0xd4);
+		// STOP
+		AccountCodes::<Test>::insert(target, vec![0x00]);
+
+		let transaction = <Test as Config>::Runner::call(
+			H160::default(),
+			target,
+			Vec::new(),
+			U256::zero(),
+			1_000_000,
+			Some(FixedGasPrice::min_gas_price().0),
+			None,
+			None,
+			Vec::new(),
+			true,
+			true,
+			None,
+			None,
+			<Test as Config>::config(),
+		)
+		.expect("call succeeds");
+		let call = EVM::call_with_value(
+			RuntimeOrigin::root(),
+			H160::default(),
+			target,
+			Vec::new(),
+			U256::zero(),
+			1_000_000,
+		)
+		.expect("call succeeds");
+		EVM_EXECUTION_WEIGHT.with(|weight| weight.set(Weight::zero()));
+
+		let executions = EVM_EXECUTIONS.with(|executions| executions.take());
+		assert_eq!(executions.len(), 2);
+		assert_eq!(
+			transaction.used_gas.standard,
+			U256::from(executions[0].gas_used + 1_000)
+		);
+		assert_eq!(
+			call.used_gas.standard,
+			U256::from(executions[1].gas_used + 1_000)
+		);
+	});
+}
+
+mod eip712_recover {
+	use super::*;
+	use proptest::prelude::*;
+	use sp_core::{ecdsa, Pair};
+
+	const HALF_ORDER: &str = "7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0";
+
+	// Reference implementation of EIP712Recover, taking `(domainSeparator, hashStruct, r, s, v)`
+	// as five calldata words and recovering through ECRecover.
+	//
+	// pragma solidity ^0.8.2;
+	// contract EIP712Reference {
+	//     uint256 constant HALF_ORDER = 0x7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0;
+	//     fallback(bytes calldata input) external returns (bytes memory) {
+	//         (bytes32 domainSeparator, bytes32 hashStruct, bytes32 r, bytes32 s, uint256 v) =
+	//             abi.decode(input, (bytes32, bytes32, bytes32, bytes32, uint256));
+	//         if (uint256(s) > HALF_ORDER || (v != 27 && v != 28)) {
+	//             return "";
+	//         }
+	//         bytes32 digest = keccak256(abi.encodePacked(hex"1901", domainSeparator, hashStruct));
+	//         (, bytes memory signer) = address(0x0101).staticcall(abi.encode(digest, v, r, s));
+	//         return signer;
+	//     }
+	// }
+	//
+	// Assembled by hand from the above, without the calldata checks added by solc.
+	const EIP712_REFERENCE_CONTRACT_BYTECODE: &str = concat!(
+		// PUSH32 HALF_ORDER, PUSH1 0x60, CALLDATALOAD, GT, PUSH1 fail, JUMPI
+		"7f7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0",
+		"60603511607f57",
+		// PUSH1 0x80, CALLDATALOAD, DUP1, PUSH1 27, EQ, SWAP1, PUSH1 28, EQ, OR, ISZERO,
+		// PUSH1 fail, JUMPI
+		"60803580601b1490601c141715607f57",
+		// PUSH2 0x1901, PUSH1 240, SHL, PUSH1 0, MSTORE
+		"61190160f01b600052",
+		// PUSH1 0, CALLDATALOAD, PUSH1 2, MSTORE, PUSH1 0x20, CALLDATALOAD, PUSH1 0x22, MSTORE
+		"600035600252602035602252",
+		// PUSH1 0x42, PUSH1 0, SHA3, PUSH1 0, MSTORE
+		"6042600020600052",
+		// PUSH1 0x80, CALLDATALOAD, PUSH1 0x20, MSTORE, PUSH1 0x40, CALLDATALOAD, PUSH1 0x40,
+		// MSTORE, PUSH1 0x60, CALLDATALOAD, PUSH1 0x60, MSTORE
+		"608035602052604035604052606035606052",
+		// PUSH1 0x20, PUSH1 0x80, PUSH1 0x80, PUSH1 0, PUSH2 0x0101, GAS, STATICCALL, POP
+		"6020608060806000610101",
+		"5afa50",
+		// RETURNDATASIZE, ISZERO, PUSH1 fail, JUMPI, PUSH1 0x20, PUSH1 0x80, RETURN
+		"3d15607f5760206080f3",
+		// fail: JUMPDEST, PUSH1 0, DUP1, RETURN
+		"5b600080f3",
+	);
+
+	fn call(target: H160, input: Vec<u8>) -> Vec<u8> {
+		let result = <Test as Config>::Runner::call(
+			H160::default(),
+			target,
+			input,
+			U256::zero(),
+			1_000_000,
+			Some(FixedGasPrice::min_gas_price().0),
+			None,
+			None,
+			Vec::new(),
+			true, // transactional
+			true, // must be validated
+			None,
+			None,
+			<Test as Config>::config(),
+		)
+		.expect("call succeeds");
+		assert_eq!(
+			result.exit_reason,
+			ExitReason::Succeed(ExitSucceed::Returned)
+		);
+		result.value
+	}
+
+	/// `n - s`, the malleable counterpart of `s`.
+	fn flip_s(s: &[u8]) -> [u8; 32] {
+		let half_order = U256::from_big_endian(&hex::decode(HALF_ORDER).unwrap());
+		let order = half_order * 2 + 1;
+		let mut flipped = [0u8; 32];
+		(order - U256::from_big_endian(s)).to_big_endian(&mut flipped);
+		flipped
+	}
+
+	/// Signs the EIP-712 digest, returning the `r || s || v` signature with `v` in `{27, 28}`.
+	fn sign(seed: [u8; 32], domain_separator: [u8; 32], hash_struct: [u8; 32]) -> [u8; 65] {
+		let mut message = vec![0x19, 0x01];
+		message.extend(domain_separator);
+		message.extend(hash_struct);
+		let pair = ecdsa::Pair::from_seed(&seed);
+		let mut signature = [0u8; 65];
+		signature.copy_from_slice(
+			pair.sign_prehashed(&sp_io::hashing::keccak_256(&message))
+				.as_ref(),
+		);
+		signature[64] += 27;
+		signature
+	}
+
+	proptest! {
+		#![proptest_config(ProptestConfig::with_cases(64))]
+
+		#[test]
+		fn eip712_recover_matches_the_solidity_reference(
+			seed in any::<[u8; 32]>(),
+			domain_separator in any::<[u8; 32]>(),
+			hash_struct in any::<[u8; 32]>(),
+			mutation in 0u8..4,
+			noise in any::<[u8; 32]>(),
+		) {
+			let mut signature = sign(seed, domain_separator, hash_struct);
+			match mutation {
+				// Malleable counterpart of a valid signature.
+				1 => {
+					let flipped = flip_s(&signature[32..64]);
+					signature[32..64].copy_from_slice(&flipped);
+					signature[64] = if signature[64] == 27 { 28 } else { 27 };
+				}
+				2 => signature[64] = noise[0],
+				3 => signature[32..64].copy_from_slice(&noise),
+				_ => {}
+			}
+
+			let mut precompile_input = Vec::new();
+			precompile_input.extend(domain_separator);
+			precompile_input.extend(hash_struct);
+			precompile_input.extend(H256::from_low_u64_be(0x60).as_bytes());
+			precompile_input.extend(H256::from_low_u64_be(65).as_bytes());
+			precompile_input.extend(signature);
+			precompile_input.extend([0u8; 31]);
+
+			let mut reference_input = Vec::new();
+			reference_input.extend(domain_separator);
+			reference_input.extend(hash_struct);
+			reference_input.extend(&signature[0..64]);
+			reference_input.extend(H256::from_low_u64_be(signature[64].into()).as_bytes());
+
+			new_test_ext().execute_with(|| {
+				let reference = H160::repeat_byte(0x71);
+				EVM::create_account(
+					reference,
+					hex::decode(EIP712_REFERENCE_CONTRACT_BYTECODE).unwrap(),
+				);
+
+				let expected = call(reference, reference_input);
+				let output = call(H160::from_low_u64_be(EIP712_RECOVER_ADDRESS), precompile_input);
+				prop_assert_eq!(&output, &expected);
+				match mutation {
+					0 => prop_assert_eq!(output.len(), 32),
+					1 => prop_assert!(output.is_empty()),
+					_ => {}
+				}
+				Ok(())
+			})?;
+		}
+	}
+}
@@ -49,6 +49,9 @@ use core::marker::PhantomData;
 /// Weight functions needed for pallet_evm.
 pub trait WeightInfo {
 	fn withdraw() -> Weight;
+	fn set_precompile_activation() -> Weight;
+	fn remove_precompile_activation() -> Weight;
+	fn precompile_activation_lookup() -> Weight;
 }
 
 /// Weights for pallet_evm using the Substrate node and recommended hardware.
@@ -61,6 +64,41 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		// Minimum execution time: 1_564_000 picoseconds.
 		Weight::from_parts(1_696_000, 0)
 	}
+	/// Storage: System Number (r:1 w:0)
+	/// Proof: System Number (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: EVM PrecompileActivations (r:0 w:1)
+	/// Proof: EVM PrecompileActivations (max_values: None, max_size: Some(41), added: 2516, mode: MaxEncodedLen)
+	fn set_precompile_activation() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `1489`
+		// Execution time not measured yet, the base being the one of `withdraw`.
+		Weight::from_parts(1_696_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: System Number (r:1 w:0)
+	/// Proof: System Number (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: EVM PrecompileActivations (r:0 w:1)
+	/// Proof: EVM PrecompileActivations (max_values: None, max_size: Some(41), added: 2516, mode: MaxEncodedLen)
+	fn remove_precompile_activation() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `1489`
+		// Execution time not measured yet, the base being the one of `withdraw`.
+		Weight::from_parts(1_696_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: System Number (r:1 w:0)
+	/// Proof: System Number (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: EVM PrecompileActivations (r:1 w:0)
+	/// Proof: EVM PrecompileActivations (max_values: None, max_size: Some(41), added: 2516, mode: MaxEncodedLen)
+	fn precompile_activation_lookup() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `4005`
+		// Execution time not measured yet, the base being the one of `withdraw`.
+		Weight::from_parts(1_696_000, 4005)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -72,4 +110,39 @@ impl WeightInfo for () {
 		// Minimum execution time: 1_564_000 picoseconds.
 		Weight::from_parts(1_696_000, 0)
 	}
+	/// Storage: System Number (r:1 w:0)
+	/// Proof: System Number (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: EVM PrecompileActivations (r:0 w:1)
+	/// Proof: EVM PrecompileActivations (max_values: None, max_size: Some(41), added: 2516, mode: MaxEncodedLen)
+	fn set_precompile_activation() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `1489`
+		// Execution time not measured yet, the base being the one of `withdraw`.
+		Weight::from_parts(1_696_000, 1489)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: System Number (r:1 w:0)
+	/// Proof: System Number (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: EVM PrecompileActivations (r:0 w:1)
+	/// Proof: EVM PrecompileActivations (max_values: None, max_size: Some(41), added: 2516, mode: MaxEncodedLen)
+	fn remove_precompile_activation() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `1489`
+		// Execution time not measured yet, the base being the one of `withdraw`.
+		Weight::from_parts(1_696_000, 1489)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: System Number (r:1 w:0)
+	/// Proof: System Number (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: EVM PrecompileActivations (r:1 w:0)
+	/// Proof: EVM PrecompileActivations (max_values: None, max_size: Some(41), added: 2516, mode: MaxEncodedLen)
+	fn precompile_activation_lookup() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `4005`
+		// Execution time not measured yet, the base being the one of `withdraw`.
+		Weight::from_parts(1_696_000, 4005)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+	}
 }
@@ -120,6 +120,20 @@ impl PrecompileHandle for MockHandle {
 	}
 }
 
+/// Executes a precompile on the given input and returns the gas it recorded, whether it succeeded
+/// or not.
+pub fn precompile_gas_used<P: Precompile>(input: Vec<u8>) -> u64 {
+	let context: Context = Context {
+		address: Default::default(),
+		caller: Default::default(),
+		apparent_value: From::from(0),
+	};
+
+	let mut handle = MockHandle::new(input, None, context);
+	let _ = P::execute(&mut handle);
+	handle.gas_used
+}
+
 /// Tests a precompile against the ethereum consensus tests defined in the given file at filepath.
 /// The file is expected to be in JSON format and contain an array of test vectors, where each
 /// vector can be deserialized into an "EthConsensusTest".
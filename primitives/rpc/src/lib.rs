@@ -45,6 +45,19 @@ pub struct TransactionStatus {
 	pub logs_bloom: Bloom,
 }
 
+/// A withdrawal from the consensus layer credited to an account, EIP-4895.
+#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct Withdrawal {
+	/// Monotonically increasing index of the withdrawal.
+	pub index: u64,
+	/// Index of the validator withdrawing.
+	pub validator_index: u64,
+	/// Account credited.
+	pub address: Address,
+	/// Amount credited, in Gwei.
+	pub amount: u64,
+}
+
 pub trait RuntimeStorageOverride<B: BlockT, C>: Send + Sync {
 	fn is_enabled() -> bool;
 
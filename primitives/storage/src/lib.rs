@@ -32,6 +32,7 @@ pub mod constants {
 	pub const ETHEREUM_CURRENT_BLOCK: &[u8] = b"CurrentBlock";
 	pub const ETHEREUM_CURRENT_RECEIPTS: &[u8] = b"CurrentReceipts";
 	pub const ETHEREUM_CURRENT_TRANSACTION_STATUSES: &[u8] = b"CurrentTransactionStatuses";
+	pub const ETHEREUM_CURRENT_WITHDRAWALS: &[u8] = b"CurrentWithdrawals";
 
 	/// Pallet BaseFee storage items
 	pub const PALLET_BASE_FEE: &[u8] = b"BaseFee";
@@ -1,4 +1,4 @@
-use std::{collections::BTreeMap, str::FromStr};
+use std::str::FromStr;
 
 use hex_literal::hex;
 // Substrate
@@ -117,49 +117,37 @@ fn testnet_genesis(
 	chain_id: u64,
 	enable_manual_seal: bool,
 ) -> serde_json::Value {
-	let evm_accounts = {
-		let mut map = BTreeMap::new();
-		map.insert(
+	let evm_accounts = pallet_evm::build_evm_genesis(vec![
+		pallet_evm::EvmGenesisAccount {
 			// H160 address of Alice dev account
 			// Derived from SS58 (42 prefix) address
 			// SS58: 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY
 			// hex: 0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d
 			// Using the full hex key, truncating to the first 20 bytes (the first 40 hex chars)
-			H160::from_str("d43593c715fdd31c61141abd04a99fd6822c8558")
+			address: H160::from_str("d43593c715fdd31c61141abd04a99fd6822c8558")
 				.expect("internal H160 is valid; qed"),
-			fp_evm::GenesisAccount {
-				balance: U256::from_str("0xffffffffffffffffffffffffffffffff")
-					.expect("internal U256 is valid; qed"),
-				code: Default::default(),
-				nonce: Default::default(),
-				storage: Default::default(),
-			},
-		);
-		map.insert(
+			balance: U256::from_str("0xffffffffffffffffffffffffffffffff")
+				.expect("internal U256 is valid; qed"),
+			..Default::default()
+		},
+		pallet_evm::EvmGenesisAccount {
 			// H160 address of CI test runner account
-			H160::from_str("6be02d1d3665660d22ff9624b7be0551ee1ac91b")
+			address: H160::from_str("6be02d1d3665660d22ff9624b7be0551ee1ac91b")
 				.expect("internal H160 is valid; qed"),
-			fp_evm::GenesisAccount {
-				balance: U256::from_str("0xffffffffffffffffffffffffffffffff")
-					.expect("internal U256 is valid; qed"),
-				code: Default::default(),
-				nonce: Default::default(),
-				storage: Default::default(),
-			},
-		);
-		map.insert(
+			balance: U256::from_str("0xffffffffffffffffffffffffffffffff")
+				.expect("internal U256 is valid; qed"),
+			..Default::default()
+		},
+		pallet_evm::EvmGenesisAccount {
 			// H160 address for benchmark usage
-			H160::from_str("1000000000000000000000000000000000000001")
+			address: H160::from_str("1000000000000000000000000000000000000001")
 				.expect("internal H160 is valid; qed"),
-			fp_evm::GenesisAccount {
-				nonce: U256::from(1),
-				balance: U256::from(1_000_000_000_000_000_000_000_000u128),
-				storage: Default::default(),
-				code: vec![0x00],
-			},
-		);
-		map
-	};
+			nonce: U256::from(1),
+			balance: U256::from(1_000_000_000_000_000_000_000_000u128),
+			code: vec![0x00],
+			..Default::default()
+		},
+	]);
 
 	serde_json::json!({
 		"sudo": { "key": Some(sudo_key) },
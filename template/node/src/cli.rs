@@ -65,4 +65,10 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	FrontierDb(fc_cli::FrontierDbCmd),
+
+	/// Check the consistency of the Frontier db mappings.
+	FrontierDbVerify(fc_cli::FrontierDbVerifyCmd),
+
+	/// Build the contract creation index of an existing chain.
+	ContractCreationBackfill(fc_cli::ContractCreationBackfillCmd),
 }
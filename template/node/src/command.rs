@@ -1,13 +1,17 @@
+use std::sync::Arc;
+
 use futures::TryFutureExt;
 // Substrate
 use sc_cli::{ChainSpec, SubstrateCli};
 use sc_service::DatabaseSource;
 // Frontier
 use fc_db::kv::frontier_database_dir;
+use frontier_template_runtime::opaque::Block;
 
 use crate::{
 	chain_spec,
 	cli::{Cli, Subcommand},
+	eth::StorageOverrideHandler,
 	service::{self, db_config_dir},
 };
 
@@ -224,6 +228,36 @@ pub fn run() -> sc_cli::Result<()> {
 				cmd.run(client, frontier_backend)
 			})
 		}
+		Some(Subcommand::FrontierDbVerify(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|mut config| {
+				let (client, _, _, _, frontier_backend) =
+					service::new_chain_ops(&mut config, &cli.eth)?;
+				let frontier_backend = match frontier_backend {
+					fc_db::Backend::KeyValue(kv) => kv,
+					_ => panic!("Only fc_db::Backend::KeyValue supported"),
+				};
+				let storage_override = Arc::new(
+					StorageOverrideHandler::<Block, _, service::Backend>::new(client.clone()),
+				);
+				cmd.run(client, storage_override, frontier_backend)
+			})
+		}
+		Some(Subcommand::ContractCreationBackfill(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|mut config| {
+				let (client, _, _, _, frontier_backend) =
+					service::new_chain_ops(&mut config, &cli.eth)?;
+				let frontier_backend = match frontier_backend {
+					fc_db::Backend::KeyValue(kv) => kv,
+					_ => panic!("Only fc_db::Backend::KeyValue supported"),
+				};
+				let storage_override = Arc::new(
+					StorageOverrideHandler::<Block, _, service::Backend>::new(client.clone()),
+				);
+				cmd.run(client, storage_override, frontier_backend)
+			})
+		}
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
 			runner.run_node_until_exit(|config| async move {
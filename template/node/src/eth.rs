@@ -12,11 +12,11 @@ use sc_executor::HostFunctions;
 use sc_network_sync::SyncingService;
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
 use sp_api::ConstructRuntimeApi;
-use sp_core::H256;
+use sp_core::{H160, H256};
 use sp_runtime::traits::Block as BlockT;
 // Frontier
 pub use fc_consensus::FrontierBlockImport;
-use fc_rpc::EthTask;
+use fc_rpc::{EthTask, FeeOracleConfig};
 pub use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
 pub use fc_storage::{StorageOverride, StorageOverrideHandler};
 
@@ -50,9 +50,37 @@ pub struct EthConfiguration {
 	#[arg(long, default_value = "2048")]
 	pub fee_history_limit: u64,
 
+	/// Transactions with a lower effective tip are left out of the fee history rewards and
+	/// of the suggested priority fee.
+	#[arg(long, default_value = "0")]
+	pub gpo_ignore_price: u64,
+
+	/// Comma separated senders whose transactions are left out of the fee history rewards
+	/// and of the suggested priority fee, e.g. system or sponsored transaction senders.
+	#[arg(long, value_delimiter = ',')]
+	pub gpo_ignored_senders: Vec<H160>,
+
+	/// Maximum number of missed canonical blocks re-notified to a `newHeads` subscriber
+	/// after a reorg or a gap in notifications.
+	#[arg(long, default_value = "10")]
+	pub new_heads_backfill_limit: u32,
+
+	/// Maximum number of notifications buffered per resumable subscription, 0 disables
+	/// resumable subscriptions.
+	#[arg(long, default_value = "128")]
+	pub resumable_subscription_buffer: usize,
+
+	/// Seconds a resumable subscription is kept after its subscriber disconnected.
+	#[arg(long, default_value = "60")]
+	pub resumable_subscription_ttl: u64,
+
 	#[arg(long)]
 	pub enable_dev_signer: bool,
 
+	/// Network id returned by `net_version`, defaults to the runtime chain id.
+	#[arg(long)]
+	pub network_id: Option<u64>,
+
 	/// The dynamic-fee pallet target gas price set by block author
 	#[arg(long, default_value = "1")]
 	pub target_gas_price: u64,
@@ -62,6 +90,58 @@ pub struct EthConfiguration {
 	#[arg(long, default_value = "10")]
 	pub execute_gas_limit_multiplier: u64,
 
+	/// Milliseconds after which an execution, logs or debug request is logged as slow.
+	#[arg(long, default_value = "5000")]
+	pub rpc_slow_request_threshold: u64,
+
+	/// Raw transactions per second each sender can submit through eth_sendRawTransaction,
+	/// 0 disables the limit.
+	#[arg(long, default_value = "0")]
+	pub rpc_tx_sender_rate: u32,
+
+	/// Raw transactions each sender can submit at once before being rate limited.
+	#[arg(long, default_value = "16")]
+	pub rpc_tx_sender_burst: u32,
+
+	/// Raw transactions per second each connection can submit through eth_sendRawTransaction,
+	/// 0 disables the limit. Only enforced by the `--frontier-rpc-port` server.
+	#[arg(long, default_value = "0", requires = "frontier_rpc_port")]
+	pub rpc_tx_connection_rate: u32,
+
+	/// Raw transactions each connection can submit at once before being rate limited.
+	#[arg(long, default_value = "16")]
+	pub rpc_tx_connection_burst: u32,
+
+	/// Number of recently submitted transaction hashes whose resubmission is rejected
+	/// without validation, 0 disables it.
+	#[arg(long, default_value = "4096")]
+	pub rpc_known_tx_cache: u32,
+
+	/// Threads used to recover the senders of the transactions returned by the block and
+	/// pool queries, 1 recovers them on the RPC thread.
+	#[arg(long, default_value = "4")]
+	pub rpc_sender_recovery_threads: usize,
+
+	/// Serve the Frontier RPC methods on a server of their own on this port of the RPC
+	/// interface, rather than on the main RPC server which keeps the Substrate methods. Only
+	/// this server traces the requests under the id of their `X-Request-Id` header.
+	#[arg(long, value_name = "PORT")]
+	pub frontier_rpc_port: Option<u16>,
+
+	/// Log the raw requests and responses of the `--frontier-rpc-port` server at `trace` level
+	/// of the `rpc-log` target, which `FRONTIER_RPC_LOG=trace` also enables.
+	#[arg(long, requires = "frontier_rpc_port")]
+	pub rpc_log: bool,
+
+	/// Origins allowed to call the methods matching a name or a prefix, overriding `--rpc-cors`
+	/// for them, like `debug_*=localhost`. Only enforced by the `--frontier-rpc-port` server.
+	#[arg(
+		long = "rpc-cors-method",
+		value_name = "METHOD=ORIGINS",
+		requires = "frontier_rpc_port"
+	)]
+	pub rpc_cors_methods: Vec<fc_rpc::MethodCorsConfig>,
+
 	/// Size in bytes of the LRU cache for block data.
 	#[arg(long, default_value = "50")]
 	pub eth_log_block_cache: usize,
@@ -74,6 +154,11 @@ pub struct EthConfiguration {
 	#[arg(long, value_enum, ignore_case = true, default_value_t = BackendType::default())]
 	pub frontier_backend_type: BackendType,
 
+	/// Disable the chain-wide log bloom index of the KeyValue frontier backend, used to
+	/// speed up `eth_getLogs` over large block ranges.
+	#[arg(long)]
+	pub disable_bloom_index: bool,
+
 	// Sets the SQL backend's pool size.
 	#[arg(long, default_value = "100")]
 	pub frontier_sql_backend_pool_size: u32,
@@ -134,12 +219,14 @@ pub async fn spawn_frontier_tasks<B, RA, HF>(
 	storage_override: Arc<dyn StorageOverride<B>>,
 	fee_history_cache: FeeHistoryCache,
 	fee_history_cache_limit: FeeHistoryCacheLimit,
+	fee_oracle_config: FeeOracleConfig,
 	sync: Arc<SyncingService<B>>,
 	pubsub_notification_sinks: Arc<
 		fc_mapping_sync::EthereumBlockNotificationSinks<
 			fc_mapping_sync::EthereumBlockNotification<B>,
 		>,
 	>,
+	disable_bloom_index: bool,
 ) where
 	B: BlockT<Hash = H256>,
 	RA: ConstructRuntimeApi<B, FullClient<B, RA, HF>>,
@@ -168,6 +255,36 @@ pub async fn spawn_frontier_tasks<B, RA, HF>(
 				)
 				.for_each(|()| future::ready(())),
 			);
+
+			if disable_bloom_index {
+				// A stale index would miss the logs of blocks imported meanwhile, rebuild it
+				// from scratch once enabled again.
+				if let Err(e) = b.bloom_bits().reset() {
+					log::warn!("Failed to reset the bloom index: {e}");
+				}
+			} else {
+				task_manager.spawn_essential_handle().spawn_blocking(
+					"frontier-bloom-bits-worker",
+					Some("frontier"),
+					fc_mapping_sync::kv::BloomBitsWorker::run(
+						client.clone(),
+						storage_override.clone(),
+						b.clone(),
+						client.import_notification_stream(),
+					),
+				);
+			}
+
+			task_manager.spawn_essential_handle().spawn_blocking(
+				"frontier-contract-creation-worker",
+				Some("frontier"),
+				fc_mapping_sync::kv::ContractCreationWorker::run(
+					client.clone(),
+					storage_override.clone(),
+					b.clone(),
+					client.import_notification_stream(),
+				),
+			);
 		}
 		fc_db::Backend::Sql(b) => {
 			task_manager.spawn_essential_handle().spawn_blocking(
@@ -210,6 +327,7 @@ pub async fn spawn_frontier_tasks<B, RA, HF>(
 			storage_override,
 			fee_history_cache,
 			fee_history_cache_limit,
+			fee_oracle_config,
 		),
 	);
 }
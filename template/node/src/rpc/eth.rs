@@ -1,10 +1,10 @@
-use std::{collections::BTreeMap, sync::Arc};
+use std::{collections::BTreeMap, marker::PhantomData, sync::Arc, time::Duration};
 
 use jsonrpsee::RpcModule;
 // Substrate
 use sc_client_api::{
 	backend::{Backend, StorageProvider},
-	client::BlockchainEvents,
+	client::{BlockBackend, BlockchainEvents},
 	AuxStore, UsageProvider,
 };
 use sc_network::service::traits::NetworkService;
@@ -16,14 +16,24 @@ use sp_api::{CallApiAt, ProvideRuntimeApi};
 use sp_block_builder::BlockBuilder as BlockBuilderApi;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use sp_consensus_aura::{sr25519::AuthorityId as AuraId, AuraApi};
-use sp_core::H256;
+use sp_core::{
+	crypto::{key_types::AURA, ByteArray},
+	traits::SpawnNamed,
+	H160, H256,
+};
 use sp_inherents::CreateInherentDataProviders;
+use sp_keystore::KeystorePtr;
 use sp_runtime::traits::Block as BlockT;
 // Frontier
-pub use fc_rpc::{EthBlockDataCacheTask, EthConfig};
+pub use fc_rpc::{
+	EthBlockDataCacheTask, EthConfig, ResumableSubscriptionConfig, SenderRecovery,
+	SubmissionLimiter,
+};
 pub use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
 use fc_storage::StorageOverride;
 use fp_rpc::{ConvertTransaction, ConvertTransactionRuntimeApi, EthereumRuntimeRPCApi};
+use frontier_template_runtime::AuraIdMapping;
+use pallet_evm::AccountIdMapping;
 
 /// Extra dependencies for Ethereum compatibility.
 pub struct EthDeps<B: BlockT, C, P, A: ChainApi, CT, CIDP> {
@@ -35,8 +45,8 @@ pub struct EthDeps<B: BlockT, C, P, A: ChainApi, CT, CIDP> {
 	pub graph: Arc<Pool<A>>,
 	/// Ethereum transaction converter.
 	pub converter: Option<CT>,
-	/// The Node authority flag
-	pub is_authority: bool,
+	/// Keystore of the node authority keys, for `eth_coinbase`.
+	pub keystore: Option<KeystorePtr>,
 	/// Whether to enable dev signer
 	pub enable_dev_signer: bool,
 	/// Network service
@@ -53,6 +63,12 @@ pub struct EthDeps<B: BlockT, C, P, A: ChainApi, CT, CIDP> {
 	pub filter_pool: Option<FilterPool>,
 	/// Maximum number of logs in a query.
 	pub max_past_logs: u32,
+	/// Maximum number of missed blocks re-notified to a `newHeads` subscriber.
+	pub new_heads_backfill_limit: u32,
+	/// Buffer size and lifetime of the resumable subscriptions.
+	pub resumable_subscription_config: ResumableSubscriptionConfig,
+	/// Network id returned by `net_version`, the runtime chain id if unset.
+	pub network_id: Option<u64>,
 	/// Fee history cache.
 	pub fee_history_cache: FeeHistoryCache,
 	/// Maximum fee history cache size.
@@ -60,12 +76,55 @@ pub struct EthDeps<B: BlockT, C, P, A: ChainApi, CT, CIDP> {
 	/// Maximum allowed gas limit will be ` block.gas_limit * execute_gas_limit_multiplier` when
 	/// using eth_call/eth_estimateGas.
 	pub execute_gas_limit_multiplier: u64,
+	/// Execution, logs and debug requests taking longer are logged as slow.
+	pub slow_request_threshold: Duration,
+	/// Throttles eth_sendRawTransaction.
+	pub submission_limiter: Arc<SubmissionLimiter>,
+	/// Recovers the senders of block and pool transactions.
+	pub sender_recovery: SenderRecovery,
 	/// Mandated parent hashes for a given block hash.
 	pub forced_parent_hashes: Option<BTreeMap<H256, H256>>,
 	/// Something that can create the inherent data providers for pending state
 	pub pending_create_inherent_data_providers: CIDP,
 }
 
+/// `eth_coinbase` of an Aura authority, the address of its key in the current authority set as
+/// mapped by the `FindAuthor` of the runtime.
+pub struct AuraCoinbase<B, C> {
+	client: Arc<C>,
+	keystore: KeystorePtr,
+	_marker: PhantomData<B>,
+}
+
+impl<B, C> AuraCoinbase<B, C> {
+	pub fn new(client: Arc<C>, keystore: KeystorePtr) -> Self {
+		Self {
+			client,
+			keystore,
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<B, C> fc_rpc::CoinbaseProvider for AuraCoinbase<B, C>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync,
+	C::Api: AuraApi<B, AuraId>,
+{
+	fn coinbase(&self) -> Option<H160> {
+		let authorities = self
+			.client
+			.runtime_api()
+			.authorities(self.client.info().best_hash)
+			.ok()?;
+		authorities
+			.into_iter()
+			.find(|authority| self.keystore.has_keys(&[(authority.to_raw_vec(), AURA)]))
+			.map(AuraIdMapping::into_address)
+	}
+}
+
 /// Instantiate Ethereum-compatible RPC extensions.
 pub fn create_eth<B, C, BE, P, A, CT, CIDP, EC>(
 	mut io: RpcModule<()>,
@@ -85,7 +144,12 @@ where
 		+ ConvertTransactionRuntimeApi<B>
 		+ EthereumRuntimeRPCApi<B>,
 	C: HeaderBackend<B> + HeaderMetadata<B, Error = BlockChainError>,
-	C: BlockchainEvents<B> + AuxStore + UsageProvider<B> + StorageProvider<B, BE> + 'static,
+	C: BlockchainEvents<B>
+		+ BlockBackend<B>
+		+ AuxStore
+		+ UsageProvider<B>
+		+ StorageProvider<B, BE>
+		+ 'static,
 	BE: Backend<B> + 'static,
 	P: TransactionPool<Block = B> + 'static,
 	A: ChainApi<Block = B> + 'static,
@@ -94,9 +158,10 @@ where
 	EC: EthConfig<B, C>,
 {
 	use fc_rpc::{
-		pending::AuraConsensusDataProvider, Debug, DebugApiServer, Eth, EthApiServer, EthDevSigner,
-		EthFilter, EthFilterApiServer, EthPubSub, EthPubSubApiServer, EthSigner, Net, NetApiServer,
-		Web3, Web3ApiServer,
+		pending::AuraConsensusDataProvider, CoinbaseProvider, Debug, DebugApiServer, Dev,
+		DevApiServer, Eth, EthApiServer, EthDevSigner, EthFilter, EthFilterApiServer, EthPubSub,
+		EthPubSubApiServer, EthSigner, Frontier, FrontierApiServer, Net, NetApiServer, Personal,
+		PersonalApiServer, RequestTracer, Web3, Web3ApiServer,
 	};
 	#[cfg(feature = "txpool")]
 	use fc_rpc::{TxPool, TxPoolApiServer};
@@ -106,7 +171,7 @@ where
 		pool,
 		graph,
 		converter,
-		is_authority,
+		keystore,
 		enable_dev_signer,
 		network,
 		sync,
@@ -115,9 +180,15 @@ where
 		block_data_cache,
 		filter_pool,
 		max_past_logs,
+		new_heads_backfill_limit,
+		resumable_subscription_config,
+		network_id,
 		fee_history_cache,
 		fee_history_cache_limit,
 		execute_gas_limit_multiplier,
+		slow_request_threshold,
+		submission_limiter,
+		sender_recovery,
 		forced_parent_hashes,
 		pending_create_inherent_data_providers,
 	} = deps;
@@ -126,6 +197,7 @@ where
 	if enable_dev_signer {
 		signers.push(Box::new(EthDevSigner::new()) as Box<dyn EthSigner>);
 	}
+	let request_tracer = RequestTracer::new(slow_request_threshold);
 
 	io.merge(
 		Eth::<B, C, P, CT, BE, A, CIDP, EC>::new(
@@ -137,11 +209,16 @@ where
 			signers,
 			storage_override.clone(),
 			frontier_backend.clone(),
-			is_authority,
+			keystore.map(|keystore| {
+				Box::new(AuraCoinbase::new(client.clone(), keystore)) as Box<dyn CoinbaseProvider>
+			}),
 			block_data_cache.clone(),
 			fee_history_cache,
 			fee_history_cache_limit,
 			execute_gas_limit_multiplier,
+			request_tracer.clone(),
+			submission_limiter,
+			sender_recovery.clone(),
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			Some(Box::new(AuraConsensusDataProvider::new(client.clone()))),
@@ -151,18 +228,21 @@ where
 	)?;
 
 	if let Some(filter_pool) = filter_pool {
-		io.merge(
+		let eth_filter = || {
 			EthFilter::new(
 				client.clone(),
 				frontier_backend.clone(),
 				graph.clone(),
-				filter_pool,
+				filter_pool.clone(),
 				500_usize, // max stored filters
 				max_past_logs,
 				block_data_cache.clone(),
+				request_tracer.clone(),
 			)
-			.into_rpc(),
-		)?;
+		};
+		io.merge(eth_filter().into_rpc())?;
+		#[cfg(feature = "logs-pagination")]
+		io.merge(fc_rpc::EthLogsPageApiServer::into_rpc(eth_filter()))?;
 	}
 
 	io.merge(
@@ -170,37 +250,57 @@ where
 			pool,
 			client.clone(),
 			sync,
-			subscription_task_executor,
+			subscription_task_executor.clone(),
 			storage_override.clone(),
 			pubsub_notification_sinks,
+			new_heads_backfill_limit,
+			resumable_subscription_config,
 		)
 		.into_rpc(),
 	)?;
 
-	io.merge(
-		Net::new(
-			client.clone(),
-			network,
-			// Whether to format the `peer_count` response as Hex (default) or not.
-			true,
-		)
-		.into_rpc(),
-	)?;
+	let net = Net::new(
+		client.clone(),
+		network,
+		// Whether to format the `peer_count` response as Hex (default) or not.
+		true,
+		network_id,
+	);
+	subscription_task_executor.spawn(
+		"frontier-net-listening",
+		Some("rpc"),
+		Box::pin(net.listening_task()),
+	);
+	io.merge(net.into_rpc())?;
 
 	io.merge(Web3::new(client.clone()).into_rpc())?;
 
+	io.merge(Frontier::new(client.clone(), frontier_backend.clone()).into_rpc())?;
+
 	io.merge(
 		Debug::new(
 			client.clone(),
 			frontier_backend,
 			storage_override,
 			block_data_cache,
+			request_tracer,
 		)
 		.into_rpc(),
 	)?;
 
+	if enable_dev_signer {
+		io.merge(Dev::new(vec![Box::new(EthDevSigner::new())]).into_rpc())?;
+	}
+
+	// Out of dev mode, `personal_sign` has no account to sign with.
+	let mut personal_signers = Vec::new();
+	if enable_dev_signer {
+		personal_signers.push(Box::new(EthDevSigner::new()) as Box<dyn EthSigner>);
+	}
+	io.merge(Personal::new(personal_signers).into_rpc())?;
+
 	#[cfg(feature = "txpool")]
-	io.merge(TxPool::new(client, graph).into_rpc())?;
+	io.merge(TxPool::new(client, graph, sender_recovery).into_rpc())?;
 
 	Ok(io)
 }
@@ -7,7 +7,7 @@ use jsonrpsee::RpcModule;
 // Substrate
 use sc_client_api::{
 	backend::{Backend, StorageProvider},
-	client::BlockchainEvents,
+	client::{BlockBackend, BlockchainEvents},
 	AuxStore, UsageProvider,
 };
 use sc_consensus_manual_seal::rpc::EngineCommand;
@@ -24,7 +24,11 @@ use sp_runtime::traits::Block as BlockT;
 use frontier_template_runtime::{AccountId, Balance, Hash, Nonce};
 
 mod eth;
-pub use self::eth::{create_eth, EthDeps};
+mod server;
+pub use self::{
+	eth::{create_eth, EthDeps},
+	server::{ensure_overrides_enforced, spawn_frontier_server, FrontierServerConfig, RpcBuilder},
+};
 
 /// Full client dependencies.
 pub struct FullDeps<B: BlockT, C, P, A: ChainApi, CT, CIDP> {
@@ -73,7 +77,7 @@ where
 	C::Api: fp_rpc::ConvertTransactionRuntimeApi<B>,
 	C::Api: fp_rpc::EthereumRuntimeRPCApi<B>,
 	C: HeaderBackend<B> + HeaderMetadata<B, Error = BlockChainError> + 'static,
-	C: BlockchainEvents<B> + AuxStore + UsageProvider<B> + StorageProvider<B, BE>,
+	C: BlockchainEvents<B> + BlockBackend<B> + AuxStore + UsageProvider<B> + StorageProvider<B, BE>,
 	BE: Backend<B> + 'static,
 	P: TransactionPool<Block = B> + 'static,
 	A: ChainApi<Block = B> + 'static,
@@ -105,12 +109,17 @@ where
 	}
 
 	// Ethereum compatibility RPCs
-	let io = create_eth::<_, _, _, _, _, _, _, DefaultEthConfig<C, BE>>(
+	let mut io = create_eth::<_, _, _, _, _, _, _, DefaultEthConfig<C, BE>>(
 		io,
 		eth,
 		subscription_task_executor,
 		pubsub_notification_sinks,
 	)?;
 
+	// Lists the methods merged above. Those which the service adds afterwards, like the
+	// `chain`, `state` and `author` namespaces, are not part of it.
+	let modules = fc_rpc::Modules::new(io.method_names());
+	io.merge(fc_rpc::ModulesApiServer::into_rpc(modules))?;
+
 	Ok(io)
 }
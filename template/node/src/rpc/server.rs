@@ -0,0 +1,143 @@
+//! Server of the Frontier RPC methods, stacking the middleware the server of `sc-service` does
+//! not take.
+
+use std::{
+	net::{Ipv4Addr, SocketAddr},
+	sync::Arc,
+};
+
+use hyper::{header, Method};
+use jsonrpsee::{
+	server::{middleware::rpc::RpcServiceBuilder, Server},
+	RpcModule,
+};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+// Substrate
+use sc_rpc::SubscriptionTaskExecutor;
+use sc_rpc_api::DenyUnsafe;
+use sc_service::{config::RpcMethods, error::Error as ServiceError, Configuration, TaskManager};
+// Frontier
+use fc_rpc::{
+	ConnectionLimitLayer, CorsConfig, LoggingLayer, MethodCorsConfig, MethodCorsLayer,
+	RequestIdLayer,
+};
+
+const MEGABYTE: u32 = 1024 * 1024;
+
+/// Builds the RPC methods of the node, as given to `sc_service::spawn_tasks`.
+pub type RpcBuilder =
+	Box<dyn Fn(DenyUnsafe, SubscriptionTaskExecutor) -> Result<RpcModule<()>, ServiceError>>;
+
+/// Settings of the server of the Frontier RPC methods.
+pub struct FrontierServerConfig {
+	/// Port on the interface of the main RPC server.
+	pub port: u16,
+	/// Overrides of the `--rpc-cors` origins for some methods.
+	pub cors_methods: Vec<MethodCorsConfig>,
+	/// Limit of the transaction submissions of each connection.
+	pub connection_limit: ConnectionLimitLayer,
+	/// Logging of the raw requests and responses.
+	pub logging: LoggingLayer,
+}
+
+/// Serve the RPC built by `rpc_builder` on its own server, with the limits of the main RPC
+/// server, the origins of `--rpc-cors` overridden by the methods of `server_config`, the
+/// submissions of each connection limited and the requests optionally logged.
+///
+/// The methods must not be served by the main RPC server as well, which would let any origin
+/// call them, see [`ensure_overrides_enforced`].
+pub async fn spawn_frontier_server(
+	config: &Configuration,
+	server_config: FrontierServerConfig,
+	rpc_builder: &RpcBuilder,
+	task_manager: &TaskManager,
+) -> Result<(), ServiceError> {
+	let FrontierServerConfig {
+		port,
+		cors_methods,
+		connection_limit,
+		logging,
+	} = server_config;
+	let ip = config
+		.rpc_addr
+		.map_or(Ipv4Addr::LOCALHOST.into(), |addr| addr.ip());
+	let addr = SocketAddr::new(ip, port);
+	let deny_unsafe = match config.rpc_methods {
+		RpcMethods::Safe => DenyUnsafe::Yes,
+		RpcMethods::Unsafe => DenyUnsafe::No,
+		RpcMethods::Auto if ip.is_loopback() => DenyUnsafe::No,
+		RpcMethods::Auto => DenyUnsafe::Yes,
+	};
+	let module = rpc_builder(deny_unsafe, Arc::new(task_manager.spawn_handle()))?;
+
+	let cors_config = CorsConfig {
+		allowed_origins: config.rpc_cors.clone(),
+		methods: cors_methods,
+	};
+	let allowed_origins = cors_config.clone();
+	// Headers for the origins some rule allows, the methods they call being checked after.
+	let cors = CorsLayer::new()
+		.allow_methods([Method::POST])
+		.allow_headers([header::CONTENT_TYPE])
+		.allow_origin(AllowOrigin::predicate(move |origin, _| {
+			origin
+				.to_str()
+				.map_or(false, |origin| allowed_origins.allows_any(origin))
+		}));
+	let max_request_size = config.rpc_max_request_size.saturating_mul(MEGABYTE);
+	let middleware = tower::ServiceBuilder::new()
+		.layer(cors)
+		.layer(RequestIdLayer)
+		.layer(MethodCorsLayer::new(cors_config, max_request_size as usize));
+	let rpc_middleware = RpcServiceBuilder::new()
+		.layer(logging)
+		.layer(connection_limit);
+
+	let server = Server::builder()
+		.max_request_body_size(max_request_size)
+		.max_response_body_size(config.rpc_max_response_size.saturating_mul(MEGABYTE))
+		.max_connections(config.rpc_max_connections)
+		.max_subscriptions_per_connection(config.rpc_max_subs_per_conn)
+		.set_id_provider(fc_rpc::EthereumSubIdProvider)
+		.set_http_middleware(middleware)
+		.set_rpc_middleware(rpc_middleware)
+		.build(addr)
+		.await
+		.map_err(|e| ServiceError::Application(e.into()))?;
+	log::info!(
+		"Running Frontier JSON-RPC server on {}",
+		server
+			.local_addr()
+			.map_err(|e| ServiceError::Application(e.into()))?
+	);
+
+	let handle = server.start(module);
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-rpc-server",
+		Some("rpc"),
+		handle.stopped(),
+	);
+	Ok(())
+}
+
+/// Fails if the main RPC server serves a method matching one of the `cors_methods` overrides,
+/// which only the Frontier server enforces.
+pub fn ensure_overrides_enforced(
+	cors_methods: &[MethodCorsConfig],
+	main_module: &RpcModule<()>,
+) -> Result<(), ServiceError> {
+	let overrides = CorsConfig {
+		allowed_origins: None,
+		methods: cors_methods.to_vec(),
+	};
+	match main_module
+		.method_names()
+		.find(|method| overrides.overrides(method))
+	{
+		Some(method) => Err(ServiceError::Other(format!(
+			"{method} matches a --rpc-cors-method override but is served by the main RPC server, \
+			 which does not enforce them"
+		))),
+		None => Ok(()),
+	}
+}
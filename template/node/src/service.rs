@@ -401,16 +401,37 @@ where
 	// for ethereum-compatibility rpc.
 	config.rpc_id_provider = Some(Box::new(fc_rpc::EthereumSubIdProvider));
 
-	let rpc_builder = {
+	let submission_limiter = Arc::new(fc_rpc::SubmissionLimiter::new(
+		fc_rpc::SubmissionLimits {
+			sender_rate: eth_config.rpc_tx_sender_rate,
+			sender_burst: eth_config.rpc_tx_sender_burst,
+			known_transactions: eth_config.rpc_known_tx_cache,
+			connection_rate: eth_config.rpc_tx_connection_rate,
+			connection_burst: eth_config.rpc_tx_connection_burst,
+		},
+		prometheus_registry.clone(),
+	));
+
+	let rpc_builder: crate::rpc::RpcBuilder = {
 		let client = client.clone();
+		let submission_limiter = submission_limiter.clone();
 		let pool = transaction_pool.clone();
 		let network = network.clone();
 		let sync_service = sync_service.clone();
 
 		let is_authority = role.is_authority();
+		let keystore = keystore_container.keystore();
 		let enable_dev_signer = eth_config.enable_dev_signer;
 		let max_past_logs = eth_config.max_past_logs;
+		let new_heads_backfill_limit = eth_config.new_heads_backfill_limit;
+		let resumable_subscription_config = fc_rpc::ResumableSubscriptionConfig {
+			buffer_size: eth_config.resumable_subscription_buffer,
+			ttl: std::time::Duration::from_secs(eth_config.resumable_subscription_ttl),
+		};
+		let network_id = eth_config.network_id;
 		let execute_gas_limit_multiplier = eth_config.execute_gas_limit_multiplier;
+		let slow_request_threshold =
+			std::time::Duration::from_millis(eth_config.rpc_slow_request_threshold);
 		let filter_pool = filter_pool.clone();
 		let frontier_backend = frontier_backend.clone();
 		let pubsub_notification_sinks = pubsub_notification_sinks.clone();
@@ -423,6 +444,7 @@ where
 			eth_config.eth_statuses_cache,
 			prometheus_registry.clone(),
 		));
+		let sender_recovery = fc_rpc::SenderRecovery::new(eth_config.rpc_sender_recovery_threads);
 
 		let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
 		let target_gas_price = eth_config.target_gas_price;
@@ -444,7 +466,7 @@ where
 				pool: pool.clone(),
 				graph: pool.pool().clone(),
 				converter: Some(TransactionConverter::<B>::default()),
-				is_authority,
+				keystore: is_authority.then(|| keystore.clone()),
 				enable_dev_signer,
 				network: network.clone(),
 				sync: sync_service.clone(),
@@ -456,9 +478,15 @@ where
 				block_data_cache: block_data_cache.clone(),
 				filter_pool: filter_pool.clone(),
 				max_past_logs,
+				new_heads_backfill_limit,
+				resumable_subscription_config,
+				network_id,
 				fee_history_cache: fee_history_cache.clone(),
 				fee_history_cache_limit,
 				execute_gas_limit_multiplier,
+				slow_request_threshold,
+				submission_limiter: submission_limiter.clone(),
+				sender_recovery: sender_recovery.clone(),
 				forced_parent_hashes: None,
 				pending_create_inherent_data_providers,
 			};
@@ -482,7 +510,27 @@ where
 		})
 	};
 
-	let _rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+	let rpc_builder = match eth_config.frontier_rpc_port {
+		Some(port) => {
+			let server_config = crate::rpc::FrontierServerConfig {
+				port,
+				cors_methods: eth_config.rpc_cors_methods.clone(),
+				connection_limit: submission_limiter.connection_layer(),
+				logging: if eth_config.rpc_log {
+					fc_rpc::LoggingLayer::new(true)
+				} else {
+					fc_rpc::LoggingLayer::from_env()
+				},
+			};
+			crate::rpc::spawn_frontier_server(&config, server_config, &rpc_builder, &task_manager)
+				.await?;
+			// The main RPC server only keeps the Substrate methods.
+			Box::new(|_, _| Ok(jsonrpsee::RpcModule::new(()))) as crate::rpc::RpcBuilder
+		}
+		None => rpc_builder,
+	};
+
+	let rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
 		config,
 		client: client.clone(),
 		backend: backend.clone(),
@@ -496,6 +544,7 @@ where
 		sync_service: sync_service.clone(),
 		telemetry: telemetry.as_mut(),
 	})?;
+	crate::rpc::ensure_overrides_enforced(&eth_config.rpc_cors_methods, &rpc_handlers.handle())?;
 
 	spawn_frontier_tasks(
 		&task_manager,
@@ -506,8 +555,13 @@ where
 		storage_override,
 		fee_history_cache,
 		fee_history_cache_limit,
+		fc_rpc::FeeOracleConfig {
+			ignore_price: eth_config.gpo_ignore_price,
+			ignored_senders: eth_config.gpo_ignored_senders.iter().cloned().collect(),
+		},
 		sync_service.clone(),
 		pubsub_notification_sinks,
+		eth_config.disable_bloom_index,
 	)
 	.await;
 
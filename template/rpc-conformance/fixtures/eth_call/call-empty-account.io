@@ -0,0 +1,3 @@
+// Calls an account without code.
+>> {"jsonrpc":"2.0","id":1,"method":"eth_call","params":[{"to":"0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa","data":"0xdeadbeef"},"latest"]}
+<< {"jsonrpc":"2.0","id":1,"result":"0x"}
@@ -0,0 +1,3 @@
+// Calls the identity precompile, which returns its input.
+>> {"jsonrpc":"2.0","id":1,"method":"eth_call","params":[{"to":"0x0000000000000000000000000000000000000004","data":"0x1234"},"latest"]}
+<< {"jsonrpc":"2.0","id":1,"result":"0x1234"}
@@ -0,0 +1,3 @@
+// Returns the chain id of the dev chain.
+>> {"jsonrpc":"2.0","id":1,"method":"eth_chainId","params":[]}
+<< {"jsonrpc":"2.0","id":1,"result":"${chainId}"}
@@ -0,0 +1,3 @@
+// Gets a block with the zero hash.
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getBlockByHash","params":["0x0000000000000000000000000000000000000000000000000000000000000000",false]}
+<< {"jsonrpc":"2.0","id":1,"result":null}
@@ -0,0 +1,3 @@
+// Requests a block with a malformed hash.
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getBlockByHash","params":["0x1234",false]}
+<< {"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"<string>","data":"<string>"}}
@@ -0,0 +1,3 @@
+// Gets the genesis block by hash.
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getBlockByHash","params":["${genesisHash}",false]}
+<< {"jsonrpc":"2.0","id":1,"result":{"baseFeePerGas":"<quantity>","difficulty":"0x0","extraData":"0x","gasLimit":"${genesisGasLimit}","gasUsed":"0x0","hash":"${genesisHash}","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","miner":"0x0000000000000000000000000000000000000000","mixHash":"<hash>","nonce":"0x0000000000000000","number":"0x0","parentHash":"<hash>","receiptsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421","sha3Uncles":"0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347","size":"${genesisSize}","stateRoot":"<hash>","timestamp":"0x0","transactions":[],"transactionsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421","uncles":[],"withdrawals":null,"withdrawalsRoot":null}}
@@ -0,0 +1,3 @@
+// Requests a block without the full transactions flag.
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getBlockByNumber","params":["0x0"]}
+<< {"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"<string>","data":"<string>"}}
@@ -0,0 +1,3 @@
+// Gets the transaction count of the genesis block.
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getBlockTransactionCountByNumber","params":["0x0"]}
+<< {"jsonrpc":"2.0","id":1,"result":"0x0"}
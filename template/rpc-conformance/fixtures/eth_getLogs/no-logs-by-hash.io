@@ -0,0 +1,3 @@
+// Gets the logs of the genesis block by hash.
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getLogs","params":[{"blockHash":"${genesisHash}"}]}
+<< {"jsonrpc":"2.0","id":1,"result":[]}
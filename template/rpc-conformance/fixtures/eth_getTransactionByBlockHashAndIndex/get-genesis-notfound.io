@@ -0,0 +1,3 @@
+// Gets a transaction of the genesis block by hash, which has none.
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getTransactionByBlockHashAndIndex","params":["${genesisHash}","0x0"]}
+<< {"jsonrpc":"2.0","id":1,"result":null}
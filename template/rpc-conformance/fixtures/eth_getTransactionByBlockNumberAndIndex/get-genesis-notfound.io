@@ -0,0 +1,3 @@
+// Gets a transaction of the genesis block, which has none.
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getTransactionByBlockNumberAndIndex","params":["0x0","0x0"]}
+<< {"jsonrpc":"2.0","id":1,"result":null}
@@ -0,0 +1,3 @@
+// Gets a transaction which doesn't exist.
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getTransactionByHash","params":["0xabababababababababababababababababababababababababababababababab"]}
+<< {"jsonrpc":"2.0","id":1,"result":null}
@@ -0,0 +1,3 @@
+// Gets the nonce of the genesis account.
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getTransactionCount","params":["${genesisAccount}","latest"]}
+<< {"jsonrpc":"2.0","id":1,"result":"0x0"}
@@ -0,0 +1,3 @@
+// Gets the receipt of a transaction which doesn't exist.
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getTransactionReceipt","params":["0xabababababababababababababababababababababababababababababababab"]}
+<< {"jsonrpc":"2.0","id":1,"result":null}
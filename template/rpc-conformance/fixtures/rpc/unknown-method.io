@@ -0,0 +1,3 @@
+// Calls a method the node doesn't have.
+>> {"jsonrpc":"2.0","id":1,"method":"eth_unknownMethod","params":[]}
+<< {"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"Method not found"}}
@@ -0,0 +1,223 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt;
+
+use serde_json::Value;
+
+/// A difference between an expected and an actual response.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Mismatch {
+	/// A field of the fixture is missing from the response.
+	Missing { path: String, expected: Value },
+	/// The response has a field the fixture doesn't have.
+	Unexpected { path: String, actual: Value },
+	/// The value differs from the fixture.
+	Different {
+		path: String,
+		expected: Value,
+		actual: Value,
+	},
+}
+
+impl Mismatch {
+	/// Path of the mismatching value, like `result.transactions[0]`.
+	pub fn path(&self) -> &str {
+		match self {
+			Self::Missing { path, .. }
+			| Self::Unexpected { path, .. }
+			| Self::Different { path, .. } => path,
+		}
+	}
+}
+
+impl fmt::Display for Mismatch {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Missing { path, expected } => write!(f, "{path}: missing, expected {expected}"),
+			Self::Unexpected { path, actual } => write!(f, "{path}: unexpected {actual}"),
+			Self::Different {
+				path,
+				expected,
+				actual,
+			} => write!(f, "{path}: expected {expected}, got {actual}"),
+		}
+	}
+}
+
+/// Differences between the `expected` response of a fixture and the `actual` one.
+///
+/// Besides literal values, the fixtures can use these placeholders:
+/// - `<any>`: any value,
+/// - `<string>`: any string,
+/// - `<quantity>`: a hex quantity, like `0x1a`,
+/// - `<data>`: hex data of any length, like `0x`,
+/// - `<address>`: a 20 bytes hex value,
+/// - `<hash>`: a 32 bytes hex value.
+pub fn diff(expected: &Value, actual: &Value) -> Vec<Mismatch> {
+	let mut mismatches = Vec::new();
+	diff_at(String::new(), expected, actual, &mut mismatches);
+	mismatches
+}
+
+fn diff_at(path: String, expected: &Value, actual: &Value, mismatches: &mut Vec<Mismatch>) {
+	match (expected, actual) {
+		(Value::String(placeholder), _) if is_placeholder(placeholder) => {
+			if !matches_placeholder(placeholder, actual) {
+				mismatches.push(Mismatch::Different {
+					path,
+					expected: expected.clone(),
+					actual: actual.clone(),
+				});
+			}
+		}
+		(Value::Object(expected), Value::Object(actual)) => {
+			for (key, expected) in expected {
+				let path = field_path(&path, key);
+				match actual.get(key) {
+					Some(actual) => diff_at(path, expected, actual, mismatches),
+					None => mismatches.push(Mismatch::Missing {
+						path,
+						expected: expected.clone(),
+					}),
+				}
+			}
+			for (key, actual) in actual {
+				if !expected.contains_key(key) {
+					mismatches.push(Mismatch::Unexpected {
+						path: field_path(&path, key),
+						actual: actual.clone(),
+					});
+				}
+			}
+		}
+		(Value::Array(expected_items), Value::Array(actual_items))
+			if expected_items.len() == actual_items.len() =>
+		{
+			for (index, (expected, actual)) in expected_items.iter().zip(actual_items).enumerate() {
+				diff_at(format!("{path}[{index}]"), expected, actual, mismatches);
+			}
+		}
+		_ if expected == actual => (),
+		_ => mismatches.push(Mismatch::Different {
+			path,
+			expected: expected.clone(),
+			actual: actual.clone(),
+		}),
+	}
+}
+
+fn field_path(path: &str, key: &str) -> String {
+	if path.is_empty() {
+		key.to_string()
+	} else {
+		format!("{path}.{key}")
+	}
+}
+
+fn is_placeholder(value: &str) -> bool {
+	matches!(
+		value,
+		"<any>" | "<string>" | "<quantity>" | "<data>" | "<address>" | "<hash>"
+	)
+}
+
+fn matches_placeholder(placeholder: &str, actual: &Value) -> bool {
+	if placeholder == "<any>" {
+		return true;
+	}
+	let Some(actual) = actual.as_str() else {
+		return false;
+	};
+	let hex = actual
+		.strip_prefix("0x")
+		.filter(|hex| hex.chars().all(|c| c.is_ascii_hexdigit()));
+	match (placeholder, hex) {
+		("<string>", _) => true,
+		("<quantity>", Some(hex)) => hex == "0" || (!hex.is_empty() && !hex.starts_with('0')),
+		("<data>", Some(hex)) => hex.len() % 2 == 0,
+		("<address>", Some(hex)) => hex.len() == 40,
+		("<hash>", Some(hex)) => hex.len() == 64,
+		_ => false,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn reports_field_mismatches_by_path() {
+		let expected =
+			json!({ "result": { "hash": "<hash>", "mixHash": "<hash>", "number": "0x0" } });
+		let actual = json!({ "result": { "author": "0x00", "hash": "0x12", "number": "0x1" } });
+
+		assert_eq!(
+			diff(&expected, &actual),
+			vec![
+				Mismatch::Different {
+					path: "result.hash".into(),
+					expected: json!("<hash>"),
+					actual: json!("0x12"),
+				},
+				Mismatch::Missing {
+					path: "result.mixHash".into(),
+					expected: json!("<hash>"),
+				},
+				Mismatch::Different {
+					path: "result.number".into(),
+					expected: json!("0x0"),
+					actual: json!("0x1"),
+				},
+				Mismatch::Unexpected {
+					path: "result.author".into(),
+					actual: json!("0x00"),
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn compares_arrays_by_index() {
+		let expected = json!({ "result": ["0x1", "<quantity>"] });
+		assert!(diff(&expected, &json!({ "result": ["0x1", "0x2"] })).is_empty());
+		assert_eq!(
+			diff(&expected, &json!({ "result": ["0x1", "0x02"] }))[0].path(),
+			"result[1]"
+		);
+		assert_eq!(
+			diff(&expected, &json!({ "result": ["0x1"] }))[0].path(),
+			"result"
+		);
+	}
+
+	#[test]
+	fn placeholders_check_the_value_format() {
+		assert!(matches_placeholder("<quantity>", &json!("0x0")));
+		assert!(!matches_placeholder("<quantity>", &json!("0x")));
+		assert!(matches_placeholder("<data>", &json!("0x")));
+		assert!(!matches_placeholder("<data>", &json!("0x123")));
+		assert!(matches_placeholder(
+			"<address>",
+			&json!("0x6be02d1d3665660d22ff9624b7be0551ee1ac91b")
+		));
+		assert!(!matches_placeholder("<hash>", &json!(null)));
+		assert!(matches_placeholder("<any>", &json!(null)));
+	}
+}
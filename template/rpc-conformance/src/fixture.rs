@@ -0,0 +1,184 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use serde_json::Value;
+
+/// Requests of a fixture file with their expected responses.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fixture {
+	/// `<method>/<file name>`, like `eth_getBlockByNumber/get-genesis`.
+	pub name: String,
+	pub exchanges: Vec<(Value, Value)>,
+}
+
+impl Fixture {
+	/// Parse the `.io` format, where `>>` lines are requests, `<<` lines responses and `//`
+	/// lines comments.
+	pub fn parse(name: String, content: &str) -> Result<Self, String> {
+		let mut exchanges = Vec::new();
+		let mut request = None;
+		for (number, line) in content.lines().enumerate() {
+			let line = line.trim();
+			let json = |text: &str| {
+				serde_json::from_str::<Value>(text)
+					.map_err(|err| format!("{name}:{}: invalid JSON: {err}", number + 1))
+			};
+			if line.is_empty() || line.starts_with("//") {
+				continue;
+			} else if let Some(text) = line.strip_prefix(">>") {
+				if request.is_some() {
+					return Err(format!("{name}:{}: request without response", number + 1));
+				}
+				request = Some(json(text)?);
+			} else if let Some(text) = line.strip_prefix("<<") {
+				let request = request
+					.take()
+					.ok_or_else(|| format!("{name}:{}: response without request", number + 1))?;
+				exchanges.push((request, json(text)?));
+			} else {
+				return Err(format!("{name}:{}: unexpected line", number + 1));
+			}
+		}
+
+		if request.is_some() {
+			return Err(format!("{name}: last request without response"));
+		}
+		if exchanges.is_empty() {
+			return Err(format!("{name}: no request"));
+		}
+		Ok(Self { name, exchanges })
+	}
+}
+
+/// Load the fixtures of `dir`, stored as `<method>/<name>.io`, sorted by name.
+pub fn load_fixtures(dir: &Path) -> Result<Vec<Fixture>, String> {
+	let read_dir =
+		|dir: &Path| fs::read_dir(dir).map_err(|err| format!("{}: {err}", dir.display()));
+
+	let mut fixtures = Vec::new();
+	for method in read_dir(dir)? {
+		let method = method.map_err(|err| err.to_string())?.path();
+		if !method.is_dir() {
+			continue;
+		}
+		for file in read_dir(&method)? {
+			let file = file.map_err(|err| err.to_string())?.path();
+			if file.extension().map_or(true, |extension| extension != "io") {
+				continue;
+			}
+			let name = format!(
+				"{}/{}",
+				method.file_name().unwrap_or_default().to_string_lossy(),
+				file.file_stem().unwrap_or_default().to_string_lossy(),
+			);
+			let content =
+				fs::read_to_string(&file).map_err(|err| format!("{}: {err}", file.display()))?;
+			fixtures.push(Fixture::parse(name, &content)?);
+		}
+	}
+
+	fixtures.sort_by(|a, b| a.name.cmp(&b.name));
+	Ok(fixtures)
+}
+
+/// Chain-specific values of the fixtures, written `${name}` in them.
+#[derive(Clone, Debug, Default)]
+pub struct Bindings(BTreeMap<String, Value>);
+
+impl Bindings {
+	pub fn insert(&mut self, name: &str, value: Value) {
+		self.0.insert(format!("${{{name}}}"), value);
+	}
+
+	/// `value` with the strings naming a binding replaced by its value.
+	pub fn substitute(&self, value: &Value) -> Result<Value, String> {
+		Ok(match value {
+			Value::String(text) if text.starts_with("${") => self
+				.0
+				.get(text)
+				.cloned()
+				.ok_or_else(|| format!("unknown binding {text}"))?,
+			Value::Array(items) => Value::Array(
+				items
+					.iter()
+					.map(|item| self.substitute(item))
+					.collect::<Result<_, _>>()?,
+			),
+			Value::Object(fields) => Value::Object(
+				fields
+					.iter()
+					.map(|(key, value)| Ok((key.clone(), self.substitute(value)?)))
+					.collect::<Result<_, String>>()?,
+			),
+			value => value.clone(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn parses_requests_and_responses() {
+		let fixture = Fixture::parse(
+			"eth_chainId/get-chain-id".into(),
+			r#"
+			// Returns the chain id.
+			>> {"jsonrpc":"2.0","id":1,"method":"eth_chainId"}
+			<< {"jsonrpc":"2.0","id":1,"result":"${chainId}"}
+			"#,
+		)
+		.unwrap();
+
+		assert_eq!(
+			fixture.exchanges,
+			vec![(
+				json!({ "jsonrpc": "2.0", "id": 1, "method": "eth_chainId" }),
+				json!({ "jsonrpc": "2.0", "id": 1, "result": "${chainId}" }),
+			)]
+		);
+	}
+
+	#[test]
+	fn rejects_unpaired_requests() {
+		let error = Fixture::parse("eth_chainId/broken".into(), r#">> {"id":1}"#).unwrap_err();
+		assert_eq!(error, "eth_chainId/broken: last request without response");
+
+		let error = Fixture::parse("eth_chainId/broken".into(), r#"<< {"id":1}"#).unwrap_err();
+		assert_eq!(error, "eth_chainId/broken:1: response without request");
+	}
+
+	#[test]
+	fn substitutes_bindings() {
+		let mut bindings = Bindings::default();
+		bindings.insert("chainId", json!("0x2a"));
+
+		assert_eq!(
+			bindings.substitute(&json!({ "result": ["${chainId}", "0x1"] })),
+			Ok(json!({ "result": ["0x2a", "0x1"] }))
+		);
+		assert_eq!(
+			bindings.substitute(&json!("${genesisHash}")),
+			Err("unknown binding ${genesisHash}".to_string())
+		);
+	}
+}
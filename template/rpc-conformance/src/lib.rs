@@ -0,0 +1,47 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Ethereum JSON-RPC conformance harness of the template node.
+//!
+//! The fixtures use the `.io` format of the execution-apis tests: `>>` lines are requests and
+//! `<<` lines their expected responses. They are adapted to the dev chain of the template node:
+//! `${name}` strings are replaced by the [`Bindings`] of the running node, and the schema
+//! placeholders of [`diff`] stand for values which differ from a run to another.
+//!
+//! The fixtures are written for this chain, as the upstream ones assume the blocks and accounts
+//! of the hive chain. The values which depend on the node rather than on the specification,
+//! like the gas limit and the size of the genesis block, are bound from its own responses.
+//!
+//! `cargo test -p frontier-rpc-conformance -- --ignored` runs the fixtures against the release
+//! build of the node, or the binary of `FRONTIER_NODE_BINARY`, and fails if it is missing. The
+//! known deviations from the fixtures are listed in the test.
+//!
+//! [`TestNode`] wraps the node with typed calls for the integration tests of the RPC methods, in
+//! `tests/integration`, which send transactions and seal blocks.
+
+mod diff;
+mod fixture;
+mod node;
+mod test_node;
+
+pub use self::{
+	diff::{diff, Mismatch},
+	fixture::{load_fixtures, Bindings, Fixture},
+	node::{node_binary, Node},
+	test_node::{to_hex, CallError, TestNode, CHAIN_ID, GENESIS_ACCOUNT, TRANSACTION_GAS_LIMIT},
+};
@@ -0,0 +1,142 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+	io::{BufRead, BufReader, Read, Write},
+	net::TcpStream,
+	path::{Path, PathBuf},
+	process::{Child, Command, Stdio},
+	sync::mpsc,
+	thread,
+	time::Duration,
+};
+
+use serde_json::Value;
+
+const SPAWNING_TIME: Duration = Duration::from_secs(60);
+
+/// The node binary, `FRONTIER_NODE_BINARY` or the build of `FRONTIER_BUILD` (`release` by
+/// default).
+///
+/// # Panics
+///
+/// If the node binary does not exist, so that the tests needing it fail instead of passing
+/// without running.
+pub fn node_binary() -> PathBuf {
+	let binary = match std::env::var_os("FRONTIER_NODE_BINARY") {
+		Some(binary) => PathBuf::from(binary),
+		None => {
+			let build = std::env::var("FRONTIER_BUILD").unwrap_or_else(|_| "release".into());
+			PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+				.join("../../target")
+				.join(build)
+				.join("frontier-template-node")
+		}
+	};
+	assert!(
+		binary.exists(),
+		"Node binary {} not found, build the node with `cargo build --release` or set \
+		FRONTIER_NODE_BINARY",
+		binary.display()
+	);
+	binary
+}
+
+/// A template node in dev mode with manual sealing, killed on drop.
+pub struct Node {
+	process: Child,
+	rpc_port: u16,
+}
+
+impl Node {
+	/// Start the node of `binary` and wait until it seals blocks.
+	pub fn start(binary: &Path, port: u16, rpc_port: u16) -> Result<Self, String> {
+		let mut process = Command::new(binary)
+			.args([
+				"--chain=dev",
+				"--validator",
+				"--execution=Native",
+				"--no-telemetry",
+				"--no-prometheus",
+				"--sealing=Manual",
+				"--no-grandpa",
+				"--force-authoring",
+				"--tmp",
+				"--unsafe-force-node-key-generation",
+			])
+			.arg(format!("--port={port}"))
+			.arg(format!("--rpc-port={rpc_port}"))
+			.stdout(Stdio::null())
+			.stderr(Stdio::piped())
+			.spawn()
+			.map_err(|err| format!("Failed to start {}: {err}", binary.display()))?;
+
+		// The logs are read until the node exits, so that it never blocks on a full pipe.
+		let stderr = process.stderr.take().expect("stderr is piped; qed");
+		let (ready_tx, ready_rx) = mpsc::channel();
+		thread::spawn(move || {
+			let mut ready_tx = Some(ready_tx);
+			for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+				if line.contains("Manual Seal Ready") {
+					if let Some(ready_tx) = ready_tx.take() {
+						let _ = ready_tx.send(());
+					}
+				}
+			}
+		});
+
+		let node = Self { process, rpc_port };
+		ready_rx
+			.recv_timeout(SPAWNING_TIME)
+			.map_err(|_| format!("{} did not start sealing", binary.display()))?;
+		Ok(node)
+	}
+
+	/// Send `request` over HTTP and return the JSON response.
+	pub fn request(&self, request: &Value) -> Result<Value, String> {
+		let body = request.to_string();
+		let mut stream = TcpStream::connect(("127.0.0.1", self.rpc_port))
+			.map_err(|err| format!("Failed to connect to the node: {err}"))?;
+		write!(
+			stream,
+			"POST / HTTP/1.1\r\nHost: 127.0.0.1\r\nContent-Type: application/json\r\n\
+			Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+			body.len()
+		)
+		.map_err(|err| format!("Failed to send the request: {err}"))?;
+
+		let mut response = String::new();
+		stream
+			.read_to_string(&mut response)
+			.map_err(|err| format!("Failed to read the response: {err}"))?;
+		let (head, body) = response
+			.split_once("\r\n\r\n")
+			.ok_or_else(|| format!("Malformed HTTP response: {response}"))?;
+		if !head.starts_with("HTTP/1.1 200") {
+			return Err(format!("HTTP error: {head}"));
+		}
+		serde_json::from_str(body).map_err(|err| format!("Invalid JSON response {body}: {err}"))
+	}
+}
+
+impl Drop for Node {
+	fn drop(&mut self) {
+		let _ = self.process.kill();
+		let _ = self.process.wait();
+	}
+}
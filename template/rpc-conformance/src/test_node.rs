@@ -0,0 +1,208 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::path::Path;
+
+use ethereum_types::{H160, H256, U256};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use crate::node::Node;
+
+/// Chain id of the dev chain.
+pub const CHAIN_ID: u64 = 42;
+/// Account endowed in the genesis of the dev chain, whose transactions the node sends.
+pub const GENESIS_ACCOUNT: H160 = H160([
+	0x6b, 0xe0, 0x2d, 0x1d, 0x36, 0x65, 0x66, 0x0d, 0x22, 0xff, 0x96, 0x24, 0xb7, 0xbe, 0x05, 0x51,
+	0xee, 0x1a, 0xc9, 0x1b,
+]);
+const GENESIS_ACCOUNT_PRIVATE_KEY: [u8; 32] = [
+	0x99, 0xb3, 0xc1, 0x22, 0x87, 0x53, 0x7e, 0x38, 0xc9, 0x0a, 0x92, 0x19, 0xd4, 0xcb, 0x07, 0x4a,
+	0x89, 0xa1, 0x6e, 0x9c, 0xdb, 0x20, 0xbf, 0x85, 0x72, 0x8e, 0xbd, 0x97, 0xc3, 0x43, 0xe3, 0x42,
+];
+
+/// Gas limit of the transactions sent by [`TestNode::send_transaction`].
+pub const TRANSACTION_GAS_LIMIT: u64 = 1_000_000;
+
+/// Error of a JSON-RPC call.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CallError {
+	/// Error object of the response.
+	Rpc { code: i64, message: String },
+	/// The request was not answered with a JSON-RPC response.
+	Transport(String),
+}
+
+/// A [`Node`] with typed calls, for the integration tests of the RPC methods.
+///
+/// Blocks are only sealed by [`TestNode::mine_block`] and [`TestNode::mine_block_on`], and never
+/// finalized so that the tests can reorganize them.
+pub struct TestNode {
+	node: Node,
+}
+
+impl TestNode {
+	pub fn start(binary: &Path, port: u16, rpc_port: u16) -> Result<Self, String> {
+		Ok(Self {
+			node: Node::start(binary, port, rpc_port)?,
+		})
+	}
+
+	/// Call `method` and return its result.
+	pub fn call(&self, method: &str, params: Value) -> Result<Value, CallError> {
+		let response = self
+			.node
+			.request(&json!({
+				"jsonrpc": "2.0",
+				"id": 1,
+				"method": method,
+				"params": params,
+			}))
+			.map_err(CallError::Transport)?;
+		if let Some(error) = response.get("error") {
+			return Err(CallError::Rpc {
+				code: error["code"].as_i64().unwrap_or_default(),
+				message: error["message"].as_str().unwrap_or_default().to_string(),
+			});
+		}
+		response
+			.get("result")
+			.cloned()
+			.ok_or_else(|| CallError::Transport(format!("No result in {response}")))
+	}
+
+	/// Call `method` and deserialize its result, panicking on errors.
+	pub fn call_as<T: DeserializeOwned>(&self, method: &str, params: Value) -> T {
+		let result = self
+			.call(method, params.clone())
+			.unwrap_or_else(|err| panic!("{method}({params}) failed: {err:?}"));
+		serde_json::from_value(result.clone())
+			.unwrap_or_else(|err| panic!("{method}({params}) returned {result}: {err}"))
+	}
+
+	/// Seal a block on the best block and return its substrate hash.
+	pub fn mine_block(&self) -> H256 {
+		self.create_block(Value::Null)
+	}
+
+	/// Seal a block on `parent`, a substrate hash, to create forks.
+	pub fn mine_block_on(&self, parent: H256) -> H256 {
+		self.create_block(json!(parent))
+	}
+
+	fn create_block(&self, parent: Value) -> H256 {
+		let created: Value = self.call_as("engine_createBlock", json!([true, false, parent]));
+		serde_json::from_value(created["hash"].clone()).expect("Block hash is valid")
+	}
+
+	/// Send a legacy transaction of [`GENESIS_ACCOUNT`] with its next nonce, creating a contract
+	/// if `to` is `None`.
+	pub fn send_transaction(
+		&self,
+		to: Option<H160>,
+		value: U256,
+		input: Vec<u8>,
+	) -> Result<H256, CallError> {
+		let nonce = self.eth_get_transaction_count(GENESIS_ACCOUNT, "pending");
+		self.send_raw_transaction(&self.sign_transaction(nonce, to, value, input))
+	}
+
+	/// The raw legacy transaction of [`GENESIS_ACCOUNT`], creating a contract if `to` is `None`.
+	pub fn sign_transaction(
+		&self,
+		nonce: U256,
+		to: Option<H160>,
+		value: U256,
+		input: Vec<u8>,
+	) -> Vec<u8> {
+		let message = ethereum::LegacyTransactionMessage {
+			nonce,
+			gas_price: self.call_as("eth_gasPrice", json!([])),
+			gas_limit: TRANSACTION_GAS_LIMIT.into(),
+			action: match to {
+				Some(to) => ethereum::TransactionAction::Call(to),
+				None => ethereum::TransactionAction::Create,
+			},
+			value,
+			input,
+			chain_id: Some(CHAIN_ID),
+		};
+		rlp::encode(&sign(message)).to_vec()
+	}
+
+	pub fn send_raw_transaction(&self, raw: &[u8]) -> Result<H256, CallError> {
+		let hash = self.call("eth_sendRawTransaction", json!([to_hex(raw)]))?;
+		serde_json::from_value(hash).map_err(|err| CallError::Transport(err.to_string()))
+	}
+
+	pub fn eth_block_number(&self) -> U256 {
+		self.call_as("eth_blockNumber", json!([]))
+	}
+
+	pub fn eth_get_balance(&self, address: H160, block: &str) -> U256 {
+		self.call_as("eth_getBalance", json!([address, block]))
+	}
+
+	pub fn eth_get_transaction_count(&self, address: H160, block: &str) -> U256 {
+		self.call_as("eth_getTransactionCount", json!([address, block]))
+	}
+
+	pub fn eth_get_code(&self, address: H160, block: &str) -> String {
+		self.call_as("eth_getCode", json!([address, block]))
+	}
+
+	/// The block of `number`, `None` if there is none.
+	pub fn eth_get_block_by_number(&self, number: &str, full: bool) -> Option<Value> {
+		self.call_as("eth_getBlockByNumber", json!([number, full]))
+	}
+
+	/// The receipt of the transaction of `hash`, `None` if it is not in a block.
+	pub fn eth_get_transaction_receipt(&self, hash: H256) -> Option<Value> {
+		self.call_as("eth_getTransactionReceipt", json!([hash]))
+	}
+}
+
+fn sign(message: ethereum::LegacyTransactionMessage) -> ethereum::LegacyTransaction {
+	let secret = libsecp256k1::SecretKey::parse(&GENESIS_ACCOUNT_PRIVATE_KEY)
+		.expect("Test key is valid; qed");
+	let signing_message =
+		libsecp256k1::Message::parse_slice(&message.hash()[..]).expect("Hash is 32 bytes; qed");
+	let (signature, recid) = libsecp256k1::sign(&signing_message, &secret);
+	let rs = signature.serialize();
+	let signature = ethereum::TransactionSignature::new(
+		2 * CHAIN_ID + 35 + recid.serialize() as u64,
+		H256::from_slice(&rs[0..32]),
+		H256::from_slice(&rs[32..64]),
+	)
+	.expect("Signature is valid; qed");
+
+	ethereum::LegacyTransaction {
+		nonce: message.nonce,
+		gas_price: message.gas_price,
+		gas_limit: message.gas_limit,
+		action: message.action,
+		value: message.value,
+		input: message.input,
+		signature,
+	}
+}
+
+/// `0x`-prefixed hex encoding of `bytes`.
+pub fn to_hex(bytes: &[u8]) -> String {
+	format!("0x{}", hex::encode(bytes))
+}
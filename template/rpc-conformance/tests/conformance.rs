@@ -0,0 +1,200 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::BTreeSet, path::PathBuf};
+
+use ethereum_types::{H256, U256};
+use frontier_rpc_conformance::{
+	diff, load_fixtures, node_binary, Bindings, Node, CHAIN_ID, GENESIS_ACCOUNT,
+};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+const PORT: u16 = 19941;
+const RPC_PORT: u16 = 19942;
+
+/// A known difference from the fixtures: a response field of a fixture, and why it differs.
+struct Deviation {
+	fixture: &'static str,
+	path: &'static str,
+	reason: &'static str,
+}
+
+const GENESIS_FIXTURES: &[&str] = &[
+	"eth_getBlockByHash/get-genesis",
+	"eth_getBlockByNumber/get-genesis",
+	"eth_getBlockByNumber/get-genesis-full",
+];
+
+/// Every deviation must still happen, so that fixed ones get removed from the list.
+fn known_deviations() -> Vec<Deviation> {
+	GENESIS_FIXTURES
+		.iter()
+		.flat_map(|&fixture| {
+			[
+				Deviation {
+					fixture,
+					path: "result.author",
+					reason: "OpenEthereum alias of `miner`, kept for backward compatibility",
+				},
+				Deviation {
+					fixture,
+					path: "result.totalDifficulty",
+					reason: "Pre-merge field, always zero",
+				},
+				Deviation {
+					fixture,
+					path: "result.mixHash",
+					reason: "Not part of the Ethereum header of Frontier",
+				},
+			]
+		})
+		.collect()
+}
+
+fn bindings(node: &Node) -> Bindings {
+	let genesis = node
+		.request(&json!({
+			"jsonrpc": "2.0",
+			"id": 1,
+			"method": "eth_getBlockByNumber",
+			"params": ["0x0", false],
+		}))
+		.expect("Genesis block is served");
+
+	let genesis = &genesis["result"];
+
+	let mut bindings = Bindings::default();
+	bindings.insert("chainId", json!(format!("0x{CHAIN_ID:x}")));
+	bindings.insert("genesisHash", genesis["hash"].clone());
+	// Set by the runtime of the node rather than by the specification.
+	bindings.insert("genesisGasLimit", genesis["gasLimit"].clone());
+	bindings.insert("genesisSize", json!(format!("0x{:x}", block_size(genesis))));
+	bindings.insert("genesisAccount", json!(GENESIS_ACCOUNT));
+	bindings
+}
+
+/// Size of the RLP encoding of the served `block`, which has no transaction nor uncle.
+fn block_size(block: &Value) -> usize {
+	fn field<T: DeserializeOwned>(block: &Value, name: &str) -> T {
+		serde_json::from_value(block[name].clone())
+			.unwrap_or_else(|err| panic!("Invalid {name} of the block: {err}"))
+	}
+
+	let extra_data = field::<String>(block, "extraData");
+	let header = ethereum::Header {
+		parent_hash: field(block, "parentHash"),
+		ommers_hash: field(block, "sha3Uncles"),
+		beneficiary: field(block, "miner"),
+		state_root: field(block, "stateRoot"),
+		transactions_root: field(block, "transactionsRoot"),
+		receipts_root: field(block, "receiptsRoot"),
+		logs_bloom: field(block, "logsBloom"),
+		difficulty: field(block, "difficulty"),
+		number: field(block, "number"),
+		gas_limit: field(block, "gasLimit"),
+		gas_used: field(block, "gasUsed"),
+		timestamp: field::<U256>(block, "timestamp").as_u64(),
+		extra_data: hex::decode(extra_data.trim_start_matches("0x"))
+			.expect("Extra data of the block is hex"),
+		// Not served, and always zero in Frontier.
+		mix_hash: H256::zero(),
+		nonce: field(block, "nonce"),
+	};
+	rlp::encode(&ethereum::BlockV2 {
+		header,
+		transactions: vec![],
+		ommers: vec![],
+	})
+	.len()
+}
+
+#[test]
+#[ignore = "needs the node binary"]
+fn responses_match_the_fixtures() {
+	let binary = node_binary();
+	let node = Node::start(&binary, PORT, RPC_PORT).unwrap();
+	let bindings = bindings(&node);
+	let fixtures = load_fixtures(&PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures"))
+		.expect("Fixtures are valid");
+
+	let deviations = known_deviations();
+	let mut seen_deviations = BTreeSet::new();
+	let mut report = Vec::new();
+	for fixture in &fixtures {
+		for (request, expected) in &fixture.exchanges {
+			let request = bindings.substitute(request).unwrap();
+			let expected = bindings.substitute(expected).unwrap();
+			let actual = match node.request(&request) {
+				Ok(actual) => actual,
+				Err(err) => {
+					report.push(format!("{}:\n  {err}", fixture.name));
+					continue;
+				}
+			};
+
+			let mismatches: Vec<_> = diff(&expected, &actual)
+				.into_iter()
+				.filter(|mismatch| {
+					let deviation = deviations.iter().position(|deviation| {
+						deviation.fixture == fixture.name && deviation.path == mismatch.path()
+					});
+					if let Some(deviation) = deviation {
+						seen_deviations.insert(deviation);
+					}
+					deviation.is_none()
+				})
+				.collect();
+			if !mismatches.is_empty() {
+				report.push(format!(
+					"{}:\n  request: {request}\n{}\n  response: {}",
+					fixture.name,
+					mismatches
+						.iter()
+						.map(|mismatch| format!("  {mismatch}"))
+						.collect::<Vec<_>>()
+						.join("\n"),
+					pretty(&actual),
+				));
+			}
+		}
+	}
+
+	for (index, deviation) in deviations.iter().enumerate() {
+		if !seen_deviations.contains(&index) {
+			report.push(format!(
+				"{}:\n  {} matches the fixture now ({}), remove it from the known deviations",
+				deviation.fixture, deviation.path, deviation.reason
+			));
+		}
+	}
+
+	assert!(
+		report.is_empty(),
+		"{} conformance failures in {} fixtures:\n\n{}",
+		report.len(),
+		fixtures.len(),
+		report.join("\n\n")
+	);
+}
+
+fn pretty(value: &Value) -> String {
+	serde_json::to_string_pretty(value)
+		.unwrap_or_default()
+		.replace('\n', "\n  ")
+}
@@ -0,0 +1,99 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum_types::{H160, H256, U256};
+use serde_json::{json, Value};
+
+use super::{bytes, with_node};
+
+#[test]
+#[ignore = "needs the node binary"]
+fn raw_blocks() {
+	with_node(|node| {
+		let transaction = node
+			.send_transaction(Some(H160::repeat_byte(0x42)), U256::one(), Vec::new())
+			.unwrap();
+		node.mine_block();
+		let block = node.eth_get_block_by_number("0x1", false).unwrap();
+
+		let raw_header = bytes(&node.call_as::<Value>("debug_getRawHeader", json!(["0x1"])));
+		let raw_block = bytes(&node.call_as::<Value>("debug_getRawBlock", json!([block["hash"]])));
+		let rlp = rlp::Rlp::new(&raw_block);
+		assert_eq!(rlp.at(0).unwrap().as_raw(), &raw_header[..]);
+		assert_eq!(rlp.at(1).unwrap().item_count().unwrap(), 1);
+		let header: ethereum::Header = rlp::decode(&raw_header).unwrap();
+		assert_eq!(json!(header.hash()), block["hash"]);
+		assert_eq!(
+			serde_json::from_value::<U256>(block["size"].clone()).unwrap(),
+			U256::from(raw_block.len())
+		);
+
+		let raw_transaction =
+			bytes(&node.call_as::<Value>("debug_getRawTransaction", json!([transaction])));
+		assert_eq!(
+			rlp::decode::<ethereum::LegacyTransaction>(&raw_transaction)
+				.unwrap()
+				.hash(),
+			transaction
+		);
+	});
+}
+
+#[test]
+#[ignore = "needs the node binary"]
+fn raw_receipts() {
+	with_node(|node| {
+		node.send_transaction(Some(H160::repeat_byte(0x42)), U256::one(), Vec::new())
+			.unwrap();
+		node.mine_block();
+
+		let receipts: Vec<Value> = node.call_as("debug_getRawReceipts", json!(["latest"]));
+		assert_eq!(receipts.len(), 1);
+		let receipt: ethereum::EIP658ReceiptData = rlp::decode(&bytes(&receipts[0])).unwrap();
+		assert_eq!(receipt.status_code, 1);
+		assert_eq!(receipt.used_gas, U256::from(21_000));
+
+		node.mine_block();
+		assert_eq!(
+			node.call_as::<Vec<Value>>("debug_getRawReceipts", json!(["latest"])),
+			Vec::<Value>::new()
+		);
+	});
+}
+
+#[test]
+#[ignore = "needs the node binary"]
+fn unknown_blocks_and_transactions() {
+	with_node(|node| {
+		for method in ["debug_getRawHeader", "debug_getRawBlock"] {
+			assert_eq!(node.call_as::<Value>(method, json!(["0x1"])), Value::Null);
+		}
+		assert_eq!(
+			node.call_as::<Value>("debug_getRawTransaction", json!([H256::repeat_byte(1)])),
+			Value::Null
+		);
+		assert_eq!(
+			node.call_as::<Vec<Value>>("debug_getRawReceipts", json!(["0x1"])),
+			Vec::<Value>::new()
+		);
+		assert_eq!(
+			node.call_as::<Vec<Value>>("debug_getBadBlocks", json!(["latest"])),
+			Vec::<Value>::new()
+		);
+	});
+}
@@ -0,0 +1,419 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum_types::{H160, H256, U256};
+use frontier_rpc_conformance::{CallError, TestNode, CHAIN_ID, GENESIS_ACCOUNT};
+use serde_json::{json, Value};
+
+use super::with_node;
+
+/// Init code of a contract whose code returns 42 as a word:
+/// `PUSH1 42, PUSH1 0, MSTORE, PUSH1 32, PUSH1 0, RETURN`, returned by
+/// `PUSH10 <code>, PUSH1 0, MSTORE, PUSH1 10, PUSH1 22, RETURN`.
+const RETURN_42_INIT_CODE: [u8; 19] = [
+	0x69, 0x60, 0x2a, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3, 0x60, 0x00, 0x52, 0x60, 0x0a,
+	0x60, 0x16, 0xf3,
+];
+/// Init code of a contract whose code returns the base fee as a word:
+/// `BASEFEE, PUSH1 0, MSTORE, PUSH1 32, PUSH1 0, RETURN`, returned by
+/// `PUSH9 <code>, PUSH1 0, MSTORE, PUSH1 9, PUSH1 23, RETURN`.
+const RETURN_BASEFEE_INIT_CODE: [u8; 18] = [
+	0x68, 0x48, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3, 0x60, 0x00, 0x52, 0x60, 0x09, 0x60,
+	0x17, 0xf3,
+];
+/// Init code emitting a log with topic 1 and no data: `PUSH1 1, PUSH1 0, PUSH1 0, LOG1, STOP`.
+const LOG_INIT_CODE: [u8; 8] = [0x60, 0x01, 0x60, 0x00, 0x60, 0x00, 0xa1, 0x00];
+
+fn quantity(value: &Value) -> U256 {
+	serde_json::from_value(value.clone()).expect("Value is a quantity")
+}
+
+/// The address of the contract created by `init`, in a new block.
+fn deploy(node: &TestNode, init: &[u8]) -> (H160, Value) {
+	let hash = node
+		.send_transaction(None, U256::zero(), init.to_vec())
+		.unwrap();
+	node.mine_block();
+	let receipt = node
+		.eth_get_transaction_receipt(hash)
+		.expect("Receipt exists");
+	assert_eq!(receipt["status"], "0x1");
+	let address = serde_json::from_value(receipt["contractAddress"].clone()).unwrap();
+	(address, receipt)
+}
+
+#[test]
+#[ignore = "needs the node binary"]
+fn chain_constants() {
+	with_node(|node| {
+		assert_eq!(
+			node.call_as::<U256>("eth_chainId", json!([])),
+			U256::from(CHAIN_ID)
+		);
+		assert!(node.call_as::<U256>("eth_gasPrice", json!([])) > U256::zero());
+		assert_eq!(
+			node.call_as::<Value>("eth_syncing", json!([])),
+			json!(false)
+		);
+		assert_eq!(node.call_as::<Value>("eth_accounts", json!([])), json!([]));
+		// The node validates without any Aura key in its keystore.
+		assert_eq!(
+			node.call_as::<H160>("eth_coinbase", json!([])),
+			H160::zero()
+		);
+		assert_eq!(node.call_as::<Value>("eth_mining", json!([])), json!(false));
+		assert_eq!(
+			node.call_as::<U256>("eth_hashrate", json!([])),
+			U256::zero()
+		);
+		assert_eq!(
+			node.call_as::<Vec<H256>>("eth_getWork", json!([])),
+			vec![H256::zero(); 3]
+		);
+		assert_eq!(node.eth_block_number(), U256::zero());
+	});
+}
+
+#[test]
+#[ignore = "needs the node binary"]
+fn rpc_modules() {
+	with_node(|node| {
+		let modules: Value = node.call_as("rpc_modules", json!([]));
+		for namespace in ["eth", "net", "web3", "debug", "rpc"] {
+			assert_eq!(modules[namespace]["version"], json!("1.0"));
+		}
+		let eth_methods = modules["eth"]["methods"].as_array().unwrap();
+		assert!(eth_methods.contains(&json!("eth_chainId")));
+		assert!(eth_methods.contains(&json!("eth_sendRawTransaction")));
+		assert_eq!(modules["rpc"]["methods"], json!(["rpc_modules"]));
+	});
+}
+
+#[test]
+#[ignore = "needs the node binary"]
+fn empty_blocks() {
+	with_node(|node| {
+		let substrate_hash = node.mine_block();
+		assert_ne!(substrate_hash, H256::zero());
+		assert_eq!(node.eth_block_number(), U256::one());
+
+		let block = node.eth_get_block_by_number("latest", true).unwrap();
+		assert_eq!(block["number"], "0x1");
+		assert_eq!(block["transactions"], json!([]));
+		assert_eq!(block["gasUsed"], "0x0");
+		let genesis = node.eth_get_block_by_number("earliest", false).unwrap();
+		assert_eq!(block["parentHash"], genesis["hash"]);
+
+		assert_eq!(
+			node.call_as::<Value>("eth_getBlockByHash", json!([block["hash"], false]))["number"],
+			"0x1"
+		);
+		assert_eq!(
+			node.call_as::<U256>("eth_getBlockTransactionCountByNumber", json!(["0x1"])),
+			U256::zero()
+		);
+		assert_eq!(
+			node.call_as::<U256>("eth_getBlockTransactionCountByHash", json!([block["hash"]])),
+			U256::zero()
+		);
+		assert_eq!(
+			node.call_as::<Value>("eth_getBlockReceipts", json!(["0x1"])),
+			json!([])
+		);
+		assert_eq!(
+			node.call_as::<U256>("eth_getUncleCountByBlockNumber", json!(["0x1"])),
+			U256::zero()
+		);
+		assert_eq!(node.eth_get_block_by_number("0x2", false), None);
+	});
+}
+
+#[test]
+#[ignore = "needs the node binary"]
+fn value_transfers() {
+	with_node(|node| {
+		let recipient = H160::repeat_byte(0x42);
+		let value = U256::from(1_000_000_000_000u64);
+		let balance = node.eth_get_balance(GENESIS_ACCOUNT, "latest");
+
+		let hash = node
+			.send_transaction(Some(recipient), value, Vec::new())
+			.unwrap();
+		node.mine_block();
+
+		let receipt = node.eth_get_transaction_receipt(hash).unwrap();
+		assert_eq!(receipt["status"], "0x1");
+		assert_eq!(receipt["gasUsed"], "0x5208");
+		assert_eq!(receipt["blockNumber"], "0x1");
+		let fee = quantity(&receipt["gasUsed"]) * quantity(&receipt["effectiveGasPrice"]);
+		assert_eq!(node.eth_get_balance(recipient, "latest"), value);
+		assert_eq!(
+			node.eth_get_balance(GENESIS_ACCOUNT, "latest"),
+			balance - value - fee
+		);
+		assert_eq!(node.eth_get_balance(recipient, "earliest"), U256::zero());
+		assert_eq!(
+			node.eth_get_transaction_count(GENESIS_ACCOUNT, "latest"),
+			U256::one()
+		);
+
+		let transaction: Value = node.call_as("eth_getTransactionByHash", json!([hash]));
+		assert_eq!(transaction["from"], json!(GENESIS_ACCOUNT));
+		assert_eq!(transaction["to"], json!(recipient));
+		assert_eq!(transaction["blockNumber"], "0x1");
+		assert_eq!(
+			node.call_as::<Value>(
+				"eth_getTransactionByBlockNumberAndIndex",
+				json!(["0x1", "0x0"])
+			)["hash"],
+			json!(hash)
+		);
+		assert_eq!(
+			node.call_as::<Value>(
+				"eth_getTransactionByBlockHashAndIndex",
+				json!([receipt["blockHash"], "0x0"])
+			)["hash"],
+			json!(hash)
+		);
+		assert_eq!(
+			node.call_as::<Value>("eth_getBlockReceipts", json!(["0x1"])),
+			json!([receipt])
+		);
+		let block = node.eth_get_block_by_number("0x1", false).unwrap();
+		assert_eq!(block["transactions"], json!([hash]));
+	});
+}
+
+#[test]
+#[ignore = "needs the node binary"]
+fn pending_transactions() {
+	with_node(|node| {
+		let hash = node
+			.send_transaction(Some(H160::repeat_byte(0x42)), U256::one(), Vec::new())
+			.unwrap();
+
+		let transaction: Value = node.call_as("eth_getTransactionByHash", json!([hash]));
+		assert_eq!(transaction["blockNumber"], Value::Null);
+		assert_eq!(node.eth_get_transaction_receipt(hash), None);
+		assert_eq!(
+			node.eth_get_transaction_count(GENESIS_ACCOUNT, "pending"),
+			U256::one()
+		);
+		assert_eq!(
+			node.eth_get_transaction_count(GENESIS_ACCOUNT, "latest"),
+			U256::zero()
+		);
+
+		let pending: Vec<Value> = node.call_as("eth_pendingTransactions", json!([]));
+		assert_eq!(pending.len(), 1);
+		assert_eq!(pending[0]["hash"], json!(hash));
+		assert_eq!(pending[0]["blockHash"], Value::Null);
+		assert_eq!(pending[0]["transactionIndex"], Value::Null);
+
+		node.mine_block();
+		assert!(node.eth_get_transaction_receipt(hash).is_some());
+		assert_eq!(
+			node.call_as::<Vec<Value>>("eth_pendingTransactions", json!([])),
+			Vec::<Value>::new()
+		);
+	});
+}
+
+#[test]
+#[ignore = "needs the node binary"]
+fn contracts() {
+	with_node(|node| {
+		let (contract, _) = deploy(node, &RETURN_42_INIT_CODE);
+
+		assert_eq!(
+			node.eth_get_code(contract, "latest"),
+			"0x602a60005260206000f3"
+		);
+		assert_eq!(node.eth_get_code(contract, "earliest"), "0x");
+
+		let word_42 = format!("0x{:064x}", 42);
+		assert_eq!(
+			node.call_as::<String>("eth_call", json!([{ "to": contract }, "latest"])),
+			word_42
+		);
+		let gas = node.call_as::<U256>("eth_estimateGas", json!([{ "to": contract }]));
+		assert!(gas > U256::from(21_000));
+		assert_eq!(
+			node.call_as::<H256>("eth_getStorageAt", json!([contract, "0x0", "latest"])),
+			H256::zero()
+		);
+	});
+}
+
+#[test]
+#[ignore = "needs the node binary"]
+fn basefee_opcode() {
+	with_node(|node| {
+		let (contract, _) = deploy(node, &RETURN_BASEFEE_INIT_CODE);
+
+		// Both read the base fee of pallet-base-fee at the best block.
+		let base_fee = node.call_as::<U256>("eth_gasPrice", json!([]));
+		assert!(base_fee > U256::zero());
+		assert_eq!(
+			node.call_as::<H256>("eth_call", json!([{ "to": contract }, "latest"])),
+			H256::from_low_u64_be(base_fee.as_u64())
+		);
+	});
+}
+
+#[test]
+#[ignore = "needs the node binary"]
+fn logs() {
+	with_node(|node| {
+		let (contract, receipt) = deploy(node, &LOG_INIT_CODE);
+		let topic = H256::from_low_u64_be(1);
+
+		assert_eq!(receipt["logs"][0]["address"], json!(contract));
+		assert_eq!(receipt["logs"][0]["topics"], json!([topic]));
+
+		let logs: Vec<Value> = node.call_as(
+			"eth_getLogs",
+			json!([{ "fromBlock": "0x0", "toBlock": "latest", "address": contract }]),
+		);
+		assert_eq!(logs.len(), 1);
+		assert_eq!(logs[0]["transactionHash"], receipt["transactionHash"]);
+		assert_eq!(logs[0]["topics"], json!([topic]));
+		let logs: Vec<Value> = node.call_as(
+			"eth_getLogs",
+			json!([{ "blockHash": receipt["blockHash"], "topics": [topic] }]),
+		);
+		assert_eq!(logs.len(), 1);
+		let logs: Vec<Value> = node.call_as(
+			"eth_getLogs",
+			json!([{ "fromBlock": "0x0", "toBlock": "0x0", "address": contract }]),
+		);
+		assert_eq!(logs, Vec::<Value>::new());
+	});
+}
+
+#[test]
+#[ignore = "needs the node binary"]
+fn filters() {
+	with_node(|node| {
+		let filter: String = node.call_as("eth_newBlockFilter", json!([]));
+		node.mine_block();
+		let block_hash = node.eth_get_block_by_number("latest", false).unwrap()["hash"].clone();
+		assert_eq!(
+			node.call_as::<Value>("eth_getFilterChanges", json!([filter])),
+			json!([block_hash])
+		);
+		assert_eq!(
+			node.call_as::<Value>("eth_getFilterChanges", json!([filter])),
+			json!([])
+		);
+		assert!(node.call_as::<bool>("eth_uninstallFilter", json!([filter])));
+		assert!(matches!(
+			node.call("eth_getFilterChanges", json!([filter])),
+			Err(CallError::Rpc { .. })
+		));
+	});
+}
+
+#[test]
+#[ignore = "needs the node binary"]
+fn invalid_transactions() {
+	with_node(|node| {
+		assert!(matches!(
+			node.send_raw_transaction(&[0xde, 0xad, 0xbe, 0xef]),
+			Err(CallError::Rpc { .. })
+		));
+
+		let raw = node.sign_transaction(
+			U256::zero(),
+			Some(H160::repeat_byte(0x42)),
+			U256::one(),
+			Vec::new(),
+		);
+		node.send_raw_transaction(&raw).unwrap();
+		node.mine_block();
+		// Its nonce is used now.
+		assert!(matches!(
+			node.send_raw_transaction(&raw),
+			Err(CallError::Rpc { .. })
+		));
+
+		let too_expensive = node.sign_transaction(
+			U256::one(),
+			Some(H160::repeat_byte(0x42)),
+			U256::MAX,
+			Vec::new(),
+		);
+		assert!(matches!(
+			node.send_raw_transaction(&too_expensive),
+			Err(CallError::Rpc { .. })
+		));
+	});
+}
+
+#[test]
+#[ignore = "needs the node binary"]
+fn invalid_requests() {
+	with_node(|node| {
+		let invalid_params = |method: &str, params: Value| match node.call(method, params) {
+			Err(CallError::Rpc { code, .. }) => code == -32602,
+			_ => false,
+		};
+		assert!(invalid_params("eth_getBalance", json!(["0x42", "latest"])));
+		assert!(invalid_params(
+			"eth_getBlockByNumber",
+			json!(["next", false])
+		));
+		assert!(invalid_params("eth_sendRawTransaction", json!([42])));
+		assert!(invalid_params("eth_call", json!([])));
+
+		assert_eq!(
+			node.call_as::<Value>("eth_getTransactionByHash", json!([H256::repeat_byte(1)])),
+			Value::Null
+		);
+		assert_eq!(
+			node.call_as::<Value>("eth_getBlockByHash", json!([H256::repeat_byte(1), false])),
+			Value::Null
+		);
+	});
+}
+
+#[test]
+#[ignore = "needs the node binary"]
+fn reorganizations() {
+	with_node(|node| {
+		let first = node.mine_block();
+		let hash = node
+			.send_transaction(Some(H160::repeat_byte(0x42)), U256::one(), Vec::new())
+			.unwrap();
+		node.mine_block();
+		let retracted = node.eth_get_block_by_number("0x2", false).unwrap();
+		assert_eq!(retracted["transactions"], json!([hash]));
+
+		// A longer fork of the first block, without the transaction.
+		let fork = node.mine_block_on(first);
+		node.mine_block_on(fork);
+
+		assert_eq!(node.eth_block_number(), U256::from(3));
+		let block = node.eth_get_block_by_number("0x2", false).unwrap();
+		assert_ne!(block["hash"], retracted["hash"]);
+		assert_eq!(
+			node.call_as::<Value>("eth_getBlockByHash", json!([retracted["hash"], false])),
+			Value::Null
+		);
+	});
+}
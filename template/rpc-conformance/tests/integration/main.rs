@@ -0,0 +1,46 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Integration tests of the RPC methods, each against its own template node.
+//!
+//! They are ignored by default, as they need the node binary: run them with
+//! `cargo test -p frontier-rpc-conformance -- --ignored` after building the node.
+
+mod debug;
+mod eth;
+
+use std::sync::atomic::{AtomicU16, Ordering};
+
+use frontier_rpc_conformance::{node_binary, TestNode};
+
+/// Ports of the next node, the conformance tests using the ones below.
+static NEXT_PORT: AtomicU16 = AtomicU16::new(19951);
+
+/// Run `test` against a new node, failing if the node was not built.
+fn with_node(test: impl FnOnce(&TestNode)) {
+	let binary = node_binary();
+	let port = NEXT_PORT.fetch_add(2, Ordering::SeqCst);
+	let node = TestNode::start(&binary, port, port + 1).unwrap();
+	test(&node);
+}
+
+/// Bytes of a `0x`-prefixed hex string of a response.
+fn bytes(value: &serde_json::Value) -> Vec<u8> {
+	let text = value.as_str().expect("Bytes are a string");
+	hex::decode(text.trim_start_matches("0x")).expect("Bytes are hex")
+}
@@ -5,8 +5,11 @@ use pallet_evm::{
 use sp_core::H160;
 
 use pallet_evm_precompile_modexp::Modexp;
-use pallet_evm_precompile_sha3fips::Sha3FIPS256;
-use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
+use pallet_evm_precompile_registry::PrecompileRegistry;
+use pallet_evm_precompile_sha3fips::{Keccak256, Sha3FIPS256};
+use pallet_evm_precompile_simple::{
+	ECRecover, ECRecoverPublicKey, EIP712Recover, Identity, Ripemd160, Sha256,
+};
 
 pub struct FrontierPrecompiles<R>(PhantomData<R>);
 
@@ -17,7 +20,7 @@ where
 	pub fn new() -> Self {
 		Self(Default::default())
 	}
-	pub fn used_addresses() -> [H160; 7] {
+	pub fn used_addresses() -> [H160; 10] {
 		[
 			hash(1),
 			hash(2),
@@ -26,6 +29,9 @@ where
 			hash(5),
 			hash(1024),
 			hash(1025),
+			hash(1026),
+			hash(1027),
+			hash(2304),
 		]
 	}
 }
@@ -44,6 +50,9 @@ where
 			// Non-Frontier specific nor Ethereum precompiles :
 			a if a == hash(1024) => Some(Sha3FIPS256::execute(handle)),
 			a if a == hash(1025) => Some(ECRecoverPublicKey::execute(handle)),
+			a if a == hash(1026) => Some(EIP712Recover::execute(handle)),
+			a if a == hash(1027) => Some(Keccak256::execute(handle)),
+			a if a == hash(2304) => Some(PrecompileRegistry::<R>::execute(handle)),
 			_ => None,
 		}
 	}
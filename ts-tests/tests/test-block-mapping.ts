@@ -0,0 +1,74 @@
+import { expect } from "chai";
+import { step } from "mocha-steps";
+
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY } from "./config";
+import { createAndFinalizeBlock, customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (Block mapping)", (context) => {
+	const UNKNOWN_HASH = "0x" + "12".repeat(32);
+
+	let transactionHash: string;
+	let ethereumHash: string;
+	let substrateHash: string;
+
+	step("should produce a block with a transaction", async function () {
+		this.timeout(10_000);
+		const tx = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				to: "0x1111111111111111111111111111111111111111",
+				value: "0x200",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		transactionHash = (await customRequest(context.web3, "eth_sendRawTransaction", [tx.rawTransaction])).result;
+		await createAndFinalizeBlock(context.web3);
+
+		ethereumHash = (await context.web3.eth.getBlock(1)).hash;
+		substrateHash = (await customRequest(context.web3, "chain_getBlockHash", [1])).result;
+	});
+
+	step("should map a substrate block to its ethereum block", async function () {
+		const { result } = await customRequest(context.web3, "frontier_substrateBlockToEthBlock", [substrateHash]);
+		expect(result).to.deep.eq({ ethereumBlockHash: ethereumHash });
+	});
+
+	step("should map an ethereum block to its substrate block", async function () {
+		for (const block of [ethereumHash, "0x1"]) {
+			const { result } = await customRequest(context.web3, "frontier_ethBlockToSubstrateBlock", [block]);
+			expect(result).to.deep.eq({ substrateBlockHash: substrateHash });
+		}
+	});
+
+	step("should map an ethereum transaction to its extrinsic", async function () {
+		const { result } = await customRequest(context.web3, "frontier_ethTxToSubstrateExtrinsic", [transactionHash]);
+		expect(result.substrateBlockHash).to.be.eq(substrateHash);
+
+		// The extrinsic follows the inherents of the block.
+		const { extrinsics } = (await customRequest(context.web3, "chain_getBlock", [substrateHash])).result.block;
+		const index = parseInt(result.extrinsicIndex, 16);
+		expect(index).to.be.gt(0);
+		expect(index).to.be.lt(extrinsics.length);
+	});
+
+	step("should give the reason of a missing mapping", async function () {
+		const genesisHash = (await customRequest(context.web3, "chain_getBlockHash", [0])).result;
+		expect(
+			(await customRequest(context.web3, "frontier_substrateBlockToEthBlock", [genesisHash])).result
+		).to.deep.eq({ ethereumBlockHash: null, reason: "nonEthereumBlock" });
+		expect(
+			(await customRequest(context.web3, "frontier_substrateBlockToEthBlock", [UNKNOWN_HASH])).result
+		).to.deep.eq({ ethereumBlockHash: null, reason: "unknownBlock" });
+		expect(
+			(await customRequest(context.web3, "frontier_ethBlockToSubstrateBlock", [UNKNOWN_HASH])).result
+		).to.deep.eq({ substrateBlockHash: null, reason: "notIndexed" });
+		expect(
+			(await customRequest(context.web3, "frontier_ethBlockToSubstrateBlock", ["0x10"])).result
+		).to.deep.eq({ substrateBlockHash: null, reason: "unknownBlock" });
+		expect(
+			(await customRequest(context.web3, "frontier_ethTxToSubstrateExtrinsic", [UNKNOWN_HASH])).result
+		).to.deep.eq({ substrateBlockHash: null, extrinsicIndex: null, reason: "notIndexed" });
+	});
+});
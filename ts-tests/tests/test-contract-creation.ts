@@ -0,0 +1,47 @@
+import { expect } from "chai";
+import { step } from "mocha-steps";
+
+import Test from "../build/contracts/Test.json";
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY, FIRST_CONTRACT_ADDRESS } from "./config";
+import { createAndFinalizeBlock, customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (Contract creation)", (context) => {
+	let transactionHash: string;
+
+	step("should return null before the contract is created", async function () {
+		const result = await customRequest(context.web3, "frontier_getContractCreation", [FIRST_CONTRACT_ADDRESS]);
+		expect(result.result).to.be.null;
+	});
+
+	step("should index the transaction creating a contract", async function () {
+		this.timeout(15000);
+		const tx = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				data: Test.bytecode,
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		transactionHash = (await customRequest(context.web3, "eth_sendRawTransaction", [tx.rawTransaction])).result;
+		await createAndFinalizeBlock(context.web3);
+
+		const receipt = await context.web3.eth.getTransactionReceipt(transactionHash);
+		expect(receipt.contractAddress.toLowerCase()).to.be.eq(FIRST_CONTRACT_ADDRESS.toLowerCase());
+
+		const result = await customRequest(context.web3, "frontier_getContractCreation", [FIRST_CONTRACT_ADDRESS]);
+		expect(result.result).to.deep.equal({
+			transactionHash,
+			blockNumber: context.web3.utils.numberToHex(receipt.blockNumber),
+			creator: GENESIS_ACCOUNT.toLowerCase(),
+			isInternal: false,
+		});
+	});
+
+	step("should return null for an account that is not a contract", async function () {
+		const result = await customRequest(context.web3, "frontier_getContractCreation", [GENESIS_ACCOUNT]);
+		expect(result.result).to.be.null;
+	});
+});
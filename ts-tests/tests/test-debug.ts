@@ -0,0 +1,85 @@
+import { ethers } from "ethers";
+import { expect } from "chai";
+import { step } from "mocha-steps";
+
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY, CHAIN_ID } from "./config";
+import { createAndFinalizeBlock, customRequest, describeWithFrontier, orderedTrieRoot } from "./util";
+
+describeWithFrontier("Frontier RPC (Debug)", (context) => {
+	const TEST_ACCOUNT = "0x1111111111111111111111111111111111111111";
+
+	let blockNumber: number;
+	let rawTransactions: string[];
+	let transactionHashes: string[] = [];
+
+	step("should include one transaction of each type in a block", async function () {
+		const signer = new ethers.Wallet(GENESIS_ACCOUNT_PRIVATE_KEY, context.ethersjs);
+		const common = {
+			from: GENESIS_ACCOUNT,
+			to: TEST_ACCOUNT,
+			value: "0x01",
+			gasLimit: "0x100000",
+			chainId: CHAIN_ID,
+		};
+		rawTransactions = [
+			await signer.signTransaction({ ...common, type: 0, nonce: 0, gasPrice: "0x3B9ACA00" }),
+			await signer.signTransaction({ ...common, type: 1, nonce: 1, gasPrice: "0x3B9ACA00", accessList: [] }),
+			await signer.signTransaction({
+				...common,
+				type: 2,
+				nonce: 2,
+				maxFeePerGas: "0x3B9ACA00",
+				maxPriorityFeePerGas: "0x01",
+			}),
+		];
+		for (const rawTransaction of rawTransactions) {
+			transactionHashes.push(
+				(await customRequest(context.web3, "eth_sendRawTransaction", [rawTransaction])).result
+			);
+		}
+		await createAndFinalizeBlock(context.web3);
+
+		const block = await context.web3.eth.getBlock("latest");
+		expect(block.transactions.length).to.be.eq(3);
+		blockNumber = block.number;
+	});
+
+	step("debug_getRawTransaction should return the bytes sent with eth_sendRawTransaction", async function () {
+		for (const [index, hash] of transactionHashes.entries()) {
+			const raw = (await customRequest(context.web3, "debug_getRawTransaction", [hash])).result;
+			expect(raw, `type ${index}`).to.be.eq(rawTransactions[index]);
+		}
+	});
+
+	step("debug_getRawReceipts should return typed receipts as EIP-2718 envelopes", async function () {
+		const receipts = (await customRequest(context.web3, "debug_getRawReceipts", [blockNumber])).result;
+		expect(receipts.length).to.be.eq(3);
+		// Legacy receipts are a bare RLP list, typed receipts are prefixed by their type byte.
+		expect(parseInt(receipts[0].slice(2, 4), 16)).to.be.gte(0xc0);
+		expect(receipts[1].slice(0, 4)).to.be.eq("0x01");
+		expect(receipts[2].slice(0, 4)).to.be.eq("0x02");
+	});
+
+	step("debug_getRawReceipts should match the block receipts root", async function () {
+		const block = await context.web3.eth.getBlock(blockNumber);
+		const receipts = (await customRequest(context.web3, "debug_getRawReceipts", [blockNumber])).result;
+		expect(orderedTrieRoot(receipts)).to.be.eq(block.receiptsRoot);
+	});
+
+	step("debug_getRawReceipts should support block tags", async function () {
+		const expected = (await customRequest(context.web3, "debug_getRawReceipts", [blockNumber])).result;
+		for (const tag of ["latest", "finalized", "safe"]) {
+			const receipts = (await customRequest(context.web3, "debug_getRawReceipts", [tag])).result;
+			expect(receipts, tag).to.deep.eq(expected);
+		}
+	});
+
+	step("debug_getRawBlock should support block tags", async function () {
+		const expected = (await customRequest(context.web3, "debug_getRawBlock", [blockNumber])).result;
+		expect(expected).to.not.be.null;
+		for (const tag of ["latest", "finalized", "safe"]) {
+			const block = (await customRequest(context.web3, "debug_getRawBlock", [tag])).result;
+			expect(block, tag).to.be.eq(expected);
+		}
+	});
+});
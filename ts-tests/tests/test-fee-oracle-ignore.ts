@@ -0,0 +1,62 @@
+import { ethers } from "ethers";
+import { expect } from "chai";
+import { step } from "mocha-steps";
+
+import { GENESIS_ACCOUNT_PRIVATE_KEY, CHAIN_ID } from "./config";
+import { createAndFinalizeBlock, describeWithFrontier, customRequest } from "./util";
+
+const SYSTEM_ACCOUNT_PRIVATE_KEY = "0x" + "11".repeat(32);
+const SYSTEM_ACCOUNT = new ethers.Wallet(SYSTEM_ACCOUNT_PRIVATE_KEY).address;
+
+// We use ethers library in this test as apparently web3js's types are not fully EIP-1559 compliant yet.
+describeWithFrontier(
+	"Frontier RPC (Fee oracle ignored transactions)",
+	(context) => {
+		const nonces = {};
+
+		async function sendTransaction(context, privateKey: string, priorityFee: number, value: string = "0x00") {
+			const signer = new ethers.Wallet(privateKey, context.ethersjs);
+			const nonce = nonces[signer.address] || 0;
+			nonces[signer.address] = nonce + 1;
+			return await signer.sendTransaction({
+				to: "0x0000000000000000000000000000000000000000",
+				data: "0x",
+				value,
+				maxFeePerGas: "0x3B9ACA00",
+				maxPriorityFeePerGas: context.web3.utils.numberToHex(priorityFee),
+				accessList: [],
+				nonce,
+				gasLimit: "0x5208",
+				chainId: CHAIN_ID,
+			});
+		}
+
+		step("should fund the system account", async function () {
+			await sendTransaction(context, GENESIS_ACCOUNT_PRIVATE_KEY, 20, "0xde0b6b3a7640000");
+			await createAndFinalizeBlock(context.web3);
+		});
+
+		// The market tips are 10, 11 and 12. The zero and one tips are below the ignore price
+		// and the system account tips are ignored whatever their value.
+		step("should leave ignored transactions out of the fee history rewards", async function () {
+			this.timeout(20000);
+			for (const tip of [0, 1, 10, 11, 12]) {
+				await sendTransaction(context, GENESIS_ACCOUNT_PRIVATE_KEY, tip);
+			}
+			for (const tip of [0, 0, 50]) {
+				await sendTransaction(context, SYSTEM_ACCOUNT_PRIVATE_KEY, tip);
+			}
+			await createAndFinalizeBlock(context.web3);
+
+			const result = (await customRequest(context.web3, "eth_feeHistory", ["0x1", "latest", [0, 60, 100]])).result;
+			expect(result.reward).to.be.deep.eq([["0xa", "0xb", "0xc"]]);
+		});
+
+		step("should leave ignored transactions out of the suggested priority fee", async function () {
+			const result = (await customRequest(context.web3, "eth_maxPriorityFeePerGas", [])).result;
+			expect(result).to.be.eq("0xb");
+		});
+	},
+	undefined,
+	["--gpo-ignore-price=2", `--gpo-ignored-senders=${SYSTEM_ACCOUNT}`]
+);
@@ -0,0 +1,55 @@
+import { expect } from "chai";
+import { step } from "mocha-steps";
+
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY } from "./config";
+import { createAndFinalizeBlock, customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (INVALID opcode)", (context) => {
+	// Deploys a contract whose code is the single `INVALID` (0xfe) opcode:
+	// `PUSH1 0xfe PUSH1 0 MSTORE8 PUSH1 1 PUSH1 0 RETURN`.
+	const INVALID_BYTECODE = "0x60fe60005360016000f3";
+	const GAS_LIMIT = 100_000;
+
+	let contractAddress: string;
+
+	step("should deploy a contract executing INVALID", async function () {
+		this.timeout(15000);
+		const tx = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				data: INVALID_BYTECODE,
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		const txHash = (await customRequest(context.web3, "eth_sendRawTransaction", [tx.rawTransaction])).result;
+		await createAndFinalizeBlock(context.web3);
+
+		const receipt = await context.web3.eth.getTransactionReceipt(txHash);
+		expect(receipt.status).to.be.true;
+		contractAddress = receipt.contractAddress;
+		expect(await context.web3.eth.getCode(contractAddress)).to.be.eq("0xfe");
+	});
+
+	step("should fail and consume the whole gas limit when calling INVALID", async function () {
+		this.timeout(15000);
+		const tx = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				to: contractAddress,
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas: GAS_LIMIT,
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		const txHash = (await customRequest(context.web3, "eth_sendRawTransaction", [tx.rawTransaction])).result;
+		await createAndFinalizeBlock(context.web3);
+
+		const receipt = await customRequest(context.web3, "eth_getTransactionReceipt", [txHash]);
+		expect(receipt.result.status).to.be.eq("0x0");
+		expect(receipt.result.gasUsed).to.be.eq(context.web3.utils.numberToHex(GAS_LIMIT));
+	});
+});
@@ -14,4 +14,23 @@ describeWithFrontier("Frontier RPC (Net)", (context) => {
 	step("should format `peer_count` as decimal using `web3.net`", async function () {
 		expect(await context.web3.eth.net.getPeerCount()).to.equal(0);
 	});
+	step("should return `net_listening`", async function () {
+		expect((await customRequest(context.web3, "net_listening", [])).result).to.be.true;
+	});
 });
+
+const NETWORK_ID = 4242;
+
+describeWithFrontier(
+	"Frontier RPC (Net with network id)",
+	(context) => {
+		step("should return the configured network id in `net_version`", async function () {
+			expect((await customRequest(context.web3, "net_version", [])).result).to.equal(NETWORK_ID.toString());
+		});
+		step("should keep returning the runtime chain id in `eth_chainId`", async function () {
+			expect(await context.web3.eth.getChainId()).to.equal(CHAIN_ID);
+		});
+	},
+	undefined,
+	[`--network-id=${NETWORK_ID}`]
+);
@@ -74,4 +74,21 @@ describeWithFrontier("Frontier RPC (Precompile)", (context) => {
 		});
 		assert.equal(callResult, message);
 	});
+
+	it("should tell precompiles from accounts with the registry", async () => {
+		const registry = new web3.eth.Contract(
+			[
+				{
+					name: "isPrecompile",
+					type: "function",
+					stateMutability: "view",
+					inputs: [{ name: "a", type: "address" }],
+					outputs: [{ name: "", type: "bool" }],
+				},
+			],
+			"0x0000000000000000000000000000000000000900"
+		);
+		expect(await registry.methods.isPrecompile("0x0000000000000000000000000000000000000004").call()).to.be.true;
+		expect(await registry.methods.isPrecompile(GENESIS_ACCOUNT).call()).to.be.false;
+	});
 });
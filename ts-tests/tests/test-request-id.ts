@@ -0,0 +1,37 @@
+import { expect } from "chai";
+
+import { ETH_BLOCK_GAS_LIMIT, GENESIS_ACCOUNT } from "./config";
+import { customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (Request id)", (context) => {
+	const request = {
+		from: GENESIS_ACCOUNT,
+		gas: `0x${(ETH_BLOCK_GAS_LIMIT * 10 + 1).toString(16)}`,
+		data: "0x",
+	};
+
+	it("should return a request id with eth_call errors", async function () {
+		const first = (await customRequest(context.web3, "eth_call", [request])).error;
+		const second = (await customRequest(context.web3, "eth_call", [request])).error;
+
+		expect(first.data.requestId).to.be.a("string");
+		expect(second.data.requestId).to.be.a("string");
+		expect(first.data.requestId).to.not.be.eq(second.data.requestId);
+	});
+
+	it("should return a request id with eth_estimateGas errors", async function () {
+		const { error } = await customRequest(context.web3, "eth_estimateGas", [request]);
+		expect(error.data.requestId).to.be.a("string");
+	});
+
+	it("should keep the revert data of eth_call errors", async function () {
+		// Reverts with `0xdeadbeef`.
+		const reverter = "0x" + "b2".repeat(20);
+		const { error } = await customRequest(context.web3, "eth_call", [
+			{ to: reverter, gas: "0x100000" },
+			"latest",
+			{ [reverter]: { code: "0x63deadbeef6000526004601cfd" } },
+		]);
+		expect(error.data).to.be.eq("0xdeadbeef");
+	});
+});
@@ -0,0 +1,26 @@
+import { expect } from "chai";
+
+import { customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (Return data)", (context) => {
+	const CALLER = "0x" + "a1".repeat(20);
+	const REVERTER = "0x" + "b2".repeat(20);
+
+	// Reverts with `0xdeadbeef`.
+	const REVERTER_CODE = "0x63deadbeef6000526004601cfd";
+	// `CALL`s the reverter, then returns `RETURNDATASIZE` followed by the return data copied with
+	// `RETURNDATACOPY`.
+	const CALLER_CODE = "0x60006000600060006000" + "73" + REVERTER.slice(2) + "5af1503d6000523d600060203e60406000f3";
+
+	it("should expose the revert data of a failed call", async function () {
+		const { result } = await customRequest(context.web3, "eth_call", [
+			{ to: CALLER, gas: "0x100000" },
+			"latest",
+			{
+				[CALLER]: { code: CALLER_CODE },
+				[REVERTER]: { code: REVERTER_CODE },
+			},
+		]);
+		expect(result).to.be.eq("0x" + "0".repeat(63) + "4" + "deadbeef" + "0".repeat(56));
+	});
+});
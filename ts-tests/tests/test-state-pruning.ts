@@ -0,0 +1,45 @@
+import { expect } from "chai";
+import { step } from "mocha-steps";
+
+import { createAndFinalizeBlock, customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (Archive node)", (context) => {
+	it("should report the genesis as earliest traceable block", async function () {
+		const response = await customRequest(context.web3, "frontier_earliestTraceableBlock", []);
+		expect(response.result).to.be.eq("0x0");
+	});
+});
+
+describeWithFrontier(
+	"Frontier RPC (Pruned node)",
+	(context) => {
+		let earliestBlock: number;
+
+		step("should report the earliest block with state", async function () {
+			this.timeout(20000);
+			for (let i = 0; i < 6; i++) {
+				await createAndFinalizeBlock(context.web3);
+			}
+
+			earliestBlock = parseInt(
+				(await customRequest(context.web3, "frontier_earliestTraceableBlock", [])).result,
+				16
+			);
+			expect(earliestBlock).to.be.gt(1);
+			expect(earliestBlock).to.be.lte(6);
+		});
+
+		step("should reject requests for pruned blocks with the earliest block", async function () {
+			const response = await customRequest(context.web3, "debug_getRawBlock", ["0x1"]);
+			expect(response.error.message).to.contain("use an archive node");
+			expect(response.error.data).to.deep.eq({ requestedBlock: 1, earliestBlock });
+		});
+
+		step("should serve blocks with state", async function () {
+			const response = await customRequest(context.web3, "debug_getRawBlock", ["latest"]);
+			expect(response.result).to.not.be.null;
+		});
+	},
+	undefined,
+	["--state-pruning=2"]
+);
@@ -0,0 +1,41 @@
+import { expect } from "chai";
+
+import { customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (STATICCALL)", (context) => {
+	const CALLER = "0x" + "a1".repeat(20);
+	const PROXY = "0x" + "b2".repeat(20);
+	const WRITER = "0x" + "c3".repeat(20);
+
+	// Stores 1 at slot 0.
+	const WRITER_CODE = "0x600160005500";
+	// `CALL`s the writer and returns whether the call succeeded.
+	const PROXY_CODE = "0x60006000600060006000" + "73" + WRITER.slice(2) + "5af160005260206000f3";
+	// `STATICCALL`s the proxy and returns its output, followed by whether the static call succeeded.
+	const CALLER_CODE = "0x6020600060006000" + "73" + PROXY.slice(2) + "5afa60205260406000f3";
+
+	const stateOverride = {
+		[CALLER]: { code: CALLER_CODE },
+		[PROXY]: { code: PROXY_CODE },
+		[WRITER]: { code: WRITER_CODE },
+	};
+
+	it("should make calls nested in a static call static", async function () {
+		const { result } = await customRequest(context.web3, "eth_call", [
+			{ to: CALLER, gas: "0x100000" },
+			"latest",
+			stateOverride,
+		]);
+		// The write of the nested call fails, the static call itself succeeds.
+		expect(result).to.be.eq("0x" + "0".repeat(64) + "0".repeat(63) + "1");
+	});
+
+	it("should allow the same call outside of a static context", async function () {
+		const { result } = await customRequest(context.web3, "eth_call", [
+			{ to: PROXY, gas: "0x100000" },
+			"latest",
+			stateOverride,
+		]);
+		expect(result).to.be.eq("0x" + "0".repeat(63) + "1");
+	});
+});
@@ -0,0 +1,48 @@
+import { expect } from "chai";
+import { step } from "mocha-steps";
+
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY } from "./config";
+import { customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier(
+	"Frontier RPC (Submission limit)",
+	(context) => {
+		const TEST_ACCOUNT = "0x1111111111111111111111111111111111111111";
+		const rawTransactions: string[] = [];
+
+		before(async function () {
+			for (let nonce = 0; nonce < 3; nonce++) {
+				const tx = await context.web3.eth.accounts.signTransaction(
+					{
+						from: GENESIS_ACCOUNT,
+						to: TEST_ACCOUNT,
+						value: "0x200",
+						gasPrice: "0x3B9ACA00",
+						gas: "0x100000",
+						nonce,
+					},
+					GENESIS_ACCOUNT_PRIVATE_KEY
+				);
+				rawTransactions.push(tx.rawTransaction);
+			}
+		});
+
+		step("should reject a known transaction", async function () {
+			expect((await customRequest(context.web3, "eth_sendRawTransaction", [rawTransactions[0]])).result).to.be.a(
+				"string"
+			);
+			const { error } = await customRequest(context.web3, "eth_sendRawTransaction", [rawTransactions[0]]);
+			expect(error.message).to.be.eq("already known");
+		});
+
+		step("should throttle a sender over its burst", async function () {
+			expect((await customRequest(context.web3, "eth_sendRawTransaction", [rawTransactions[1]])).result).to.be.a(
+				"string"
+			);
+			const { error } = await customRequest(context.web3, "eth_sendRawTransaction", [rawTransactions[2]]);
+			expect(error.code).to.be.eq(-32005);
+		});
+	},
+	undefined,
+	["--rpc-tx-sender-rate=1", "--rpc-tx-sender-burst=2"]
+);
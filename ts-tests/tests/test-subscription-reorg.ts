@@ -0,0 +1,55 @@
+import { expect } from "chai";
+import { step } from "mocha-steps";
+
+import { customRequest, describeWithFrontierWs } from "./util";
+
+describeWithFrontierWs("Frontier RPC (Subscription reorg)", (context) => {
+	let headers = [];
+	// Substrate hash of block 1, used as the fork point.
+	let forkPoint: string;
+
+	// Create a block on top of the given parent (or the best block), without finalizing it.
+	async function createBlock(parentHash: string | null) {
+		const response = await customRequest(context.web3, "engine_createBlock", [true, false, parentHash]);
+		if (!response.result) {
+			throw new Error(`Unexpected result: ${JSON.stringify(response)}`);
+		}
+		await new Promise<void>((resolve) => setTimeout(() => resolve(), 500));
+		return response.result.hash;
+	}
+
+	step("should subscribe to newHeads", async function () {
+		const subscription = context.web3.eth.subscribe("newBlockHeaders", function (error, result) {});
+		await new Promise<void>((resolve) => {
+			subscription.on("connected", function (d: any) {
+				resolve();
+			});
+		});
+		subscription.on("data", function (d: any) {
+			headers.push(d);
+		});
+	}).timeout(20000);
+
+	step("should report a zero reorg depth on the canonical chain", async function () {
+		forkPoint = await createBlock(null);
+		await createBlock(null);
+
+		expect(headers.map((header) => header.number)).to.eql([1, 2]);
+		expect(headers.map((header) => header.reorgDepth)).to.eql([0, 0]);
+	}).timeout(20000);
+
+	step("should notify the missed blocks of the new canonical chain after a reorg", async function () {
+		const replaced = headers[1].hash;
+
+		// The fork only becomes the best chain once it is longer than the current one.
+		const fork = await createBlock(forkPoint);
+		expect(headers.length).to.be.eq(2);
+		await createBlock(fork);
+
+		expect(headers.map((header) => header.number)).to.eql([1, 2, 2, 3]);
+		expect(headers.map((header) => header.reorgDepth)).to.eql([0, 0, 1, 0]);
+		expect(headers[2].parentHash).to.be.eq(headers[0].hash);
+		expect(headers[2].hash).to.not.be.eq(replaced);
+		expect(headers[3].parentHash).to.be.eq(headers[2].hash);
+	}).timeout(20000);
+});
@@ -0,0 +1,57 @@
+import { expect } from "chai";
+import { step } from "mocha-steps";
+
+import { createAndFinalizeBlock, customRequest, describeWithFrontierWs } from "./util";
+
+describeWithFrontierWs("Frontier RPC (Resumable subscription)", (context) => {
+	// Notifications received by subscription id.
+	let notifications: { [subscription: string]: any[] } = {};
+	let resumeToken: string;
+	let subscriptionId: string;
+
+	step("should subscribe to resumable newHeads", async function () {
+		(context.web3.currentProvider as any).on("data", (message: any) => {
+			if (message.method == "eth_subscription") {
+				const { subscription, result } = message.params;
+				(notifications[subscription] = notifications[subscription] || []).push(result);
+			}
+		});
+
+		subscriptionId = (await customRequest(context.web3, "eth_subscribe", ["newHeads", null, true])).result;
+		expect(subscriptionId).to.not.be.empty;
+		resumeToken = subscriptionId;
+	}).timeout(20000);
+
+	step("should notify cursors with the subscription results", async function () {
+		await createAndFinalizeBlock(context.web3);
+		await createAndFinalizeBlock(context.web3);
+
+		const received = notifications[subscriptionId];
+		expect(received.map((notification) => notification.cursor)).to.eql([1, 2]);
+		expect(received.map((notification) => notification.result.number)).to.eql(["0x1", "0x2"]);
+	}).timeout(20000);
+
+	step("should replay the notifications missed since the last seen cursor", async function () {
+		await customRequest(context.web3, "eth_unsubscribe", [subscriptionId]);
+		await createAndFinalizeBlock(context.web3);
+		await createAndFinalizeBlock(context.web3);
+
+		subscriptionId = (await customRequest(context.web3, "eth_resubscribe", [resumeToken, 2])).result;
+		expect(subscriptionId).to.not.be.empty;
+		await createAndFinalizeBlock(context.web3);
+
+		const received = notifications[subscriptionId];
+		expect(received.map((notification) => notification.cursor)).to.eql([3, 4, 5]);
+		expect(received.map((notification) => notification.result.number)).to.eql(["0x3", "0x4", "0x5"]);
+	}).timeout(20000);
+
+	step("should reject an unknown resume token", async function () {
+		const response = await customRequest(context.web3, "eth_resubscribe", ["unknown-token", 0]);
+		expect(response.error.message).to.be.eq("Unknown or expired resume token");
+	});
+
+	step("should only accept the id of the subscription that created it as resume token", async function () {
+		const response = await customRequest(context.web3, "eth_resubscribe", [subscriptionId, 0]);
+		expect(response.error.message).to.be.eq("Unknown or expired resume token");
+	});
+});
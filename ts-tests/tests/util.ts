@@ -58,7 +58,10 @@ export async function createAndFinalizeBlockNowait(web3: Web3) {
 	}
 }
 
-export async function startFrontierNode(provider?: string): Promise<{
+export async function startFrontierNode(
+	provider?: string,
+	additionalArgs: string[] = []
+): Promise<{
 	web3: Web3;
 	binary: ChildProcess;
 	ethersjs: ethers.JsonRpcProvider;
@@ -84,6 +87,7 @@ export async function startFrontierNode(provider?: string): Promise<{
 		`--frontier-backend-type=${FRONTIER_BACKEND_TYPE}`,
 		`--tmp`,
 		`--unsafe-force-node-key-generation`,
+		...additionalArgs,
 	];
 	const binary = spawn(cmd, args);
 
@@ -144,7 +148,12 @@ export async function startFrontierNode(provider?: string): Promise<{
 	return { web3, binary, ethersjs };
 }
 
-export function describeWithFrontier(title: string, cb: (context: { web3: Web3 }) => void, provider?: string) {
+export function describeWithFrontier(
+	title: string,
+	cb: (context: { web3: Web3 }) => void,
+	provider?: string,
+	additionalArgs?: string[]
+) {
 	describe(title, () => {
 		let context: {
 			web3: Web3;
@@ -154,7 +163,7 @@ export function describeWithFrontier(title: string, cb: (context: { web3: Web3 }
 		// Making sure the Frontier node has started
 		before("Starting Frontier Test Node", async function () {
 			this.timeout(SPAWNING_TIME);
-			const init = await startFrontierNode(provider);
+			const init = await startFrontierNode(provider, additionalArgs);
 			context.web3 = init.web3;
 			context.ethersjs = init.ethersjs;
 			binary = init.binary;
@@ -172,3 +181,60 @@ export function describeWithFrontier(title: string, cb: (context: { web3: Web3 }
 export function describeWithFrontierWs(title: string, cb: (context: { web3: Web3 }) => void) {
 	describeWithFrontier(title, cb, "ws");
 }
+
+// Computes the ordered Merkle-Patricia trie root of the given encoded items, keyed by their
+// RLP-encoded index. This is how the transactions and receipts roots of a block are built.
+export function orderedTrieRoot(items: string[]): string {
+	const toNibbles = (hex: string): number[] =>
+		Array.from(ethers.getBytes(hex)).flatMap((byte) => [byte >> 4, byte & 0x0f]);
+	const compactPath = (nibbles: number[], isLeaf: boolean): string => {
+		const flag = isLeaf ? 2 : 0;
+		const prefixed = nibbles.length % 2 ? [flag + 1, ...nibbles] : [flag, 0, ...nibbles];
+		const bytes = [];
+		for (let i = 0; i < prefixed.length; i += 2) {
+			bytes.push((prefixed[i] << 4) | prefixed[i + 1]);
+		}
+		return ethers.hexlify(new Uint8Array(bytes));
+	};
+	// Nodes shorter than 32 bytes are embedded in their parent instead of being hashed.
+	const nodeRef = (node: any): any => {
+		const encoded = ethers.encodeRlp(node);
+		return ethers.getBytes(encoded).length < 32 ? node : ethers.keccak256(encoded);
+	};
+	const buildNode = (entries: [number[], string][]): any => {
+		if (entries.length == 1) {
+			return [compactPath(entries[0][0], true), entries[0][1]];
+		}
+		let shared = 0;
+		while (entries.every(([key]) => key.length > shared && key[shared] == entries[0][0][shared])) {
+			shared++;
+		}
+		if (shared > 0) {
+			const child = buildNode(entries.map(([key, value]) => [key.slice(shared), value]));
+			return [compactPath(entries[0][0].slice(0, shared), false), nodeRef(child)];
+		}
+		const branch: any[] = new Array(17).fill("0x");
+		for (let nibble = 0; nibble < 16; nibble++) {
+			const children = entries
+				.filter(([key]) => key.length > 0 && key[0] == nibble)
+				.map(([key, value]): [number[], string] => [key.slice(1), value]);
+			if (children.length > 0) {
+				branch[nibble] = nodeRef(buildNode(children));
+			}
+		}
+		const terminal = entries.find(([key]) => key.length == 0);
+		if (terminal) {
+			branch[16] = terminal[1];
+		}
+		return branch;
+	};
+
+	if (items.length == 0) {
+		return ethers.keccak256(ethers.encodeRlp("0x"));
+	}
+	const entries = items.map((item, index): [number[], string] => [
+		toNibbles(ethers.encodeRlp(ethers.hexlify(ethers.toBeArray(index)))),
+		item,
+	]);
+	return ethers.keccak256(ethers.encodeRlp(buildNode(entries)));
+}